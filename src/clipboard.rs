@@ -0,0 +1,213 @@
+// ABOUTME: Abstracts over the system clipboard tool used by injector::paste_text.
+// ABOUTME: Picks a provider once at startup by probing the session type and $PATH.
+
+use std::process::Stdio;
+
+use tokio::process::Command;
+use voxkey_ipc::ClipboardProviderChoice;
+
+/// A clipboard tool `paste_text` can drive to set, read, and clear the
+/// system clipboard. Implemented per backend (`wl-clipboard`, `xclip`,
+/// `xsel`) so `paste_text` itself doesn't need to know which is in use.
+pub trait ClipboardProvider: Send + Sync {
+    /// Name used in log messages, e.g. "wl-clipboard".
+    fn name(&self) -> &'static str;
+
+    /// Build the command that sets the clipboard from stdin, optionally
+    /// tagged with a MIME type.
+    fn copy_command(&self, mime_type: Option<&str>) -> Command;
+
+    /// Build the command that reads the clipboard to stdout, optionally of
+    /// a specific MIME type.
+    fn paste_command(&self, mime_type: Option<&str>) -> Command;
+
+    /// Build the command that lists the clipboard's available MIME types,
+    /// or `None` if this provider can't enumerate types (xsel).
+    fn list_types_command(&self) -> Option<Command>;
+
+    /// Build the command that clears the clipboard.
+    fn clear_command(&self) -> Command;
+}
+
+struct WlClipboard;
+
+impl ClipboardProvider for WlClipboard {
+    fn name(&self) -> &'static str {
+        "wl-clipboard"
+    }
+
+    fn copy_command(&self, mime_type: Option<&str>) -> Command {
+        let mut cmd = Command::new("wl-copy");
+        if let Some(mime_type) = mime_type {
+            cmd.args(["--type", mime_type]);
+        }
+        cmd
+    }
+
+    fn paste_command(&self, mime_type: Option<&str>) -> Command {
+        let mut cmd = Command::new("wl-paste");
+        cmd.arg("--no-newline");
+        if let Some(mime_type) = mime_type {
+            cmd.args(["--type", mime_type]);
+        }
+        cmd
+    }
+
+    fn list_types_command(&self) -> Option<Command> {
+        let mut cmd = Command::new("wl-paste");
+        cmd.arg("--list-types");
+        Some(cmd)
+    }
+
+    fn clear_command(&self) -> Command {
+        let mut cmd = Command::new("wl-copy");
+        cmd.arg("--clear");
+        cmd
+    }
+}
+
+struct Xclip;
+
+impl ClipboardProvider for Xclip {
+    fn name(&self) -> &'static str {
+        "xclip"
+    }
+
+    fn copy_command(&self, mime_type: Option<&str>) -> Command {
+        let mut cmd = Command::new("xclip");
+        cmd.args(["-selection", "clipboard"]);
+        if let Some(mime_type) = mime_type {
+            cmd.args(["-t", mime_type]);
+        }
+        cmd.arg("-i");
+        cmd
+    }
+
+    fn paste_command(&self, mime_type: Option<&str>) -> Command {
+        let mut cmd = Command::new("xclip");
+        cmd.args(["-selection", "clipboard"]);
+        if let Some(mime_type) = mime_type {
+            cmd.args(["-t", mime_type]);
+        }
+        cmd.arg("-o");
+        cmd
+    }
+
+    fn list_types_command(&self) -> Option<Command> {
+        let mut cmd = Command::new("xclip");
+        cmd.args(["-selection", "clipboard", "-t", "TARGETS", "-o"]);
+        Some(cmd)
+    }
+
+    fn clear_command(&self) -> Command {
+        // xclip has no dedicated clear flag; copying an empty selection has
+        // the same practical effect for our purposes.
+        self.copy_command(None)
+    }
+}
+
+struct Xsel;
+
+impl ClipboardProvider for Xsel {
+    fn name(&self) -> &'static str {
+        "xsel"
+    }
+
+    fn copy_command(&self, _mime_type: Option<&str>) -> Command {
+        // xsel has no MIME type support, so the hint is ignored.
+        let mut cmd = Command::new("xsel");
+        cmd.args(["--clipboard", "--input"]);
+        cmd
+    }
+
+    fn paste_command(&self, _mime_type: Option<&str>) -> Command {
+        let mut cmd = Command::new("xsel");
+        cmd.args(["--clipboard", "--output"]);
+        cmd
+    }
+
+    fn list_types_command(&self) -> Option<Command> {
+        None
+    }
+
+    fn clear_command(&self) -> Command {
+        let mut cmd = Command::new("xsel");
+        cmd.args(["--clipboard", "--clear"]);
+        cmd
+    }
+}
+
+/// Stdio setup for a command the caller writes bytes to (copy, clear — the
+/// clear commands are always fed an empty write, even on providers whose
+/// clear command ignores stdin, since `Xclip::clear_command` needs it).
+fn with_stdin_pipe(mut cmd: Command) -> Command {
+    cmd.stdin(Stdio::piped()).stdout(Stdio::null()).stderr(Stdio::null());
+    cmd
+}
+
+/// Stdio setup for a command the caller only reads output from (paste,
+/// list-types) via `Command::output`. Stdin is left closed rather than
+/// piped, since nothing will ever write to it.
+fn with_stdout_pipe(mut cmd: Command) -> Command {
+    cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::null());
+    cmd
+}
+
+/// Probe for an available clipboard tool and return a provider for it.
+/// `preferred` pins the choice to a specific backend (still gated on the
+/// binary actually being on `$PATH`); `Auto` probes in the order a Wayland
+/// or X11/XWayland session is actually likely to have a working tool for,
+/// based on whether `$WAYLAND_DISPLAY` is set. Returns `None` if nothing usable
+/// was found, so callers can fall back to keystroke injection.
+pub fn detect_provider(preferred: ClipboardProviderChoice) -> Option<Box<dyn ClipboardProvider>> {
+    let candidates: Vec<(&'static str, fn() -> Box<dyn ClipboardProvider>)> = match preferred {
+        ClipboardProviderChoice::WlClipboard => vec![("wl-copy", || Box::new(WlClipboard))],
+        ClipboardProviderChoice::Xclip => vec![("xclip", || Box::new(Xclip))],
+        ClipboardProviderChoice::Xsel => vec![("xsel", || Box::new(Xsel))],
+        ClipboardProviderChoice::Auto => auto_probe_order(),
+    };
+
+    for (binary, make_provider) in candidates {
+        if which::which(binary).is_ok() {
+            return Some(make_provider());
+        }
+    }
+
+    None
+}
+
+/// Probe order for `ClipboardProviderChoice::Auto`: prefer the tool that
+/// matches the session type the environment actually advertises, then fall
+/// back to the others in case the advertised session type is wrong or the
+/// preferred tool just isn't installed.
+fn auto_probe_order() -> Vec<(&'static str, fn() -> Box<dyn ClipboardProvider>)> {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+
+    let wl_clipboard: (&'static str, fn() -> Box<dyn ClipboardProvider>) =
+        ("wl-copy", || Box::new(WlClipboard));
+    let xclip: (&'static str, fn() -> Box<dyn ClipboardProvider>) =
+        ("xclip", || Box::new(Xclip));
+    let xsel: (&'static str, fn() -> Box<dyn ClipboardProvider>) = ("xsel", || Box::new(Xsel));
+
+    if wayland {
+        vec![wl_clipboard, xclip, xsel]
+    } else {
+        vec![xclip, xsel, wl_clipboard]
+    }
+}
+
+pub(crate) fn copy_command(provider: &dyn ClipboardProvider, mime_type: Option<&str>) -> Command {
+    with_stdin_pipe(provider.copy_command(mime_type))
+}
+
+pub(crate) fn paste_command(provider: &dyn ClipboardProvider, mime_type: Option<&str>) -> Command {
+    with_stdout_pipe(provider.paste_command(mime_type))
+}
+
+pub(crate) fn list_types_command(provider: &dyn ClipboardProvider) -> Option<Command> {
+    provider.list_types_command().map(with_stdout_pipe)
+}
+
+pub(crate) fn clear_command(provider: &dyn ClipboardProvider) -> Command {
+    with_stdin_pipe(provider.clear_command())
+}
@@ -0,0 +1,95 @@
+// ABOUTME: Composes accented/dead-key characters that have no single keysym.
+// ABOUTME: Used by injector::inject_text as a fallback when the active keymap has no key for a character.
+
+use xkbcommon::xkb;
+use xkbcommon::xkb::keysyms;
+
+type DynError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Dead key + base letter pairs for the accented characters dictation is
+/// most likely to produce. Not exhaustive — just what `inject_text`'s
+/// direct-keysym fast path is known to miss for Western European text.
+const DEAD_KEY_SEQUENCES: &[(char, u32, u32)] = &[
+    ('\u{e1}', keysyms::KEY_dead_acute, keysyms::KEY_a), // á
+    ('\u{e9}', keysyms::KEY_dead_acute, keysyms::KEY_e), // é
+    ('\u{ed}', keysyms::KEY_dead_acute, keysyms::KEY_i), // í
+    ('\u{f3}', keysyms::KEY_dead_acute, keysyms::KEY_o), // ó
+    ('\u{fa}', keysyms::KEY_dead_acute, keysyms::KEY_u), // ú
+    ('\u{e0}', keysyms::KEY_dead_grave, keysyms::KEY_a), // à
+    ('\u{e8}', keysyms::KEY_dead_grave, keysyms::KEY_e), // è
+    ('\u{ec}', keysyms::KEY_dead_grave, keysyms::KEY_i), // ì
+    ('\u{f2}', keysyms::KEY_dead_grave, keysyms::KEY_o), // ò
+    ('\u{f9}', keysyms::KEY_dead_grave, keysyms::KEY_u), // ù
+    ('\u{e2}', keysyms::KEY_dead_circumflex, keysyms::KEY_a), // â
+    ('\u{ea}', keysyms::KEY_dead_circumflex, keysyms::KEY_e), // ê
+    ('\u{ee}', keysyms::KEY_dead_circumflex, keysyms::KEY_i), // î
+    ('\u{f4}', keysyms::KEY_dead_circumflex, keysyms::KEY_o), // ô
+    ('\u{fb}', keysyms::KEY_dead_circumflex, keysyms::KEY_u), // û
+    ('\u{e4}', keysyms::KEY_dead_diaeresis, keysyms::KEY_a), // ä
+    ('\u{eb}', keysyms::KEY_dead_diaeresis, keysyms::KEY_e), // ë
+    ('\u{ef}', keysyms::KEY_dead_diaeresis, keysyms::KEY_i), // ï
+    ('\u{f6}', keysyms::KEY_dead_diaeresis, keysyms::KEY_o), // ö
+    ('\u{fc}', keysyms::KEY_dead_diaeresis, keysyms::KEY_u), // ü
+    ('\u{f1}', keysyms::KEY_dead_tilde, keysyms::KEY_n),      // ñ
+    ('\u{e3}', keysyms::KEY_dead_tilde, keysyms::KEY_a),      // ã
+    ('\u{f5}', keysyms::KEY_dead_tilde, keysyms::KEY_o),      // õ
+    ('\u{e7}', keysyms::KEY_dead_cedilla, keysyms::KEY_c),    // ç
+];
+
+/// Drives an xkb compose state to turn a dead key + base letter pair into the
+/// resulting composed keysym, so `inject_text` can fall back to a two-tap
+/// sequence for characters it can't tap directly on the active keymap.
+pub struct ComposeFallback {
+    state: xkb::compose::State,
+}
+
+impl ComposeFallback {
+    /// Build a compose table for `$LC_CTYPE` (falling back to `"C"` if it's
+    /// unset or not valid UTF-8) and the compose state driven from it.
+    /// Returns `Ok(None)` if the locale has no compose table at all.
+    pub fn new() -> Result<Option<Self>, DynError> {
+        let locale = std::env::var("LC_CTYPE").unwrap_or_else(|_| "C".to_string());
+        let context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+
+        let table = xkb::compose::Table::new_from_locale(
+            &context,
+            &locale,
+            xkb::compose::COMPILE_NO_FLAGS,
+        )
+        .or_else(|| {
+            xkb::compose::Table::new_from_locale(&context, "C", xkb::compose::COMPILE_NO_FLAGS)
+        });
+
+        let Some(table) = table else {
+            return Ok(None);
+        };
+
+        let state = xkb::compose::State::new(&table, xkb::compose::STATE_NO_FLAGS);
+        Ok(Some(Self { state }))
+    }
+
+    /// Look up `ch` in the dead-key table and, if present, feed the dead key
+    /// then the base letter through the compose state. Returns the sequence
+    /// of keysyms to tap (dead key, base letter) if the compose state
+    /// actually produces a composed result for it, or `None` if `ch` isn't
+    /// in the table or the locale's compose rules don't compose that pair —
+    /// in which case `inject_text` taps the character's own keysym directly
+    /// if it has one, and drops the character otherwise.
+    pub fn sequence_for_char(&mut self, ch: char) -> Option<[i32; 2]> {
+        let (_, dead, base) = DEAD_KEY_SEQUENCES.iter().find(|(c, _, _)| *c == ch)?;
+
+        self.state.feed(xkb::Keysym::from(*dead));
+        self.state.feed(xkb::Keysym::from(*base));
+
+        let composed = self.state.status() == xkb::compose::Status::Composed
+            && self.state.keysym().map(|sym| sym.raw()) != Some(keysyms::KEY_NoSymbol);
+
+        self.state.reset();
+
+        if composed {
+            Some([*dead as i32, *base as i32])
+        } else {
+            None
+        }
+    }
+}
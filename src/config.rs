@@ -1,10 +1,14 @@
-// ABOUTME: Loads TOML configuration and manages restore token persistence.
+// ABOUTME: Loads layered TOML configuration (default/system/user/env/arg/profile/override) and manages restore token persistence.
 // ABOUTME: Provides defaults for shortcut, transcriber, audio, and persistence settings.
 
+use clap::Parser;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
-pub use voxkey_ipc::{InjectionConfig, TranscriberConfig};
+pub use voxkey_ipc::{InjectionConfig, MqttConfig, StreamingCaptureConfig, TranscriberConfig, VadConfig};
+
+use crate::dbus::SharedState;
+use crate::resample::ResampleQuality;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -15,9 +19,25 @@ pub struct Config {
     #[serde(default)]
     pub injection: InjectionConfig,
     #[serde(default)]
+    pub vad: VadConfig,
+    #[serde(default)]
+    pub mqtt: MqttConfig,
+    #[serde(default)]
+    pub capture: StreamingCaptureConfig,
+    #[serde(default)]
     pub persistence: PersistenceConfig,
     #[serde(default)]
     pub audio: AudioConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub recovery: RecoveryConfig,
+    #[serde(default)]
+    pub watchdog: WatchdogConfig,
+    #[serde(default)]
+    pub latency: LatencyConfig,
+    #[serde(default)]
+    pub control_socket: ControlSocketConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +48,15 @@ pub struct ShortcutConfig {
     pub description: String,
     #[serde(default = "default_shortcut_trigger")]
     pub trigger: String,
+    /// Presses held for less than this are treated as key bounce and
+    /// discarded entirely (no dictation starts). `0` disables debouncing.
+    #[serde(default)]
+    pub min_hold_ms: u64,
+    /// Presses shorter than this (but at least `min_hold_ms`) are a tap that
+    /// flips a latched "continuous dictation" state, rather than a hold.
+    /// `0` disables tap-toggle, leaving plain hold-to-dictate.
+    #[serde(default)]
+    pub tap_toggle_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +71,159 @@ pub struct AudioConfig {
     pub sample_rate: u32,
     #[serde(default = "default_channels")]
     pub channels: u16,
+    /// Name of the preferred input device, as reported by `Recorder::list_input_devices`.
+    /// Falls back to the host's default input device when unset or not found.
+    #[serde(default)]
+    pub device: Option<String>,
+    /// Interpolation quality used when the device's native sample rate
+    /// differs from `sample_rate` and must be resampled.
+    #[serde(default)]
+    pub resample_quality: ResampleQuality,
+}
+
+/// Configuration for the optional metrics HTTP endpoint (see `crate::metrics`,
+/// gated behind the `metrics` cargo feature).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_metrics_listen_addr")]
+    pub listen_addr: String,
+    /// Optional node_exporter-style textfile collector path. When set, the
+    /// daemon periodically renders `crate::metrics::Metrics::render` and
+    /// writes it here via a temp file + rename, independent of `enabled`.
+    #[serde(default)]
+    pub textfile_path: Option<String>,
+}
+
+fn default_metrics_listen_addr() -> String {
+    "127.0.0.1:9090".to_string()
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_addr: default_metrics_listen_addr(),
+            textfile_path: None,
+        }
+    }
+}
+
+/// Configuration for the headless Unix-socket control gateway (see
+/// `crate::control`), a D-Bus-free alternative for session buses that
+/// aren't available (sway/tty/containers).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlSocketConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Falls back to `$XDG_RUNTIME_DIR/voxkey/control.sock` (or
+    /// `/tmp/voxkey/control.sock` if unset) when not given.
+    #[serde(default)]
+    pub socket_path: Option<String>,
+}
+
+impl Default for ControlSocketConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            socket_path: None,
+        }
+    }
+}
+
+/// Backoff policy for `run_with_recovery`'s portal/session reconnection loop.
+/// Delay grows as `min(base_secs * 2^(attempt - 1), cap_secs)` with jitter of
+/// up to `±20%` added, similar to how librespot reconnects its session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryConfig {
+    #[serde(default = "default_recovery_base_secs")]
+    pub base_secs: f64,
+    #[serde(default = "default_recovery_cap_secs")]
+    pub cap_secs: f64,
+    /// Consecutive failures after which a terminal error is surfaced via
+    /// `set_last_error_and_publish`. `0` disables the terminal error and the
+    /// daemon retries at `cap_secs` forever.
+    #[serde(default = "default_recovery_max_attempts")]
+    pub max_attempts: u32,
+    /// If true, the daemon exits once `max_attempts` consecutive failures
+    /// have been reached instead of continuing to retry at `cap_secs`.
+    #[serde(default)]
+    pub exit_after_max_attempts: bool,
+}
+
+fn default_recovery_base_secs() -> f64 {
+    2.0
+}
+
+fn default_recovery_cap_secs() -> f64 {
+    60.0
+}
+
+fn default_recovery_max_attempts() -> u32 {
+    10
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self {
+            base_secs: default_recovery_base_secs(),
+            cap_secs: default_recovery_cap_secs(),
+            max_attempts: default_recovery_max_attempts(),
+            exit_after_max_attempts: false,
+        }
+    }
+}
+
+/// Recording-start watchdog: if no audio has flowed from the capture device
+/// within `timeout_secs` of starting, the daemon aborts the recording rather
+/// than waiting on a dead stream. Catches the Wayland/PipeWire failure mode
+/// where the stream opens but the device never delivers samples.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchdogConfig {
+    #[serde(default = "default_watchdog_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_watchdog_timeout_secs")]
+    pub timeout_secs: f64,
+}
+
+fn default_watchdog_enabled() -> bool {
+    true
+}
+
+fn default_watchdog_timeout_secs() -> f64 {
+    2.0
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_watchdog_enabled(),
+            timeout_secs: default_watchdog_timeout_secs(),
+        }
+    }
+}
+
+/// End-to-end latency tracking for the streaming path (see
+/// `streaming::run_streaming_session`): the delay between when audio was
+/// captured and when the resulting text was injected. A warning is logged
+/// whenever a single injection's latency exceeds `warn_threshold_ms`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyConfig {
+    #[serde(default = "default_latency_warn_threshold_ms")]
+    pub warn_threshold_ms: u64,
+}
+
+fn default_latency_warn_threshold_ms() -> u64 {
+    1500
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            warn_threshold_ms: default_latency_warn_threshold_ms(),
+        }
+    }
 }
 
 fn default_shortcut_id() -> String {
@@ -79,6 +261,8 @@ impl Default for ShortcutConfig {
             id: default_shortcut_id(),
             description: default_shortcut_description(),
             trigger: default_shortcut_trigger(),
+            min_hold_ms: 0,
+            tap_toggle_ms: 0,
         }
     }
 }
@@ -96,6 +280,8 @@ impl Default for AudioConfig {
         Self {
             sample_rate: default_sample_rate(),
             channels: default_channels(),
+            device: None,
+            resample_quality: ResampleQuality::default(),
         }
     }
 }
@@ -106,8 +292,16 @@ impl Default for Config {
             shortcut: ShortcutConfig::default(),
             transcriber: TranscriberConfig::default(),
             injection: InjectionConfig::default(),
+            vad: VadConfig::default(),
+            mqtt: MqttConfig::default(),
+            capture: StreamingCaptureConfig::default(),
             persistence: PersistenceConfig::default(),
             audio: AudioConfig::default(),
+            metrics: MetricsConfig::default(),
+            recovery: RecoveryConfig::default(),
+            watchdog: WatchdogConfig::default(),
+            latency: LatencyConfig::default(),
+            control_socket: ControlSocketConfig::default(),
         }
     }
 }
@@ -126,43 +320,577 @@ struct LegacyConfig {
     transcriber: Option<LegacyTranscriberFields>,
 }
 
+/// Where a resolved configuration layer came from, in increasing precedence order.
+/// Mirrors jj's `ConfigSource` so each setting's origin can be reported back to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigSource {
+    Default,
+    System,
+    User,
+    Env,
+    Arg,
+    Profile,
+    Override,
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ConfigSource::Default => "default",
+            ConfigSource::System => "system",
+            ConfigSource::User => "user",
+            ConfigSource::Env => "env",
+            ConfigSource::Arg => "arg",
+            ConfigSource::Profile => "profile",
+            ConfigSource::Override => "override",
+        };
+        write!(f, "{label}")
+    }
+}
+
+/// Process-wide CLI flags: an explicit `--config PATH` file layer plus
+/// one-off `ConfigOverride` flags for individual settings.
+#[derive(Parser, Debug, Default)]
+#[command(name = "voxkey")]
+pub struct Cli {
+    /// Path to a config file, merged in as the highest-precedence file layer.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// Named `[profiles.<name>]` overlay to apply on top of the merged file
+    /// layers. Falls back to `$VOXKEY_PROFILE`, then the config's
+    /// `default_profile` key, when unset.
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    #[command(flatten)]
+    pub overrides: ConfigOverride,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// A subcommand that talks to an already-running daemon instead of starting
+/// one. When present, `main` dispatches here and returns without ever calling
+/// [`Config::load`] or bringing up the D-Bus/portal/recording machinery.
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Send a request to a running daemon over its control socket (see
+    /// `crate::control`) and print the reply.
+    Ctl {
+        #[command(subcommand)]
+        action: CtlAction,
+    },
+}
+
+/// One request sendable over the control socket from the command line.
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum CtlAction {
+    /// Print the daemon's current state (Idle, Recording, ...).
+    GetState,
+    /// Print the daemon's resolved configuration as JSON.
+    GetConfig,
+    /// Rebind the dictation shortcut to a new trigger, e.g. "<Super>space".
+    SetShortcut { trigger: String },
+    /// Queue a model download.
+    DownloadModel {
+        model_name: String,
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long)]
+        sha256: Option<String>,
+    },
+    /// Ask the daemon to shut down.
+    Quit,
+    /// Start dictation, as if the shortcut had just been pressed.
+    Start,
+    /// Stop dictation, as if the shortcut had just been released.
+    Stop,
+}
+
+/// One-off overrides for individual config leaves, given directly on the
+/// command line. Applied after all file-based layers, so these always win.
+#[derive(clap::Args, Debug, Default, Clone)]
+pub struct ConfigOverride {
+    #[arg(long = "transcriber.provider")]
+    pub transcriber_provider: Option<String>,
+    #[arg(long = "transcriber.mistral.model")]
+    pub transcriber_mistral_model: Option<String>,
+    #[arg(long = "audio.sample-rate")]
+    pub audio_sample_rate: Option<u32>,
+    #[arg(long = "shortcut.trigger")]
+    pub shortcut_trigger: Option<String>,
+}
+
+impl ConfigOverride {
+    /// Record this override's `Some(..)` fields into `leaves`, so `Config::annotated`
+    /// reports them as `ConfigSource::Override` like any other layer.
+    fn annotate(&self, leaves: &mut std::collections::BTreeMap<Vec<String>, (toml::Value, ConfigSource)>) {
+        if let Some(provider) = &self.transcriber_provider {
+            leaves.insert(
+                vec!["transcriber".to_string(), "provider".to_string()],
+                (toml::Value::String(provider.clone()), ConfigSource::Override),
+            );
+        }
+        if let Some(model) = &self.transcriber_mistral_model {
+            leaves.insert(
+                vec!["transcriber".to_string(), "mistral".to_string(), "model".to_string()],
+                (toml::Value::String(model.clone()), ConfigSource::Override),
+            );
+        }
+        if let Some(sample_rate) = self.audio_sample_rate {
+            leaves.insert(
+                vec!["audio".to_string(), "sample_rate".to_string()],
+                (toml::Value::Integer(sample_rate as i64), ConfigSource::Override),
+            );
+        }
+        if let Some(trigger) = &self.shortcut_trigger {
+            leaves.insert(
+                vec!["shortcut".to_string(), "trigger".to_string()],
+                (toml::Value::String(trigger.clone()), ConfigSource::Override),
+            );
+        }
+    }
+}
+
+/// Applies only the `Some(..)` fields of a [`ConfigOverride`] onto a config section.
+pub trait Merge {
+    fn merge(&mut self, overrides: &ConfigOverride);
+}
+
+impl Merge for Config {
+    fn merge(&mut self, overrides: &ConfigOverride) {
+        self.shortcut.merge(overrides);
+        self.transcriber.merge(overrides);
+        self.audio.merge(overrides);
+    }
+}
+
+impl Merge for ShortcutConfig {
+    fn merge(&mut self, overrides: &ConfigOverride) {
+        if let Some(trigger) = &overrides.shortcut_trigger {
+            self.trigger = trigger.clone();
+        }
+    }
+}
+
+impl Merge for TranscriberConfig {
+    fn merge(&mut self, overrides: &ConfigOverride) {
+        if let Some(provider) = &overrides.transcriber_provider {
+            let parsed = serde_json::from_value::<voxkey_ipc::TranscriberProvider>(
+                serde_json::Value::String(provider.clone()),
+            );
+            match parsed {
+                Ok(provider) => self.provider = provider,
+                Err(e) => tracing::warn!("Ignoring invalid --transcriber.provider '{provider}': {e}"),
+            }
+        }
+        if let Some(model) = &overrides.transcriber_mistral_model {
+            self.mistral.model = model.clone();
+        }
+    }
+}
+
+impl Merge for AudioConfig {
+    fn merge(&mut self, overrides: &ConfigOverride) {
+        if let Some(sample_rate) = overrides.audio_sample_rate {
+            self.sample_rate = sample_rate;
+        }
+    }
+}
+
+/// A single resolved config leaf together with the layer that last set it.
+/// Backs a future `voxkey config list` command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotatedValue {
+    pub path: Vec<String>,
+    pub value: toml::Value,
+    pub source: ConfigSource,
+}
+
+/// Failure to load configuration, distinguishing an unreadable file from
+/// broken TOML so callers can tell "file missing" (fall back to defaults)
+/// apart from "file broken" (keep the last-good config, don't crash).
+#[derive(Debug)]
+pub enum ConfigError {
+    /// A layer's file exists but couldn't be read (e.g. permission denied).
+    /// A simply-missing file is `Ok(None)`, not this variant.
+    Io { path: PathBuf, source: std::io::Error },
+    /// A layer's TOML failed to parse, or the merged result didn't match the
+    /// `Config` schema. `message` includes a line/column pointer when available.
+    Parse { path: String, message: String },
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => write!(f, "failed to read {}: {source}", path.display()),
+            ConfigError::Parse { path, message } => write!(f, "{path}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            ConfigError::Parse { .. } => None,
+        }
+    }
+}
+
+/// Recursively merge `overlay` into `base`, key-by-key, so a table in `overlay`
+/// only replaces the leaves it actually sets rather than clobbering its siblings.
+/// Non-table values (including arrays) are overwritten wholesale.
+fn merge_toml_values(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base_table), toml::Value::Table(overlay_table)) => {
+            for (key, overlay_value) in overlay_table {
+                match base_table.get_mut(&key) {
+                    Some(base_value) => merge_toml_values(base_value, overlay_value),
+                    None => {
+                        base_table.insert(key, overlay_value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Walk a resolved `toml::Value` tree, yielding `(dotted path, leaf value)` pairs.
+fn flatten_leaves(value: &toml::Value, prefix: Vec<String>) -> Vec<(Vec<String>, toml::Value)> {
+    match value {
+        toml::Value::Table(table) => table
+            .iter()
+            .flat_map(|(key, v)| {
+                let mut path = prefix.clone();
+                path.push(key.clone());
+                flatten_leaves(v, path)
+            })
+            .collect(),
+        other => vec![(prefix, other.clone())],
+    }
+}
+
+/// Build a nested `toml::Value` overlay from every `VOXKEY__`-prefixed
+/// environment variable, double-underscore-delimited into a config path —
+/// e.g. `VOXKEY__TRANSCRIBER__MISTRAL__API_KEY` becomes
+/// `transcriber.mistral.api_key`. Returns `None` if no such variable is set.
+fn structured_env_overlay() -> Option<toml::Value> {
+    let mut root = toml::value::Table::new();
+    let mut any = false;
+    for (key, value) in std::env::vars() {
+        let Some(rest) = key.strip_prefix("VOXKEY__") else { continue };
+        let path: Vec<String> = rest.split("__").map(|s| s.to_lowercase()).collect();
+        if path.iter().any(|segment| segment.is_empty()) {
+            continue;
+        }
+        insert_env_path(&mut root, &path, coerce_env_value(&value));
+        any = true;
+    }
+    any.then(|| toml::Value::Table(root))
+}
+
+/// Insert `value` at `path` within `table`, creating intermediate tables as needed.
+fn insert_env_path(table: &mut toml::value::Table, path: &[String], value: toml::Value) {
+    let (head, rest) = path.split_first().expect("path is non-empty");
+    if rest.is_empty() {
+        table.insert(head.clone(), value);
+        return;
+    }
+    let entry = table
+        .entry(head.clone())
+        .or_insert_with(|| toml::Value::Table(Default::default()));
+    if let Some(nested) = entry.as_table_mut() {
+        insert_env_path(nested, rest, value);
+    }
+}
+
+/// Coerce a raw environment variable string into the most specific TOML
+/// scalar it parses as (bool, then integer, then float), falling back to a
+/// plain string. Lets e.g. `VOXKEY__AUDIO__SAMPLE_RATE=48000` deserialize
+/// into the `u32` field instead of failing as a string-typed mismatch.
+fn coerce_env_value(raw: &str) -> toml::Value {
+    if let Ok(b) = raw.parse::<bool>() {
+        toml::Value::Boolean(b)
+    } else if let Ok(i) = raw.parse::<i64>() {
+        toml::Value::Integer(i)
+    } else if let Ok(f) = raw.parse::<f64>() {
+        toml::Value::Float(f)
+    } else {
+        toml::Value::String(raw.to_string())
+    }
+}
+
 impl Config {
-    /// Load configuration from the standard config file location.
-    /// Falls back to defaults if the file doesn't exist.
-    /// Migrates old-format `[transcriber]` (bare `command`/`args`) to the
-    /// provider-based structure.
-    pub fn load() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        let config_path = Self::config_file_path();
-        if !config_path.exists() {
-            return Ok(Config::default());
+    /// Load configuration by merging, lowest to highest precedence: built-in
+    /// defaults, the system-wide file, the per-user XDG file, an environment
+    /// override, an explicit `--config PATH` argument, any
+    /// `VOXKEY__SECTION__KEY`-style variable, the active profile, and any one-off
+    /// `ConfigOverride` flags. Migrates old-format `[transcriber]` (bare
+    /// `command`/`args`) to the provider-based structure on each file layer
+    /// before it is merged in.
+    pub fn load() -> Result<Self, ConfigError> {
+        let cli = Cli::parse();
+        let mut config = Self::merge_layers(Self::layer_sources(cli.config.as_deref())?, cli.profile.as_deref())?;
+        config.merge(&cli.overrides);
+        Ok(config)
+    }
+
+    /// Like [`Config::load`], but forces `name` as the active profile,
+    /// ignoring `$VOXKEY_PROFILE` and the config's `default_profile` key.
+    pub fn load_profile(name: &str) -> Result<Self, ConfigError> {
+        let cli = Cli::parse();
+        let mut config = Self::merge_layers(Self::layer_sources(cli.config.as_deref())?, Some(name))?;
+        config.merge(&cli.overrides);
+        Ok(config)
+    }
+
+    /// Like [`Config::load`], but also reports which layer last set each leaf.
+    pub fn annotated() -> Result<Vec<AnnotatedValue>, ConfigError> {
+        let cli = Cli::parse();
+        let mut annotated =
+            Self::annotate_layers(&Self::layer_sources(cli.config.as_deref())?, cli.profile.as_deref())?;
+        let mut leaves: std::collections::BTreeMap<Vec<String>, (toml::Value, ConfigSource)> =
+            annotated.drain(..).map(|v| (v.path, (v.value, v.source))).collect();
+        cli.overrides.annotate(&mut leaves);
+        Ok(leaves
+            .into_iter()
+            .map(|(path, (value, source))| AnnotatedValue { path, value, source })
+            .collect())
+    }
+
+    /// Read one layer's file, distinguishing "doesn't exist" (fine, the layer
+    /// is simply absent) from any other IO failure (e.g. permission denied),
+    /// which is surfaced rather than silently treated as a missing layer.
+    fn read_layer(path: &Path) -> Result<Option<String>, ConfigError> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(source) => Err(ConfigError::Io { path: path.to_path_buf(), source }),
         }
-        let contents = std::fs::read_to_string(&config_path)?;
-        Self::load_from_str(&contents)
     }
 
-    fn load_from_str(contents: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+    /// Read each layer's raw contents in precedence order, from its on-disk or
+    /// environment source. A layer is `None` when its source is absent.
+    fn layer_sources(explicit_path: Option<&Path>) -> Result<Vec<(ConfigSource, Option<String>)>, ConfigError> {
+        let arg_layer = match explicit_path {
+            Some(path) => Self::read_layer(path)?,
+            None => None,
+        };
+        Ok(vec![
+            (ConfigSource::System, Self::read_layer(&Self::system_config_path())?),
+            (ConfigSource::User, Self::read_layer(&Self::config_file_path())?),
+            (ConfigSource::Env, std::env::var("VOXKEY_CONFIG_TOML").ok()),
+            (ConfigSource::Arg, arg_layer),
+        ])
+    }
+
+    fn merge_layers(layers: Vec<(ConfigSource, Option<String>)>, explicit_profile: Option<&str>) -> Result<Self, ConfigError> {
+        let mut merged = toml::Value::try_from(Config::default()).map_err(|e| ConfigError::Parse {
+            path: Self::layer_path_hint(ConfigSource::Default),
+            message: e.to_string(),
+        })?;
+        for (source, contents) in layers {
+            let Some(contents) = contents else { continue };
+            merge_toml_values(&mut merged, Self::migrate_layer(&contents, source)?);
+        }
+
+        if let Some(env_overlay) = structured_env_overlay() {
+            merge_toml_values(&mut merged, env_overlay);
+        }
+
+        if let Some(name) = Self::resolve_profile_name(&merged, explicit_profile) {
+            match Self::profile_table(&merged, &name) {
+                Some(profile) => merge_toml_values(&mut merged, profile),
+                None => tracing::warn!("Profile '{name}' not found in config; ignoring"),
+            }
+        }
+
+        // Each layer was already validated against the typed schema in
+        // `migrate_layer`, with a real span into the file it came from. This
+        // final deserialize is just a backstop for errors only the merged
+        // shape can produce (e.g. a profile overlay introducing a bad
+        // combination) — `merged` is a `toml::Value` by this point, so it
+        // has no span to report.
+        Config::deserialize(merged).map_err(|e| ConfigError::Parse {
+            path: "merged configuration".to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    fn annotate_layers(
+        layers: &[(ConfigSource, Option<String>)],
+        explicit_profile: Option<&str>,
+    ) -> Result<Vec<AnnotatedValue>, ConfigError> {
+        let mut leaves: std::collections::BTreeMap<Vec<String>, (toml::Value, ConfigSource)> =
+            std::collections::BTreeMap::new();
+
+        let mut merged = toml::Value::try_from(Config::default()).map_err(|e| ConfigError::Parse {
+            path: Self::layer_path_hint(ConfigSource::Default),
+            message: e.to_string(),
+        })?;
+        for (path, value) in flatten_leaves(&merged, Vec::new()) {
+            leaves.insert(path, (value, ConfigSource::Default));
+        }
+
+        for (source, contents) in layers {
+            let Some(contents) = contents else { continue };
+            let layer = Self::migrate_layer(contents, *source)?;
+            merge_toml_values(&mut merged, layer.clone());
+            for (path, value) in flatten_leaves(&layer, Vec::new()) {
+                leaves.insert(path, (value, *source));
+            }
+        }
+
+        if let Some(env_overlay) = structured_env_overlay() {
+            merge_toml_values(&mut merged, env_overlay.clone());
+            for (path, value) in flatten_leaves(&env_overlay, Vec::new()) {
+                leaves.insert(path, (value, ConfigSource::Env));
+            }
+        }
+
+        if let Some(name) = Self::resolve_profile_name(&merged, explicit_profile) {
+            match Self::profile_table(&merged, &name) {
+                Some(profile) => {
+                    for (path, value) in flatten_leaves(&profile, Vec::new()) {
+                        leaves.insert(path, (value, ConfigSource::Profile));
+                    }
+                }
+                None => tracing::warn!("Profile '{name}' not found in config; ignoring"),
+            }
+        }
+
+        Ok(leaves
+            .into_iter()
+            .map(|(path, (value, source))| AnnotatedValue { path, value, source })
+            .collect())
+    }
+
+    /// The active profile name, in precedence order: an explicit `--profile`
+    /// (or forced `load_profile` name), then `$VOXKEY_PROFILE`, then the
+    /// merged config's own `default_profile` key.
+    fn resolve_profile_name(merged: &toml::Value, explicit: Option<&str>) -> Option<String> {
+        explicit
+            .map(|s| s.to_string())
+            .or_else(|| std::env::var("VOXKEY_PROFILE").ok())
+            .or_else(|| merged.get("default_profile")?.as_str().map(|s| s.to_string()))
+    }
+
+    /// The `[profiles.<name>]` table, if the merged config defines one by that name.
+    fn profile_table(merged: &toml::Value, name: &str) -> Option<toml::Value> {
+        merged.get("profiles")?.get(name).cloned()
+    }
+
+    fn load_from_str(contents: &str) -> Result<Self, ConfigError> {
+        Self::merge_layers(vec![(ConfigSource::User, Some(contents.to_string()))], None)
+    }
+
+    /// A human-readable origin for a layer, used in parse-error messages.
+    fn layer_path_hint(source: ConfigSource) -> String {
+        match source {
+            ConfigSource::Default => "<built-in defaults>".to_string(),
+            ConfigSource::System => Self::system_config_path().display().to_string(),
+            ConfigSource::User => Self::config_file_path().display().to_string(),
+            ConfigSource::Env => "$VOXKEY_CONFIG_TOML".to_string(),
+            ConfigSource::Arg => "--config file".to_string(),
+            ConfigSource::Profile => "active profile".to_string(),
+            ConfigSource::Override => "command-line override".to_string(),
+        }
+    }
+
+    /// Turn a `toml::de::Error` into a `ConfigError::Parse` that points at the
+    /// offending line of `contents`, using the error's byte span when toml
+    /// provides one.
+    fn render_toml_error(path_hint: &str, contents: &str, err: toml::de::Error) -> ConfigError {
+        let message = match err.span() {
+            Some(span) => {
+                let (line, column) = Self::line_col(contents, span.start);
+                let snippet = contents.lines().nth(line.saturating_sub(1)).unwrap_or("");
+                format!(
+                    "{err}\n  --> line {line}, column {column}\n   | {snippet}\n   | {pad}^",
+                    pad = " ".repeat(column.saturating_sub(1)),
+                )
+            }
+            None => err.to_string(),
+        };
+        ConfigError::Parse { path: path_hint.to_string(), message }
+    }
+
+    /// 1-based (line, column) of `byte_offset` within `contents`.
+    fn line_col(contents: &str, byte_offset: usize) -> (usize, usize) {
+        let mut line = 1;
+        let mut column = 1;
+        for (i, ch) in contents.char_indices() {
+            if i >= byte_offset {
+                break;
+            }
+            if ch == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        (line, column)
+    }
+
+    /// Parse one layer's raw TOML and migrate legacy bare `command`/`args`
+    /// fields under `[transcriber]` into `[transcriber.whisper_cpp]`, before
+    /// this layer is merged with any other.
+    fn migrate_layer(contents: &str, source: ConfigSource) -> Result<toml::Value, ConfigError> {
         // The new TranscriberConfig silently ignores unknown fields like
-        // bare `command`/`args`, so this always succeeds — but loses custom
-        // whisper-cpp settings from old configs. We detect and migrate them.
-        let mut config: Config = toml::from_str(contents)?;
+        // bare `command`/`args`, so parsing always succeeds — but loses
+        // custom whisper-cpp settings from old configs. We detect and
+        // migrate them here, before this layer is merged into the others.
+        let path_hint = Self::layer_path_hint(source);
+        let mut value: toml::Value = toml::from_str(contents)
+            .map_err(|e| Self::render_toml_error(&path_hint, contents, e))?;
+
+        // Validate this layer against the typed schema here, while `contents`
+        // is still the original string and `toml::de::Error` can still carry
+        // a byte span — every field is `#[serde(default)]`, so a legitimate
+        // partial layer deserializes fine, and a bad value (unknown enum
+        // variant, wrong type, etc) is reported against the file the user
+        // actually wrote instead of the merged, span-less result further
+        // down the pipeline.
+        toml::from_str::<Config>(contents).map_err(|e| Self::render_toml_error(&path_hint, contents, e))?;
 
-        // Check for legacy bare command/args under [transcriber]
         if let Ok(legacy) = toml::from_str::<LegacyConfig>(contents) {
             if let Some(legacy_t) = legacy.transcriber {
                 let has_legacy = legacy_t.command.is_some() || legacy_t.args.is_some();
                 if has_legacy {
+                    let transcriber = value
+                        .as_table_mut()
+                        .unwrap()
+                        .entry("transcriber")
+                        .or_insert_with(|| toml::Value::Table(Default::default()));
+                    let whisper_cpp = transcriber
+                        .as_table_mut()
+                        .unwrap()
+                        .entry("whisper_cpp")
+                        .or_insert_with(|| toml::Value::Table(Default::default()));
+                    let whisper_cpp_table = whisper_cpp.as_table_mut().unwrap();
                     if let Some(cmd) = legacy_t.command {
-                        config.transcriber.whisper_cpp.command = cmd;
+                        whisper_cpp_table.insert("command".to_string(), toml::Value::String(cmd));
                     }
                     if let Some(args) = legacy_t.args {
-                        config.transcriber.whisper_cpp.args = args;
+                        whisper_cpp_table.insert(
+                            "args".to_string(),
+                            toml::Value::Array(args.into_iter().map(toml::Value::String).collect()),
+                        );
                     }
                     tracing::info!("Migrated legacy transcriber config format");
                 }
             }
         }
 
-        Ok(config)
+        Ok(value)
+    }
+
+    fn system_config_path() -> PathBuf {
+        PathBuf::from("/etc/voxkey/config.toml")
     }
 
     fn config_file_path() -> PathBuf {
@@ -193,6 +921,82 @@ impl Config {
         }
         PathBuf::from(&self.persistence.token_path)
     }
+
+    /// Resolve the control-socket path: `control_socket.socket_path` if set,
+    /// else `$XDG_RUNTIME_DIR/voxkey/control.sock`, else `/tmp/voxkey/control.sock`.
+    pub fn control_socket_path(&self) -> PathBuf {
+        if let Some(path) = &self.control_socket.socket_path {
+            return PathBuf::from(path);
+        }
+        let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
+        Path::new(&runtime_dir).join("voxkey").join("control.sock")
+    }
+
+    /// Resolve the path to the append-only transcript history JSONL file.
+    /// Falls back to `~/.local/share/voxkey/history.jsonl` when unset.
+    pub fn history_path(&self) -> PathBuf {
+        let data_dir = std::env::var("XDG_DATA_HOME").unwrap_or_else(|_| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| "~".to_string());
+            format!("{home}/.local/share")
+        });
+        Path::new(&data_dir).join("voxkey").join("history.jsonl")
+    }
+
+    /// Resolve the destination directory for streaming session WAV captures.
+    /// Falls back to `~/.local/share/voxkey/captures` when unset.
+    pub fn capture_directory(&self) -> PathBuf {
+        if !self.capture.directory.is_empty() {
+            return PathBuf::from(&self.capture.directory);
+        }
+        let data_dir = std::env::var("XDG_DATA_HOME").unwrap_or_else(|_| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| "~".to_string());
+            format!("{home}/.local/share")
+        });
+        Path::new(&data_dir).join("voxkey").join("captures")
+    }
+
+    /// Install a SIGUSR1 handler that re-runs [`Config::load`] and publishes the
+    /// result to `shared` on success. Invalid TOML (or any other load error) is
+    /// logged and the last-good config is kept — a bad edit to `config.toml`
+    /// must never crash the daemon or touch the restore token, which `load()`
+    /// never reads or writes in the first place.
+    pub fn reload_on_signal(shared: SharedState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut sigusr1 = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::user_defined1())?;
+        tokio::spawn(async move {
+            while sigusr1.recv().await.is_some() {
+                tracing::info!("Received SIGUSR1, reloading configuration");
+                match Config::load() {
+                    Ok(new_config) => {
+                        let changed = Self::changed_sections(&shared.config(), &new_config);
+                        if changed.is_empty() {
+                            tracing::info!("Configuration reloaded, no changes detected");
+                        } else {
+                            tracing::info!("Configuration reloaded, changed sections: {}", changed.join(", "));
+                        }
+                        shared.update_config(new_config);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to reload configuration, keeping last-good config: {e}");
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Names of the top-level sections that differ between `old` and `new`, for logging.
+    fn changed_sections(old: &Config, new: &Config) -> Vec<String> {
+        let (Ok(toml::Value::Table(old_table)), Ok(toml::Value::Table(new_table))) =
+            (toml::Value::try_from(old), toml::Value::try_from(new))
+        else {
+            return Vec::new();
+        };
+        new_table
+            .keys()
+            .filter(|key| old_table.get(*key) != new_table.get(*key))
+            .cloned()
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -259,4 +1063,273 @@ sample_rate = 48000
         assert_eq!(config.transcriber.whisper_cpp.command, "my-whisper");
         assert_eq!(config.audio.sample_rate, 48000);
     }
+
+    #[test]
+    fn later_layers_override_earlier_ones_without_clobbering_siblings() {
+        let system = r#"
+[transcriber]
+provider = "mistral"
+
+[transcriber.mistral]
+api_key = "system-key"
+model = "voxtral-mini-2602"
+
+[audio]
+sample_rate = 48000
+"#;
+        let user = r#"
+[transcriber.mistral]
+api_key = "user-key"
+"#;
+        let config = Config::merge_layers(
+            vec![
+                (ConfigSource::System, Some(system.to_string())),
+                (ConfigSource::User, Some(user.to_string())),
+            ],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(config.transcriber.provider, TranscriberProvider::Mistral);
+        assert_eq!(config.transcriber.mistral.api_key, "user-key");
+        assert_eq!(config.transcriber.mistral.model, "voxtral-mini-2602");
+        assert_eq!(config.audio.sample_rate, 48000);
+    }
+
+    #[test]
+    fn arg_layer_takes_precedence_over_env_layer() {
+        let env = r#"
+[shortcut]
+trigger = "<Control>e"
+"#;
+        let arg = r#"
+[shortcut]
+trigger = "<Control>a"
+"#;
+        let config = Config::merge_layers(
+            vec![
+                (ConfigSource::Env, Some(env.to_string())),
+                (ConfigSource::Arg, Some(arg.to_string())),
+            ],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(config.shortcut.trigger, "<Control>a");
+    }
+
+    #[test]
+    fn migration_runs_per_layer_before_merge() {
+        let system = r#"
+[transcriber]
+command = "system-whisper"
+args = ["--system"]
+"#;
+        let user = r#"
+[transcriber]
+command = "user-whisper"
+"#;
+        let config = Config::merge_layers(
+            vec![
+                (ConfigSource::System, Some(system.to_string())),
+                (ConfigSource::User, Some(user.to_string())),
+            ],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(config.transcriber.whisper_cpp.command, "user-whisper");
+        assert_eq!(config.transcriber.whisper_cpp.args, vec!["--system"]);
+    }
+
+    #[test]
+    fn explicit_profile_overrides_top_level_settings() {
+        let user = r#"
+[audio]
+sample_rate = 16000
+
+[profiles.accurate]
+transcriber = { provider = "mistral" }
+
+[profiles.accurate.audio]
+sample_rate = 48000
+"#;
+        let config = Config::merge_layers(vec![(ConfigSource::User, Some(user.to_string()))], Some("accurate"))
+            .unwrap();
+
+        assert_eq!(config.transcriber.provider, TranscriberProvider::Mistral);
+        assert_eq!(config.audio.sample_rate, 48000);
+    }
+
+    #[test]
+    fn default_profile_key_selects_profile_when_none_given_explicitly() {
+        let user = r#"
+default_profile = "fast"
+
+[profiles.fast]
+audio = { sample_rate = 8000 }
+"#;
+        let config = Config::merge_layers(vec![(ConfigSource::User, Some(user.to_string()))], None).unwrap();
+
+        assert_eq!(config.audio.sample_rate, 8000);
+    }
+
+    #[test]
+    fn unknown_profile_name_is_ignored_with_defaults_kept() {
+        let user = r#"
+[profiles.fast]
+audio = { sample_rate = 8000 }
+"#;
+        let config =
+            Config::merge_layers(vec![(ConfigSource::User, Some(user.to_string()))], Some("nonexistent")).unwrap();
+
+        assert_eq!(config.audio.sample_rate, default_sample_rate());
+    }
+
+    #[test]
+    fn structured_env_var_overrides_a_nested_string_field() {
+        std::env::set_var("VOXKEY__SHORTCUT__DESCRIPTION", "Env Dictate");
+        let result = Config::load_from_str("");
+        std::env::remove_var("VOXKEY__SHORTCUT__DESCRIPTION");
+
+        assert_eq!(result.unwrap().shortcut.description, "Env Dictate");
+    }
+
+    #[test]
+    fn structured_env_var_with_unparseable_value_is_a_parse_error() {
+        std::env::set_var("VOXKEY__VAD__SILENCE_TIMEOUT_MS", "not-a-number");
+        let result = Config::load_from_str("");
+        std::env::remove_var("VOXKEY__VAD__SILENCE_TIMEOUT_MS");
+
+        assert!(matches!(result.unwrap_err(), ConfigError::Parse { .. }));
+    }
+
+    #[test]
+    fn annotate_layers_reports_source_of_each_overridden_leaf() {
+        let system = r#"
+[audio]
+sample_rate = 48000
+"#;
+        let user = r#"
+[shortcut]
+trigger = "<Control>d"
+"#;
+        let annotated = Config::annotate_layers(
+            &[
+                (ConfigSource::System, Some(system.to_string())),
+                (ConfigSource::User, Some(user.to_string())),
+            ],
+            None,
+        )
+        .unwrap();
+
+        let sample_rate = annotated
+            .iter()
+            .find(|v| v.path == ["audio".to_string(), "sample_rate".to_string()])
+            .unwrap();
+        assert_eq!(sample_rate.source, ConfigSource::System);
+
+        let trigger = annotated
+            .iter()
+            .find(|v| v.path == ["shortcut".to_string(), "trigger".to_string()])
+            .unwrap();
+        assert_eq!(trigger.source, ConfigSource::User);
+
+        let untouched = annotated
+            .iter()
+            .find(|v| v.path == ["audio".to_string(), "channels".to_string()])
+            .unwrap();
+        assert_eq!(untouched.source, ConfigSource::Default);
+    }
+
+    #[test]
+    fn changed_sections_reports_only_differing_top_level_sections() {
+        let mut old = Config::default();
+        old.audio.sample_rate = 16000;
+        let mut new = old.clone();
+        new.audio.sample_rate = 48000;
+
+        let changed = Config::changed_sections(&old, &new);
+        assert_eq!(changed, vec!["audio".to_string()]);
+    }
+
+    #[test]
+    fn changed_sections_is_empty_for_identical_configs() {
+        let config = Config::default();
+        assert!(Config::changed_sections(&config, &config).is_empty());
+    }
+
+    #[test]
+    fn merge_applies_only_set_override_fields() {
+        let mut config = Config::default();
+        config.audio.channels = 2;
+        let overrides = ConfigOverride {
+            shortcut_trigger: Some("<Control>d".to_string()),
+            audio_sample_rate: Some(48000),
+            ..Default::default()
+        };
+
+        config.merge(&overrides);
+
+        assert_eq!(config.shortcut.trigger, "<Control>d");
+        assert_eq!(config.audio.sample_rate, 48000);
+        assert_eq!(config.audio.channels, 2);
+        assert_eq!(config.transcriber.provider, TranscriberProvider::WhisperCpp);
+    }
+
+    #[test]
+    fn merge_ignores_invalid_transcriber_provider() {
+        let mut config = Config::default();
+        let overrides = ConfigOverride {
+            transcriber_provider: Some("not-a-real-provider".to_string()),
+            ..Default::default()
+        };
+
+        config.merge(&overrides);
+
+        assert_eq!(config.transcriber.provider, TranscriberProvider::WhisperCpp);
+    }
+
+    #[test]
+    fn malformed_toml_reports_line_and_column() {
+        let toml = "[shortcut]\ntrigger = \"<Super>space\n";
+        let err = Config::load_from_str(toml).unwrap_err();
+        let ConfigError::Parse { message, .. } = err else {
+            panic!("expected a Parse error, got {err:?}");
+        };
+        assert!(message.contains("line 2"), "message was: {message}");
+    }
+
+    #[test]
+    fn unknown_transcriber_provider_is_reported_as_a_parse_error() {
+        let toml = r#"
+[transcriber]
+provider = "not-a-real-provider"
+"#;
+        let err = Config::load_from_str(toml).unwrap_err();
+        let ConfigError::Parse { message, .. } = err else {
+            panic!("expected a Parse error, got {err:?}");
+        };
+        assert!(message.contains("line 3"), "message was: {message}");
+    }
+
+    #[test]
+    fn malformed_sample_rate_is_reported_with_a_line_number() {
+        let toml = r#"
+[audio]
+sample_rate = "not-a-number"
+"#;
+        let err = Config::load_from_str(toml).unwrap_err();
+        let ConfigError::Parse { message, .. } = err else {
+            panic!("expected a Parse error, got {err:?}");
+        };
+        assert!(message.contains("line 3"), "message was: {message}");
+    }
+
+    #[test]
+    fn read_layer_treats_missing_file_as_absent_not_an_error() {
+        let missing = std::env::temp_dir().join("voxkey-test-does-not-exist.toml");
+        let _ = std::fs::remove_file(&missing);
+        assert_eq!(Config::read_layer(&missing).unwrap(), None);
+    }
 }
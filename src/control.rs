@@ -0,0 +1,285 @@
+// ABOUTME: Headless control gateway over a Unix domain socket, a D-Bus-free
+// ABOUTME: alternative for session-bus-less targets (sway/tty/containers).
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::Mutex;
+
+use crate::config::CtlAction;
+use crate::dbus::{DaemonEvent, SharedState};
+use crate::shortcuts::DictationEvent;
+
+type DynError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A command sent to the daemon over the control socket.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ControlRequest {
+    GetState,
+    GetConfig,
+    SetShortcut { trigger: String },
+    DownloadModel { model_name: String, url: Option<String>, sha256: Option<String> },
+    Quit,
+    StartDictation,
+    StopDictation,
+}
+
+/// Reply to a [`ControlRequest`].
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ControlResponse {
+    Ok,
+    State(String),
+    /// The daemon's config, serialized as JSON (same shape as the D-Bus
+    /// `*_config` properties).
+    Config(String),
+    Error(String),
+}
+
+/// A server-pushed notification, unprompted by any request, mirroring the
+/// same daemon state changes the D-Bus property-changed signals carry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ControlEvent {
+    StateChanged(String),
+    TranscriptionComplete(String),
+    Error(String),
+    DownloadProgress { model_name: String, percent: u8 },
+}
+
+/// One frame written to a control socket connection: either the reply to the
+/// client's most recent request, or an event pushed asynchronously.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ControlMessage {
+    Response(ControlResponse),
+    Event(ControlEvent),
+}
+
+/// Write a length-prefixed bincode frame.
+async fn write_frame<W: AsyncWrite + Unpin, T: Serialize>(writer: &mut W, msg: &T) -> std::io::Result<()> {
+    let payload = bincode::serialize(msg)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    writer.write_u32_le(payload.len() as u32).await?;
+    writer.write_all(&payload).await?;
+    writer.flush().await
+}
+
+/// Read a length-prefixed bincode frame, returning `None` on a clean EOF.
+async fn read_frame<R: AsyncRead + Unpin, T: for<'de> Deserialize<'de>>(
+    reader: &mut R,
+) -> std::io::Result<Option<T>> {
+    let len = match reader.read_u32_le().await {
+        Ok(len) => len,
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload).await?;
+    bincode::deserialize(&payload)
+        .map(Some)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Bind the control socket and accept connections for the lifetime of the
+/// daemon. Recreates the socket file (0600, matching the restore token) on
+/// every start, since a stale socket from a crashed daemon would otherwise
+/// make the bind fail with `AddrInUse`.
+pub fn spawn(shared: SharedState, connection: zbus::Connection, socket_path: PathBuf) {
+    tokio::spawn(async move {
+        if let Some(parent) = socket_path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                tracing::error!("Failed to create control socket directory {}: {e}", parent.display());
+                return;
+            }
+        }
+        let _ = std::fs::remove_file(&socket_path);
+
+        let listener = match UnixListener::bind(&socket_path) {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::error!("Failed to bind control socket at {}: {e}", socket_path.display());
+                return;
+            }
+        };
+        if let Err(e) = std::fs::set_permissions(&socket_path, std::fs::Permissions::from_mode(0o600)) {
+            tracing::warn!("Failed to set control socket permissions: {e}");
+        }
+        tracing::info!("Control socket listening at {}", socket_path.display());
+
+        loop {
+            let (stream, _) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    tracing::warn!("Control socket accept failed: {e}");
+                    continue;
+                }
+            };
+            tokio::spawn(handle_connection(stream, shared.clone(), connection.clone()));
+        }
+    });
+}
+
+/// Translate a [`DaemonEvent`] into the wire-level [`ControlEvent`], dropping
+/// events this transport doesn't mirror (portal connectivity isn't
+/// meaningful to a control-socket client, and an empty error is a clear).
+fn daemon_event_to_control_event(event: DaemonEvent) -> Option<ControlEvent> {
+    match event {
+        DaemonEvent::StateChanged(state) => Some(ControlEvent::StateChanged(state.to_string())),
+        DaemonEvent::TranscriptionComplete(text) => Some(ControlEvent::TranscriptionComplete(text)),
+        DaemonEvent::LastError(message) if !message.is_empty() => Some(ControlEvent::Error(message)),
+        DaemonEvent::LastError(_) => None,
+        DaemonEvent::PortalConnected(_) => None,
+        DaemonEvent::DownloadProgress { model_name, percent } => {
+            Some(ControlEvent::DownloadProgress { model_name, percent })
+        }
+    }
+}
+
+/// Serve one client connection: forward `ControlEvent`s from the shared
+/// `DaemonEvent` broadcast channel while concurrently answering requests,
+/// until the client disconnects.
+async fn handle_connection(stream: UnixStream, shared: SharedState, connection: zbus::Connection) {
+    let (mut read_half, write_half) = stream.into_split();
+    let write_half = Arc::new(Mutex::new(write_half));
+
+    let mut events = shared.events();
+    let event_writer = write_half.clone();
+    let forward_task = tokio::spawn(async move {
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Control socket event forwarder lagged, skipped {skipped} events");
+                    continue;
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+            };
+            let Some(event) = daemon_event_to_control_event(event) else {
+                continue;
+            };
+            let mut writer = event_writer.lock().await;
+            if write_frame(&mut *writer, &ControlMessage::Event(event)).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    loop {
+        let request: ControlRequest = match read_frame(&mut read_half).await {
+            Ok(Some(request)) => request,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::debug!("Control socket read error: {e}");
+                break;
+            }
+        };
+        let response = handle_request(request, &shared, &connection).await;
+        let mut writer = write_half.lock().await;
+        if write_frame(&mut *writer, &ControlMessage::Response(response)).await.is_err() {
+            break;
+        }
+    }
+    forward_task.abort();
+}
+
+/// Dispatch one request onto the same `SharedState`/`DownloadManager` methods
+/// the `DaemonInterface` D-Bus methods already call.
+async fn handle_request(
+    request: ControlRequest,
+    shared: &SharedState,
+    connection: &zbus::Connection,
+) -> ControlResponse {
+    match request {
+        ControlRequest::GetState => ControlResponse::State(shared.state().to_string()),
+        ControlRequest::GetConfig => match serde_json::to_string(&shared.config()) {
+            Ok(json) => ControlResponse::Config(json),
+            Err(e) => ControlResponse::Error(e.to_string()),
+        },
+        ControlRequest::SetShortcut { trigger } => {
+            shared.set_shortcut_trigger(trigger);
+            let config = shared.config();
+            if let Err(e) = config.save() {
+                return ControlResponse::Error(format!("Failed to save config: {e}"));
+            }
+            if let Err(e) = crate::shortcuts::write_shortcut_dconf(&config.shortcut) {
+                tracing::warn!("Failed to write shortcut to dconf (non-GNOME?): {e}");
+            }
+            crate::dbus::DaemonInterface::notify_shortcut_trigger(connection).await;
+            shared.request_session_restart();
+            ControlResponse::Ok
+        }
+        ControlRequest::DownloadModel { model_name, url, sha256 } => {
+            shared.download_manager().enqueue(model_name, url, sha256);
+            ControlResponse::Ok
+        }
+        ControlRequest::Quit => {
+            shared.request_shutdown();
+            ControlResponse::Ok
+        }
+        ControlRequest::StartDictation => {
+            if shared.request_external_dictation(DictationEvent::Start) {
+                ControlResponse::Ok
+            } else {
+                ControlResponse::Error("no active session".to_string())
+            }
+        }
+        ControlRequest::StopDictation => {
+            if shared.request_external_dictation(DictationEvent::Stop) {
+                ControlResponse::Ok
+            } else {
+                ControlResponse::Error("no active session".to_string())
+            }
+        }
+    }
+}
+
+/// The thin `voxkey ctl <action>` client: connects to the control socket of
+/// an already-running daemon, sends one request, prints the reply, and
+/// exits. Lets the daemon be scripted from the shell without `busctl`.
+pub async fn run_ctl_client(action: CtlAction) -> Result<(), DynError> {
+    let config = crate::config::Config::load()?;
+    let socket_path = config.control_socket_path();
+
+    let stream = UnixStream::connect(&socket_path).await.map_err(|e| -> DynError {
+        format!("Failed to connect to control socket at {}: {e}", socket_path.display()).into()
+    })?;
+    let (mut read_half, mut write_half) = stream.into_split();
+
+    let request = match action {
+        CtlAction::GetState => ControlRequest::GetState,
+        CtlAction::GetConfig => ControlRequest::GetConfig,
+        CtlAction::SetShortcut { trigger } => ControlRequest::SetShortcut { trigger },
+        CtlAction::DownloadModel { model_name, url, sha256 } => {
+            ControlRequest::DownloadModel { model_name, url, sha256 }
+        }
+        CtlAction::Quit => ControlRequest::Quit,
+        CtlAction::Start => ControlRequest::StartDictation,
+        CtlAction::Stop => ControlRequest::StopDictation,
+    };
+
+    write_frame(&mut write_half, &request).await?;
+    match read_frame::<_, ControlMessage>(&mut read_half).await? {
+        Some(ControlMessage::Response(response)) => print_ctl_response(response),
+        Some(ControlMessage::Event(_)) => {
+            return Err("control socket sent an event before replying to the request".into());
+        }
+        None => return Err("control socket closed before replying".into()),
+    }
+    Ok(())
+}
+
+/// Render a [`ControlResponse`] for the `voxkey ctl` command line, exiting
+/// non-zero on `ControlResponse::Error` the same way any other CLI failure does.
+fn print_ctl_response(response: ControlResponse) {
+    match response {
+        ControlResponse::Ok => println!("ok"),
+        ControlResponse::State(state) => println!("{state}"),
+        ControlResponse::Config(json) => println!("{json}"),
+        ControlResponse::Error(message) => {
+            eprintln!("error: {message}");
+            std::process::exit(1);
+        }
+    }
+}
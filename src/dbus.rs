@@ -1,61 +1,213 @@
 // ABOUTME: D-Bus interface exposing daemon state and configuration to the settings GUI.
 // ABOUTME: Registered on the session bus so the GUI can read properties and call methods.
 
-use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::{Arc, Mutex};
 
-use tokio::sync::watch;
+use arc_swap::ArcSwap;
+use tokio::sync::{broadcast, mpsc, watch};
 
 use crate::config::Config;
-use crate::model_download::DownloadStatus;
+use crate::download_manager::DownloadManager;
+use crate::metrics::Metrics;
+use crate::mqtt::MqttBridge;
+use crate::recorder::Recorder;
 use crate::shortcuts;
+use crate::shortcuts::DictationEvent;
 use crate::state::State;
 
+/// The clipboard's content and primary MIME type, snapshotted by
+/// `injector::paste_text` before a dictation paste overwrites the clipboard,
+/// so the original content can be restored once the paste completes.
+pub struct SavedClipboard {
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+/// A daemon state change, published once by the event loop and fanned out to
+/// every subscriber — the D-Bus property-changed/signal bridge
+/// ([`spawn_event_bridge`]) and any other transport (e.g. the control-socket
+/// gateway) — instead of each call site driving each transport directly.
+#[derive(Debug, Clone)]
+pub enum DaemonEvent {
+    StateChanged(State),
+    TranscriptionComplete(String),
+    PortalConnected(bool),
+    LastError(String),
+    DownloadProgress { model_name: String, percent: u8 },
+}
+
 /// Shared daemon state readable by the D-Bus interface and writable by the event loop.
+///
+/// `state`, `portal_connected`, and `config` sit on the audio/transcription
+/// hot path and are read on every D-Bus property fetch, so they're lock-free
+/// (`AtomicU8`/`AtomicBool`/`ArcSwap`) instead of living behind the same
+/// mutex as the rest of the daemon's state, which changes far less often.
 #[derive(Clone)]
 pub struct SharedState {
     inner: Arc<Mutex<SharedStateInner>>,
+    state: Arc<AtomicU8>,
+    portal_connected: Arc<AtomicBool>,
+    config: Arc<ArcSwap<Config>>,
     restart_signal: Arc<tokio::sync::Notify>,
     shutdown_signal: Arc<tokio::sync::Notify>,
+    config_tx: Arc<watch::Sender<Config>>,
+    metrics: Metrics,
+    /// Forwards Start/Stop requests from the control-socket gateway into
+    /// whichever session is currently running. `None` between sessions.
+    external_dictation: Arc<Mutex<Option<mpsc::UnboundedSender<DictationEvent>>>>,
+    /// Broadcasts [`DaemonEvent`]s to every subscriber.
+    events: broadcast::Sender<DaemonEvent>,
 }
 
 struct SharedStateInner {
-    state: State,
-    config: Config,
-    portal_connected: bool,
+    state_entered_at: std::time::Instant,
     last_transcript: String,
     last_error: String,
+    last_latency_ms: u64,
+    resolved_execution_provider: String,
+    input_devices: Vec<String>,
     pending_injection: Option<String>,
-    download_watchers: HashMap<String, watch::Receiver<DownloadStatus>>,
+    saved_clipboard: Option<SavedClipboard>,
+    download_manager: DownloadManager,
+    mqtt: Option<Arc<MqttBridge>>,
 }
 
 impl SharedState {
     pub fn new(config: Config) -> Self {
+        let (config_tx, _) = watch::channel(config.clone());
+        let input_devices = Recorder::list_input_devices()
+            .map(|devices| devices.into_iter().map(|d| d.name).collect())
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to enumerate input devices: {e}");
+                Vec::new()
+            });
         Self {
             inner: Arc::new(Mutex::new(SharedStateInner {
-                state: State::Idle,
-                config,
-                portal_connected: false,
+                state_entered_at: std::time::Instant::now(),
                 last_transcript: String::new(),
                 last_error: String::new(),
+                last_latency_ms: 0,
+                resolved_execution_provider: String::new(),
+                input_devices,
                 pending_injection: None,
-                download_watchers: HashMap::new(),
+                saved_clipboard: None,
+                download_manager: DownloadManager::new(),
+                mqtt: None,
             })),
+            state: Arc::new(AtomicU8::new(State::Idle.into())),
+            portal_connected: Arc::new(AtomicBool::new(false)),
+            config: Arc::new(ArcSwap::from_pointee(config)),
             restart_signal: Arc::new(tokio::sync::Notify::new()),
             shutdown_signal: Arc::new(tokio::sync::Notify::new()),
+            config_tx: Arc::new(config_tx),
+            metrics: Metrics::new(),
+            external_dictation: Arc::new(Mutex::new(None)),
+            events: broadcast::channel(32).0,
         }
     }
 
+    /// Install the channel the running session listens on for control-socket
+    /// Start/Stop requests, replacing whatever session-restart left behind.
+    pub fn register_external_dictation_sender(&self, tx: mpsc::UnboundedSender<DictationEvent>) {
+        *self.external_dictation.lock().unwrap() = Some(tx);
+    }
+
+    /// Ask the currently running session to start or stop dictation, as if
+    /// the physical shortcut had been pressed. Returns `false` if no session
+    /// is currently running to receive it.
+    pub fn request_external_dictation(&self, event: DictationEvent) -> bool {
+        match self.external_dictation.lock().unwrap().as_ref() {
+            Some(tx) => tx.send(event).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Set the shortcut trigger in the shared config, without saving or
+    /// signaling anything — callers (the D-Bus `set_shortcut` method and the
+    /// control-socket gateway's equivalent) are responsible for those steps.
+    pub fn set_shortcut_trigger(&self, trigger: String) {
+        self.mutate_config(|config| config.shortcut.trigger = trigger);
+    }
+
+    /// Swap in a config clone mutated by `f`, without publishing to
+    /// [`SharedState::subscribe_config`] — only [`SharedState::update_config`]
+    /// does that, matching how these setters already left `config_tx` alone
+    /// when they mutated `inner.config` directly.
+    fn mutate_config<F: FnOnce(&mut Config)>(&self, f: F) {
+        let mut new_config = (**self.config.load()).clone();
+        f(&mut new_config);
+        self.config.store(Arc::new(new_config));
+    }
+
+    /// Subscribe to the `DaemonEvent` stream — used by [`spawn_event_bridge`]
+    /// and by any other transport (e.g. the control-socket gateway) that
+    /// wants to mirror daemon state changes without the event loop knowing
+    /// about it.
+    pub fn events(&self) -> broadcast::Receiver<DaemonEvent> {
+        self.events.subscribe()
+    }
+
+    pub fn publish_event(&self, event: DaemonEvent) {
+        let _ = self.events.send(event);
+    }
+
+    /// Set `portal_connected` and publish `DaemonEvent::PortalConnected`.
+    pub fn set_portal_connected_and_publish(&self, connected: bool) {
+        self.set_portal_connected(connected);
+        self.publish_event(DaemonEvent::PortalConnected(connected));
+    }
+
+    /// Record a new last-error message and publish `DaemonEvent::LastError`.
+    /// Clearing the error (an empty string) still publishes, so the D-Bus
+    /// property stays in sync, but [`spawn_event_bridge`] only forwards
+    /// non-empty messages to MQTT, and the control-socket gateway likewise
+    /// drops empty ones.
+    pub fn set_last_error_and_publish(&self, message: String) {
+        self.set_last_error(message.clone());
+        self.publish_event(DaemonEvent::LastError(message));
+    }
+
+    /// Record a new transcript and publish `DaemonEvent::TranscriptionComplete`.
+    pub fn set_last_transcript_and_publish(&self, text: String) {
+        self.set_last_transcript(text.clone());
+        self.publish_event(DaemonEvent::TranscriptionComplete(text));
+    }
+
+    /// The daemon's metrics handle. Cheap to clone; recording methods are
+    /// lock-free except `record_model_invocation`.
+    pub fn metrics(&self) -> Metrics {
+        self.metrics.clone()
+    }
+
+    /// Install (or clear) the MQTT bridge, e.g. after a session restart picks up
+    /// a new `MqttConfig`.
+    pub fn set_mqtt(&self, bridge: Option<MqttBridge>) {
+        self.inner.lock().unwrap().mqtt = bridge.map(Arc::new);
+    }
+
+    /// The active MQTT bridge, if the feature is enabled and connected.
+    pub fn mqtt(&self) -> Option<Arc<MqttBridge>> {
+        self.inner.lock().unwrap().mqtt.clone()
+    }
+
     pub fn state(&self) -> State {
-        self.inner.lock().unwrap().state
+        State::try_from(self.state.load(Ordering::Relaxed)).expect("valid State byte")
     }
 
     pub fn set_state(&self, state: State) {
-        self.inner.lock().unwrap().state = state;
+        self.state.store(state.into(), Ordering::Relaxed);
+        self.inner.lock().unwrap().state_entered_at = std::time::Instant::now();
+    }
+
+    /// How long the daemon has been in its current state. Fed to
+    /// `State::poll_timeout` by the main select loop's watchdog tick.
+    pub fn time_in_state(&self) -> std::time::Duration {
+        self.inner.lock().unwrap().state_entered_at.elapsed()
     }
 
     pub fn set_portal_connected(&self, connected: bool) {
-        self.inner.lock().unwrap().portal_connected = connected;
+        self.portal_connected.store(connected, Ordering::Relaxed);
     }
 
     pub fn set_last_transcript(&self, text: String) {
@@ -66,6 +218,23 @@ impl SharedState {
         self.inner.lock().unwrap().last_error = text;
     }
 
+    pub fn set_last_latency_ms(&self, ms: u64) {
+        self.inner.lock().unwrap().last_latency_ms = ms;
+    }
+
+    /// Record the execution provider the active Parakeet transcriber resolved
+    /// to (after `Auto` was resolved to a concrete choice), so the settings
+    /// GUI can show what actually ran instead of just what was requested.
+    pub fn set_resolved_execution_provider(&self, provider: String) {
+        self.inner.lock().unwrap().resolved_execution_provider = provider;
+    }
+
+    /// Replace the list of available input device names, e.g. after the
+    /// background hotplug watcher detects a device add/remove.
+    pub fn set_input_devices(&self, devices: Vec<String>) {
+        self.inner.lock().unwrap().input_devices = devices;
+    }
+
     pub fn set_pending_injection(&self, text: Option<String>) {
         self.inner.lock().unwrap().pending_injection = text;
     }
@@ -74,16 +243,39 @@ impl SharedState {
         self.inner.lock().unwrap().pending_injection.take()
     }
 
-    pub fn config(&self) -> Config {
-        self.inner.lock().unwrap().config.clone()
+    /// Stash the clipboard snapshot `injector::paste_text` took before
+    /// overwriting the clipboard with the transcript, for later restoration.
+    pub fn set_saved_clipboard(&self, clipboard: Option<SavedClipboard>) {
+        self.inner.lock().unwrap().saved_clipboard = clipboard;
+    }
+
+    /// Take the stashed clipboard snapshot, if any, to restore it.
+    pub fn take_saved_clipboard(&self) -> Option<SavedClipboard> {
+        self.inner.lock().unwrap().saved_clipboard.take()
+    }
+
+    /// The current config snapshot. Cheap `Arc` clone, no lock — readers
+    /// never block a writer publishing a new config, or each other.
+    pub fn config(&self) -> Arc<Config> {
+        self.config.load_full()
     }
 
+    /// Replace the shared config and publish it to anything subscribed via
+    /// [`SharedState::subscribe_config`].
     pub fn update_config(&self, config: Config) {
-        self.inner.lock().unwrap().config = config;
+        self.config.store(Arc::new(config.clone()));
+        let _ = self.config_tx.send(config);
+    }
+
+    /// Subscribe to live config updates, e.g. after a SIGUSR1 reload.
+    /// Subsystems that can apply new settings without a session restart
+    /// (transcriber, audio) watch this instead of re-reading `config()` once at startup.
+    pub fn subscribe_config(&self) -> watch::Receiver<Config> {
+        self.config_tx.subscribe()
     }
 
     fn portal_connected(&self) -> bool {
-        self.inner.lock().unwrap().portal_connected
+        self.portal_connected.load(Ordering::Relaxed)
     }
 
     fn last_transcript(&self) -> String {
@@ -94,6 +286,18 @@ impl SharedState {
         self.inner.lock().unwrap().last_error.clone()
     }
 
+    fn last_latency_ms(&self) -> u64 {
+        self.inner.lock().unwrap().last_latency_ms
+    }
+
+    fn resolved_execution_provider(&self) -> String {
+        self.inner.lock().unwrap().resolved_execution_provider.clone()
+    }
+
+    fn input_devices(&self) -> Vec<String> {
+        self.inner.lock().unwrap().input_devices.clone()
+    }
+
     pub fn request_session_restart(&self) {
         self.restart_signal.notify_one();
     }
@@ -110,15 +314,10 @@ impl SharedState {
         self.shutdown_signal.notified().await;
     }
 
-    pub fn start_model_download(&self, model_name: String) -> watch::Receiver<DownloadStatus> {
-        let rx = crate::model_download::start_download(model_name.clone());
-        self.inner.lock().unwrap().download_watchers.insert(model_name, rx.clone());
-        rx
-    }
-
-    pub fn model_download_status(&self, model_name: &str) -> Option<DownloadStatus> {
-        let inner = self.inner.lock().unwrap();
-        inner.download_watchers.get(model_name).map(|rx| rx.borrow().clone())
+    /// The daemon's download manager. Cheap to clone; serializes queued
+    /// model downloads and persists their status across restarts.
+    pub fn download_manager(&self) -> DownloadManager {
+        self.inner.lock().unwrap().download_manager.clone()
     }
 }
 
@@ -132,7 +331,21 @@ impl DaemonInterface {
         Self { shared }
     }
 
-    pub async fn notify_state(connection: &zbus::Connection) {
+    /// Emit the not-yet-stable tail of an in-progress realtime transcript.
+    pub async fn notify_transcription_partial(connection: &zbus::Connection, text: &str) {
+        let Ok(iface_ref) = connection
+            .object_server()
+            .interface::<_, DaemonInterface>(voxkey_ipc::OBJECT_PATH)
+            .await
+        else {
+            return;
+        };
+        let _ = DaemonInterface::transcription_partial(iface_ref.signal_emitter(), text).await;
+    }
+
+    /// Emit a property-changed signal for the latest end-to-end streaming
+    /// injection latency recorded via `SharedState::set_last_latency_ms`.
+    pub async fn notify_last_latency(connection: &zbus::Connection) {
         let Ok(iface_ref) = connection
             .object_server()
             .interface::<_, DaemonInterface>(voxkey_ipc::OBJECT_PATH)
@@ -143,11 +356,13 @@ impl DaemonInterface {
         let _ = iface_ref
             .get()
             .await
-            .state_changed(iface_ref.signal_emitter())
+            .last_latency_ms_changed(iface_ref.signal_emitter())
             .await;
     }
 
-    pub async fn notify_portal_connected(connection: &zbus::Connection) {
+    /// Emit a property-changed signal after `SharedState::set_resolved_execution_provider`
+    /// records what the active Parakeet transcriber actually resolved `Auto` to.
+    pub async fn notify_resolved_execution_provider(connection: &zbus::Connection) {
         let Ok(iface_ref) = connection
             .object_server()
             .interface::<_, DaemonInterface>(voxkey_ipc::OBJECT_PATH)
@@ -158,11 +373,13 @@ impl DaemonInterface {
         let _ = iface_ref
             .get()
             .await
-            .portal_connected_changed(iface_ref.signal_emitter())
+            .resolved_execution_provider_changed(iface_ref.signal_emitter())
             .await;
     }
 
-    pub async fn notify_last_error(connection: &zbus::Connection) {
+    /// Emit a property-changed signal for the available input device list,
+    /// e.g. after the background hotplug watcher detects a device add/remove.
+    pub async fn notify_input_devices(connection: &zbus::Connection) {
         let Ok(iface_ref) = connection
             .object_server()
             .interface::<_, DaemonInterface>(voxkey_ipc::OBJECT_PATH)
@@ -173,11 +390,14 @@ impl DaemonInterface {
         let _ = iface_ref
             .get()
             .await
-            .last_error_changed(iface_ref.signal_emitter())
+            .input_devices_changed(iface_ref.signal_emitter())
             .await;
     }
 
-    pub async fn notify_last_transcript(connection: &zbus::Connection) {
+    /// Emit a property-changed signal for the shortcut trigger, e.g. after
+    /// `SharedState::set_shortcut_trigger` is used by the control socket
+    /// gateway as well as this interface's own `set_shortcut` method.
+    pub async fn notify_shortcut_trigger(connection: &zbus::Connection) {
         let Ok(iface_ref) = connection
             .object_server()
             .interface::<_, DaemonInterface>(voxkey_ipc::OBJECT_PATH)
@@ -188,7 +408,24 @@ impl DaemonInterface {
         let _ = iface_ref
             .get()
             .await
-            .last_transcript_changed(iface_ref.signal_emitter())
+            .shortcut_trigger_changed(iface_ref.signal_emitter())
+            .await;
+    }
+
+    /// Emit a property-changed signal for the download queue, e.g. after
+    /// `DownloadManager` adds, progresses, or finishes a job.
+    pub async fn notify_download_queue(connection: &zbus::Connection) {
+        let Ok(iface_ref) = connection
+            .object_server()
+            .interface::<_, DaemonInterface>(voxkey_ipc::OBJECT_PATH)
+            .await
+        else {
+            return;
+        };
+        let _ = iface_ref
+            .get()
+            .await
+            .download_queue_changed(iface_ref.signal_emitter())
             .await;
     }
 }
@@ -207,7 +444,7 @@ impl DaemonInterface {
 
     #[zbus(property)]
     fn transcriber_config(&self) -> String {
-        serde_json::to_string(&self.shared.config().transcriber)
+        serde_json::to_string(&redact_transcriber_secrets(&self.shared.config().transcriber))
             .unwrap_or_default()
     }
 
@@ -217,6 +454,18 @@ impl DaemonInterface {
             .unwrap_or_default()
     }
 
+    #[zbus(property)]
+    fn vad_config(&self) -> String {
+        serde_json::to_string(&self.shared.config().vad)
+            .unwrap_or_default()
+    }
+
+    #[zbus(property)]
+    fn mqtt_config(&self) -> String {
+        serde_json::to_string(&self.shared.config().mqtt)
+            .unwrap_or_default()
+    }
+
     #[zbus(property)]
     fn sample_rate(&self) -> u32 {
         self.shared.config().audio.sample_rate
@@ -227,6 +476,16 @@ impl DaemonInterface {
         self.shared.config().audio.channels
     }
 
+    #[zbus(property)]
+    fn input_devices(&self) -> Vec<String> {
+        self.shared.input_devices()
+    }
+
+    #[zbus(property)]
+    fn input_device(&self) -> String {
+        self.shared.config().audio.device.clone().unwrap_or_default()
+    }
+
     #[zbus(property)]
     fn portal_connected(&self) -> bool {
         self.shared.portal_connected()
@@ -242,15 +501,88 @@ impl DaemonInterface {
         self.shared.last_error()
     }
 
+    #[zbus(property)]
+    fn last_latency_ms(&self) -> u64 {
+        self.shared.last_latency_ms()
+    }
+
+    /// Execution provider the active Parakeet transcriber resolved `Auto` to,
+    /// or empty if Parakeet isn't the active provider.
+    #[zbus(property)]
+    fn resolved_execution_provider(&self) -> String {
+        self.shared.resolved_execution_provider()
+    }
+
+    /// The download manager's queue as serialized JSON.
+    #[zbus(property)]
+    fn download_queue(&self) -> String {
+        serde_json::to_string(&self.shared.download_manager().statuses()).unwrap_or_default()
+    }
+
+    #[zbus(property)]
+    fn transcriptions_total(&self) -> u64 {
+        self.shared.metrics().transcriptions_total()
+    }
+
+    #[zbus(property)]
+    fn audio_seconds_total(&self) -> f64 {
+        self.shared.metrics().audio_seconds_total()
+    }
+
+    #[zbus(property)]
+    fn words_injected_total(&self) -> u64 {
+        self.shared.metrics().words_injected_total()
+    }
+
+    #[zbus(property)]
+    fn characters_injected_total(&self) -> u64 {
+        self.shared.metrics().characters_injected_total()
+    }
+
+    #[zbus(property)]
+    fn transcription_failures_total(&self) -> u64 {
+        self.shared.metrics().transcription_failures_total()
+    }
+
+    /// Per-engine invocation counts as serialized JSON.
+    #[zbus(property)]
+    fn model_invocations(&self) -> String {
+        serde_json::to_string(&self.shared.metrics().model_invocations()).unwrap_or_default()
+    }
+
+    /// Execution providers usable on this machine (serialized as their
+    /// kebab-case config names, e.g. "cuda", "tensor-rt"), for the settings
+    /// GUI to gray out unsupported combo entries.
+    fn available_execution_providers(&self) -> zbus::fdo::Result<Vec<String>> {
+        Ok(crate::execution_providers::available_providers()
+            .into_iter()
+            .map(|p| p.config_name().to_string())
+            .collect())
+    }
+
+    /// Store a secret (e.g. a provider API key) in the OS secret service
+    /// under a stable account label, so it never has to live in the on-disk
+    /// transcriber config.
+    fn store_secret(&self, key: &str, value: &str) -> zbus::fdo::Result<()> {
+        crate::secret_store::store(key, value).map_err(zbus::fdo::Error::Failed)
+    }
+
+    /// Remove a previously stored secret, if any.
+    fn clear_secret(&self, key: &str) -> zbus::fdo::Result<()> {
+        crate::secret_store::clear(key).map_err(zbus::fdo::Error::Failed)
+    }
+
+    /// Load a previously stored secret, or an empty string if none is stored.
+    fn load_secret(&self, key: &str) -> zbus::fdo::Result<String> {
+        Ok(crate::secret_store::load(key).unwrap_or_default())
+    }
+
     async fn set_shortcut(
         &self,
         #[zbus(connection)] connection: &zbus::Connection,
         trigger: &str,
     ) -> zbus::fdo::Result<()> {
-        {
-            let mut inner = self.shared.inner.lock().unwrap();
-            inner.config.shortcut.trigger = trigger.to_string();
-        }
+        self.shared.set_shortcut_trigger(trigger.to_string());
         let config = self.shared.config();
         config.save().map_err(|e| {
             zbus::fdo::Error::Failed(format!("Failed to save config: {e}"))
@@ -260,6 +592,30 @@ impl DaemonInterface {
             tracing::warn!("Failed to write shortcut to dconf (non-GNOME?): {e}");
         }
 
+        DaemonInterface::notify_shortcut_trigger(connection).await;
+
+        self.shared.request_session_restart();
+
+        Ok(())
+    }
+
+    async fn set_transcriber_config(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        config_json: &str,
+    ) -> zbus::fdo::Result<()> {
+        let mut transcriber_config: voxkey_ipc::TranscriberConfig =
+            serde_json::from_str(config_json).map_err(|e| {
+                zbus::fdo::Error::InvalidArgs(format!("Invalid transcriber config JSON: {e}"))
+            })?;
+        resolve_transcriber_secrets(&mut transcriber_config);
+        self.shared.mutate_config(|config| config.transcriber = transcriber_config);
+        let mut persisted = (*self.shared.config()).clone();
+        persisted.transcriber = redact_transcriber_secrets(&persisted.transcriber);
+        persisted.save().map_err(|e| {
+            zbus::fdo::Error::Failed(format!("Failed to save config: {e}"))
+        })?;
+
         if let Ok(iface_ref) = connection
             .object_server()
             .interface::<_, DaemonInterface>(voxkey_ipc::OBJECT_PATH)
@@ -268,7 +624,7 @@ impl DaemonInterface {
             let _ = iface_ref
                 .get()
                 .await
-                .shortcut_trigger_changed(iface_ref.signal_emitter())
+                .transcriber_config_changed(iface_ref.signal_emitter())
                 .await;
         }
 
@@ -277,19 +633,16 @@ impl DaemonInterface {
         Ok(())
     }
 
-    async fn set_transcriber_config(
+    async fn set_injection_config(
         &self,
         #[zbus(connection)] connection: &zbus::Connection,
         config_json: &str,
     ) -> zbus::fdo::Result<()> {
-        let transcriber_config: voxkey_ipc::TranscriberConfig =
+        let injection_config: voxkey_ipc::InjectionConfig =
             serde_json::from_str(config_json).map_err(|e| {
-                zbus::fdo::Error::InvalidArgs(format!("Invalid transcriber config JSON: {e}"))
+                zbus::fdo::Error::InvalidArgs(format!("Invalid injection config JSON: {e}"))
             })?;
-        {
-            let mut inner = self.shared.inner.lock().unwrap();
-            inner.config.transcriber = transcriber_config;
-        }
+        self.shared.mutate_config(|config| config.injection = injection_config);
         self.shared.config().save().map_err(|e| {
             zbus::fdo::Error::Failed(format!("Failed to save config: {e}"))
         })?;
@@ -302,7 +655,7 @@ impl DaemonInterface {
             let _ = iface_ref
                 .get()
                 .await
-                .transcriber_config_changed(iface_ref.signal_emitter())
+                .injection_config_changed(iface_ref.signal_emitter())
                 .await;
         }
 
@@ -311,19 +664,47 @@ impl DaemonInterface {
         Ok(())
     }
 
-    async fn set_injection_config(
+    async fn set_vad_config(
         &self,
         #[zbus(connection)] connection: &zbus::Connection,
         config_json: &str,
     ) -> zbus::fdo::Result<()> {
-        let injection_config: voxkey_ipc::InjectionConfig =
+        let vad_config: voxkey_ipc::VadConfig =
             serde_json::from_str(config_json).map_err(|e| {
-                zbus::fdo::Error::InvalidArgs(format!("Invalid injection config JSON: {e}"))
+                zbus::fdo::Error::InvalidArgs(format!("Invalid VAD config JSON: {e}"))
             })?;
+        self.shared.mutate_config(|config| config.vad = vad_config);
+        self.shared.config().save().map_err(|e| {
+            zbus::fdo::Error::Failed(format!("Failed to save config: {e}"))
+        })?;
+
+        if let Ok(iface_ref) = connection
+            .object_server()
+            .interface::<_, DaemonInterface>(voxkey_ipc::OBJECT_PATH)
+            .await
         {
-            let mut inner = self.shared.inner.lock().unwrap();
-            inner.config.injection = injection_config;
+            let _ = iface_ref
+                .get()
+                .await
+                .vad_config_changed(iface_ref.signal_emitter())
+                .await;
         }
+
+        self.shared.request_session_restart();
+
+        Ok(())
+    }
+
+    async fn set_mqtt_config(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        config_json: &str,
+    ) -> zbus::fdo::Result<()> {
+        let mqtt_config: voxkey_ipc::MqttConfig =
+            serde_json::from_str(config_json).map_err(|e| {
+                zbus::fdo::Error::InvalidArgs(format!("Invalid MQTT config JSON: {e}"))
+            })?;
+        self.shared.mutate_config(|config| config.mqtt = mqtt_config);
         self.shared.config().save().map_err(|e| {
             zbus::fdo::Error::Failed(format!("Failed to save config: {e}"))
         })?;
@@ -336,7 +717,7 @@ impl DaemonInterface {
             let _ = iface_ref
                 .get()
                 .await
-                .injection_config_changed(iface_ref.signal_emitter())
+                .mqtt_config_changed(iface_ref.signal_emitter())
                 .await;
         }
 
@@ -350,11 +731,21 @@ impl DaemonInterface {
         sample_rate: u32,
         channels: u16,
     ) -> zbus::fdo::Result<()> {
-        {
-            let mut inner = self.shared.inner.lock().unwrap();
-            inner.config.audio.sample_rate = sample_rate;
-            inner.config.audio.channels = channels;
-        }
+        self.shared.mutate_config(|config| {
+            config.audio.sample_rate = sample_rate;
+            config.audio.channels = channels;
+        });
+        self.shared.config().save().map_err(|e| {
+            zbus::fdo::Error::Failed(format!("Failed to save config: {e}"))
+        })?;
+        self.shared.request_session_restart();
+        Ok(())
+    }
+
+    async fn set_input_device(&self, device: &str) -> zbus::fdo::Result<()> {
+        self.shared.mutate_config(|config| {
+            config.audio.device = (!device.is_empty()).then(|| device.to_string());
+        });
         self.shared.config().save().map_err(|e| {
             zbus::fdo::Error::Failed(format!("Failed to save config: {e}"))
         })?;
@@ -377,6 +768,12 @@ impl DaemonInterface {
         Ok(())
     }
 
+    async fn restart_session(&self) -> zbus::fdo::Result<()> {
+        tracing::info!("Session restart requested via D-Bus");
+        self.shared.request_session_restart();
+        Ok(())
+    }
+
     async fn clear_restore_token(&self) -> zbus::fdo::Result<()> {
         let token_path = self.shared.config().token_path();
         if token_path.exists() {
@@ -388,48 +785,24 @@ impl DaemonInterface {
         Ok(())
     }
 
-    async fn download_model(
-        &self,
-        #[zbus(connection)] connection: &zbus::Connection,
-        model_name: &str,
-    ) -> zbus::fdo::Result<()> {
-        let model_name = model_name.to_string();
-        let mut rx = self.shared.start_model_download(model_name.clone());
-        let connection = connection.clone();
-        let shared = self.shared.clone();
-
-        tokio::spawn(async move {
-            while rx.changed().await.is_ok() {
-                let status = rx.borrow().clone();
-                match &status {
-                    DownloadStatus::InProgress(pct) => {
-                        if let Ok(iface_ref) = connection
-                            .object_server()
-                            .interface::<_, DaemonInterface>(voxkey_ipc::OBJECT_PATH)
-                            .await
-                        {
-                            let _ = DaemonInterface::download_progress(
-                                iface_ref.signal_emitter(),
-                                &model_name,
-                                *pct,
-                            ).await;
-                        }
-                    }
-                    DownloadStatus::Complete => {
-                        tracing::info!("Model download complete: {model_name}");
-                        break;
-                    }
-                    DownloadStatus::Failed(msg) => {
-                        tracing::error!("Model download failed: {msg}");
-                        shared.set_last_error(format!("Download failed: {msg}"));
-                        DaemonInterface::notify_last_error(&connection).await;
-                        break;
-                    }
-                }
-            }
-            shared.inner.lock().unwrap().download_watchers.remove(&model_name);
-        });
+    /// Return the last `limit` dictation history entries and the lifetime
+    /// total word count, as `{"entries": [...], "total_words": N}`, for the
+    /// settings GUI's dictation log and lifetime stats.
+    fn dictation_history(&self, limit: u32) -> zbus::fdo::Result<String> {
+        let history_path = self.shared.config().history_path();
+        let entries = crate::persistence::read_history(&history_path, limit as usize);
+        let total_words = crate::persistence::history_word_total(&history_path);
+        let payload = serde_json::json!({ "entries": entries, "total_words": total_words });
+        Ok(payload.to_string())
+    }
 
+    /// Queue a model download with the download manager. Progress and
+    /// terminal status are published through the `download_queue` property
+    /// by `spawn_download_queue_watcher` rather than tracked inline here.
+    fn download_model(&self, model_name: &str, url: &str, sha256: &str) -> zbus::fdo::Result<()> {
+        let url_override = (!url.is_empty()).then(|| url.to_string());
+        let sha256_override = (!sha256.is_empty()).then(|| sha256.to_string());
+        self.shared.download_manager().enqueue(model_name.to_string(), url_override, sha256_override);
         Ok(())
     }
 
@@ -439,13 +812,22 @@ impl DaemonInterface {
         })
     }
 
+    fn cancel_download(&self, job_id: u64) -> zbus::fdo::Result<()> {
+        self.shared.download_manager().cancel(job_id);
+        Ok(())
+    }
+
     fn model_status(&self, model_name: &str) -> zbus::fdo::Result<String> {
-        if let Some(status) = self.shared.model_download_status(model_name) {
-            return Ok(match status {
-                DownloadStatus::InProgress(_) => "downloading".to_string(),
-                DownloadStatus::Complete => "available".to_string(),
-                DownloadStatus::Failed(_) => "not_downloaded".to_string(),
-            });
+        let statuses = self.shared.download_manager().statuses();
+        if let Some(job) = statuses.iter().rev().find(|j| j.model_name == model_name) {
+            let status = match job.state {
+                voxkey_ipc::DownloadJobState::Pending | voxkey_ipc::DownloadJobState::InProgress => "downloading",
+                voxkey_ipc::DownloadJobState::Verifying => "verifying",
+                voxkey_ipc::DownloadJobState::Complete => "available",
+                voxkey_ipc::DownloadJobState::ChecksumFailed => "checksum_failed",
+                voxkey_ipc::DownloadJobState::Failed | voxkey_ipc::DownloadJobState::Cancelled => "not_downloaded",
+            };
+            return Ok(status.to_string());
         }
         if crate::models::is_model_available(model_name) {
             Ok("available".to_string())
@@ -454,6 +836,14 @@ impl DaemonInterface {
         }
     }
 
+    /// SHA-256 of a fully-downloaded model's files, for the settings GUI to
+    /// compare against a catalog entry's expected checksum and detect that a
+    /// newer revision has been published. Empty if the model isn't fully
+    /// downloaded yet.
+    fn installed_model_sha256(&self, model_name: &str) -> zbus::fdo::Result<String> {
+        Ok(crate::model_download::installed_sha256(model_name).unwrap_or_default())
+    }
+
     #[zbus(signal)]
     async fn transcription_complete(
         ctxt: &zbus::object_server::SignalEmitter<'_>,
@@ -461,15 +851,178 @@ impl DaemonInterface {
     ) -> zbus::Result<()>;
 
     #[zbus(signal)]
-    async fn error_occurred(
+    async fn transcription_partial(
         ctxt: &zbus::object_server::SignalEmitter<'_>,
-        message: &str,
+        text: &str,
     ) -> zbus::Result<()>;
 
     #[zbus(signal)]
-    async fn download_progress(
+    async fn error_occurred(
         ctxt: &zbus::object_server::SignalEmitter<'_>,
-        model_name: &str,
-        percent: u8,
+        message: &str,
     ) -> zbus::Result<()>;
 }
+
+/// Resolve each provider's API key for in-memory use: a key left blank by
+/// the GUI is refilled from the secret store, and a key the GUI sent inline
+/// is mirrored into the secret store so later snapshots can redact it.
+/// Leaves keys untouched when no secret service is reachable.
+fn resolve_transcriber_secrets(config: &mut voxkey_ipc::TranscriberConfig) {
+    for (account, api_key) in config.secret_fields_mut() {
+        if api_key.is_empty() {
+            if let Some(secret) = crate::secret_store::load(account) {
+                *api_key = secret;
+            }
+        } else {
+            let _ = crate::secret_store::store(account, api_key);
+        }
+    }
+}
+
+/// Blank any API key that's recoverable from the secret store, for config
+/// snapshots that get persisted to disk or exposed over D-Bus. Keys with no
+/// corresponding secret-store entry (e.g. no secret service is reachable)
+/// are left as-is.
+fn redact_transcriber_secrets(
+    config: &voxkey_ipc::TranscriberConfig,
+) -> voxkey_ipc::TranscriberConfig {
+    let mut redacted = config.clone();
+    for (account, api_key) in redacted.secret_fields_mut() {
+        if crate::secret_store::has(account) {
+            api_key.clear();
+        }
+    }
+    redacted
+}
+
+/// How often to re-enumerate input devices for hotplug detection. cpal has
+/// no push-based device-change notification, so this polls the host's
+/// device registry (backed by PipeWire/ALSA) instead.
+const INPUT_DEVICE_WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Spawn a background task that periodically re-enumerates input devices and
+/// pushes an updated `input_devices` property whenever the available devices
+/// change, so the settings GUI's device picker reflects hotplug events
+/// without a daemon restart.
+pub fn spawn_input_device_watcher(shared: SharedState, connection: zbus::Connection) {
+    tokio::spawn(async move {
+        let mut known = shared.input_devices();
+        loop {
+            tokio::time::sleep(INPUT_DEVICE_WATCH_INTERVAL).await;
+            let devices = match tokio::task::spawn_blocking(Recorder::list_input_devices).await {
+                Ok(Ok(devices)) => devices.into_iter().map(|d| d.name).collect::<Vec<_>>(),
+                Ok(Err(e)) => {
+                    tracing::warn!("Failed to enumerate input devices: {e}");
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!("Input device enumeration task panicked: {e}");
+                    continue;
+                }
+            };
+            if devices != known {
+                tracing::info!("Input devices changed: {devices:?}");
+                known = devices.clone();
+                shared.set_input_devices(devices);
+                DaemonInterface::notify_input_devices(&connection).await;
+            }
+        }
+    });
+}
+
+/// Spawn a background task that mirrors the download manager's queue into
+/// the `download_queue` D-Bus property, and reports a newly `Failed` job's
+/// message through `last_error` — the same side effects the old per-download
+/// watcher task used to produce inline before downloads moved behind a queue.
+pub fn spawn_download_queue_watcher(shared: SharedState, connection: zbus::Connection) {
+    tokio::spawn(async move {
+        let mut rx = shared.download_manager().subscribe();
+        let mut previously_failed = std::collections::HashSet::new();
+        loop {
+            let queue = rx.borrow().clone();
+            DaemonInterface::notify_download_queue(&connection).await;
+            for job in &queue {
+                if job.state == voxkey_ipc::DownloadJobState::InProgress {
+                    shared.publish_event(DaemonEvent::DownloadProgress {
+                        model_name: job.model_name.clone(),
+                        percent: job.percent,
+                    });
+                } else if job.state == voxkey_ipc::DownloadJobState::Failed && previously_failed.insert(job.job_id) {
+                    shared.set_last_error_and_publish(format!("Download failed for {}: {}", job.model_name, job.error));
+                }
+            }
+            if rx.changed().await.is_err() {
+                return;
+            }
+        }
+    });
+}
+
+/// Subscribe to `SharedState`'s `DaemonEvent` broadcast and translate each
+/// event into the zbus property-changed signal (and MQTT publish) the event
+/// loop used to drive directly via the `notify_state`/`notify_portal_connected`/
+/// `notify_last_error`/`notify_last_transcript` helpers. Letting this run as
+/// an independent subscriber, rather than a call inline at every state
+/// change, means a second transport (e.g. the control-socket gateway) can
+/// subscribe to the same `DaemonEvent` stream without the event loop knowing
+/// it exists.
+pub fn spawn_event_bridge(shared: SharedState, connection: zbus::Connection) {
+    tokio::spawn(async move {
+        let mut events = shared.events();
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Event bridge lagged, skipped {skipped} events");
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            };
+
+            let iface_ref = connection
+                .object_server()
+                .interface::<_, DaemonInterface>(voxkey_ipc::OBJECT_PATH)
+                .await
+                .ok();
+
+            match &event {
+                DaemonEvent::StateChanged(state) => {
+                    if let Some(iface_ref) = &iface_ref {
+                        let _ = iface_ref.get().await.state_changed(iface_ref.signal_emitter()).await;
+                    }
+                    if let Some(bridge) = shared.mqtt() {
+                        bridge.publish_state(*state).await;
+                    }
+                }
+                DaemonEvent::PortalConnected(_) => {
+                    if let Some(iface_ref) = &iface_ref {
+                        let _ = iface_ref.get().await.portal_connected_changed(iface_ref.signal_emitter()).await;
+                    }
+                }
+                DaemonEvent::LastError(message) => {
+                    if let Some(iface_ref) = &iface_ref {
+                        let _ = iface_ref.get().await.last_error_changed(iface_ref.signal_emitter()).await;
+                    }
+                    if !message.is_empty() {
+                        if let Some(bridge) = shared.mqtt() {
+                            bridge.publish_error(message).await;
+                        }
+                    }
+                }
+                DaemonEvent::TranscriptionComplete(text) => {
+                    if let Some(iface_ref) = &iface_ref {
+                        let _ = iface_ref.get().await.last_transcript_changed(iface_ref.signal_emitter()).await;
+                    }
+                    if let Some(bridge) = shared.mqtt() {
+                        bridge.publish_transcript(text).await;
+                    }
+                }
+                DaemonEvent::DownloadProgress { model_name, percent } => {
+                    if let Some(bridge) = shared.mqtt() {
+                        bridge.publish_download_progress(model_name, *percent).await;
+                    }
+                }
+            }
+        }
+    });
+}
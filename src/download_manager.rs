@@ -0,0 +1,325 @@
+// ABOUTME: Serializes queued Parakeet model downloads into a single job queue with persisted status.
+// ABOUTME: Jobs survive a daemon restart; an interrupted download resumes via model_download's .part-file logic.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, watch};
+
+use voxkey_ipc::{DownloadJobState, DownloadJobStatus};
+
+use crate::model_download::DownloadStatus;
+
+/// A queued download, persisted to `queue_path()` so it survives a daemon
+/// restart. `url_override`/`sha256_override` are kept here (not on
+/// `DownloadJobStatus`) so a reconciled job can resume with the same
+/// parameters it was enqueued with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Job {
+    id: u64,
+    model_name: String,
+    url_override: Option<String>,
+    sha256_override: Option<String>,
+    state: DownloadJobState,
+    percent: u8,
+    downloaded_bytes: u64,
+    total_bytes: u64,
+    bytes_per_sec: f64,
+    error: String,
+}
+
+impl From<&Job> for DownloadJobStatus {
+    fn from(job: &Job) -> Self {
+        DownloadJobStatus {
+            job_id: job.id,
+            model_name: job.model_name.clone(),
+            state: job.state,
+            percent: job.percent,
+            downloaded_bytes: job.downloaded_bytes,
+            total_bytes: job.total_bytes,
+            bytes_per_sec: job.bytes_per_sec,
+            error: job.error.clone(),
+        }
+    }
+}
+
+/// Any job left `Pending`/`InProgress` by an unclean shutdown is reset to
+/// `Pending` so the worker retries it; `model_download`'s existing
+/// `.part`-file resume logic picks the transfer back up from disk.
+fn reconcile(mut jobs: Vec<Job>) -> Vec<Job> {
+    for job in &mut jobs {
+        if job.state == DownloadJobState::InProgress {
+            job.state = DownloadJobState::Pending;
+            job.percent = 0;
+        }
+    }
+    jobs
+}
+
+fn queue_path() -> std::path::PathBuf {
+    let models_dir = crate::models::models_dir();
+    match models_dir.parent() {
+        Some(parent) => parent.join("download_queue.json"),
+        None => models_dir.join("download_queue.json"),
+    }
+}
+
+fn load_queue() -> Vec<Job> {
+    std::fs::read_to_string(queue_path())
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn persist_queue(jobs: &[Job]) {
+    let path = queue_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(jobs) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+struct ManagerInner {
+    jobs: Vec<Job>,
+    next_id: u64,
+    /// The job currently being downloaded and its cancellation flag, if any.
+    active: Option<(u64, Arc<AtomicBool>)>,
+}
+
+/// Coordinates queued model downloads: assigns each a job id, runs exactly
+/// one at a time, persists queue state to disk after every change, and
+/// broadcasts the queue to anything subscribed via [`DownloadManager::subscribe`].
+#[derive(Clone)]
+pub struct DownloadManager {
+    inner: Arc<Mutex<ManagerInner>>,
+    wake_tx: mpsc::UnboundedSender<()>,
+    status_tx: Arc<watch::Sender<Vec<DownloadJobStatus>>>,
+}
+
+impl DownloadManager {
+    /// Load the persisted queue, reconcile it, and spawn the background
+    /// worker that runs queued jobs one at a time.
+    pub fn new() -> Self {
+        let jobs = reconcile(load_queue());
+        persist_queue(&jobs);
+        let next_id = jobs.iter().map(|j| j.id + 1).max().unwrap_or(0);
+        let has_pending = jobs.iter().any(|j| j.state == DownloadJobState::Pending);
+        let (status_tx, _) = watch::channel(jobs.iter().map(DownloadJobStatus::from).collect());
+        let (wake_tx, wake_rx) = mpsc::unbounded_channel();
+
+        let manager = Self {
+            inner: Arc::new(Mutex::new(ManagerInner { jobs, next_id, active: None })),
+            wake_tx,
+            status_tx: Arc::new(status_tx),
+        };
+        manager.clone().spawn_worker(wake_rx);
+        if has_pending {
+            let _ = manager.wake_tx.send(());
+        }
+        manager
+    }
+
+    /// Subscribe to the queue; fires whenever a job is added, cancelled, or
+    /// changes progress/state.
+    pub fn subscribe(&self) -> watch::Receiver<Vec<DownloadJobStatus>> {
+        self.status_tx.subscribe()
+    }
+
+    pub fn statuses(&self) -> Vec<DownloadJobStatus> {
+        self.inner.lock().unwrap().jobs.iter().map(DownloadJobStatus::from).collect()
+    }
+
+    /// Queue a model download and return its job id. Wakes the worker if
+    /// nothing is currently downloading.
+    pub fn enqueue(&self, model_name: String, url_override: Option<String>, sha256_override: Option<String>) -> u64 {
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.jobs.push(Job {
+            id,
+            model_name,
+            url_override,
+            sha256_override,
+            state: DownloadJobState::Pending,
+            percent: 0,
+            downloaded_bytes: 0,
+            total_bytes: 0,
+            bytes_per_sec: 0.0,
+            error: String::new(),
+        });
+        persist_queue(&inner.jobs);
+        self.publish(&inner.jobs);
+        let _ = self.wake_tx.send(());
+        id
+    }
+
+    /// Cancel a job by id: a still-queued job is dropped outright, while the
+    /// job currently downloading is signaled to stop at its next checkpoint.
+    /// Returns `true` if a pending or active job with that id was found; a
+    /// job that has already finished is left alone and returns `false`.
+    pub fn cancel(&self, job_id: u64) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some((active_id, cancel)) = &inner.active {
+            if *active_id == job_id {
+                cancel.store(true, Ordering::Relaxed);
+                return true;
+            }
+        }
+        let before = inner.jobs.len();
+        inner.jobs.retain(|j| j.id != job_id || j.state != DownloadJobState::Pending);
+        let removed = inner.jobs.len() != before;
+        if removed {
+            persist_queue(&inner.jobs);
+            self.publish(&inner.jobs);
+        }
+        removed
+    }
+
+    fn publish(&self, jobs: &[Job]) {
+        let _ = self.status_tx.send(jobs.iter().map(DownloadJobStatus::from).collect());
+    }
+
+    fn update_job(&self, id: u64, f: impl FnOnce(&mut Job)) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(job) = inner.jobs.iter_mut().find(|j| j.id == id) {
+            f(job);
+        }
+        persist_queue(&inner.jobs);
+        self.publish(&inner.jobs);
+    }
+
+    fn spawn_worker(self, mut wake_rx: mpsc::UnboundedReceiver<()>) {
+        tokio::spawn(async move {
+            loop {
+                let next = {
+                    let inner = self.inner.lock().unwrap();
+                    inner
+                        .jobs
+                        .iter()
+                        .find(|j| j.state == DownloadJobState::Pending)
+                        .map(|j| (j.id, j.model_name.clone(), j.url_override.clone(), j.sha256_override.clone()))
+                };
+                match next {
+                    Some((id, model_name, url_override, sha256_override)) => {
+                        self.run_job(id, model_name, url_override, sha256_override).await;
+                    }
+                    None => {
+                        if wake_rx.recv().await.is_none() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    async fn run_job(&self, id: u64, model_name: String, url_override: Option<String>, sha256_override: Option<String>) {
+        self.update_job(id, |job| job.state = DownloadJobState::InProgress);
+        let (mut rx, cancel) = crate::model_download::start_download(model_name, url_override, sha256_override);
+        self.inner.lock().unwrap().active = Some((id, cancel));
+
+        while rx.changed().await.is_ok() {
+            match rx.borrow().clone() {
+                DownloadStatus::InProgress { percent, downloaded_bytes, total_bytes, bytes_per_sec, .. } => {
+                    self.update_job(id, |job| {
+                        job.percent = percent;
+                        job.downloaded_bytes = downloaded_bytes;
+                        job.total_bytes = total_bytes;
+                        job.bytes_per_sec = bytes_per_sec;
+                    });
+                }
+                DownloadStatus::Verifying => {
+                    self.update_job(id, |job| job.state = DownloadJobState::Verifying);
+                }
+                DownloadStatus::Complete => {
+                    self.update_job(id, |job| {
+                        job.state = DownloadJobState::Complete;
+                        job.percent = 100;
+                    });
+                    break;
+                }
+                DownloadStatus::Cancelled => {
+                    self.update_job(id, |job| job.state = DownloadJobState::Cancelled);
+                    break;
+                }
+                DownloadStatus::ChecksumFailed { expected, actual } => {
+                    self.update_job(id, |job| {
+                        job.state = DownloadJobState::ChecksumFailed;
+                        job.error = format!("expected {expected}, got {actual}");
+                    });
+                    break;
+                }
+                DownloadStatus::Failed(msg) => {
+                    self.update_job(id, |job| {
+                        job.state = DownloadJobState::Failed;
+                        job.error = msg;
+                    });
+                    break;
+                }
+            }
+        }
+        self.inner.lock().unwrap().active = None;
+    }
+}
+
+impl Default for DownloadManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: u64, state: DownloadJobState) -> Job {
+        Job {
+            id,
+            model_name: "parakeet-tdt-0.6b-v3".to_string(),
+            url_override: None,
+            sha256_override: None,
+            state,
+            percent: 42,
+            downloaded_bytes: 100,
+            total_bytes: 200,
+            bytes_per_sec: 0.0,
+            error: String::new(),
+        }
+    }
+
+    #[test]
+    fn reconcile_resets_in_progress_jobs_to_pending() {
+        let jobs = reconcile(vec![job(0, DownloadJobState::InProgress)]);
+        assert_eq!(jobs[0].state, DownloadJobState::Pending);
+        assert_eq!(jobs[0].percent, 0);
+    }
+
+    #[test]
+    fn reconcile_leaves_terminal_jobs_alone() {
+        let jobs = reconcile(vec![job(0, DownloadJobState::Complete)]);
+        assert_eq!(jobs[0].state, DownloadJobState::Complete);
+        assert_eq!(jobs[0].percent, 42);
+    }
+
+    #[test]
+    fn job_status_conversion_drops_download_parameters() {
+        let mut j = job(7, DownloadJobState::Pending);
+        j.url_override = Some("https://example.com/x".to_string());
+        j.sha256_override = Some("deadbeef".to_string());
+        let status = DownloadJobStatus::from(&j);
+        assert_eq!(status.job_id, 7);
+        assert_eq!(status.model_name, "parakeet-tdt-0.6b-v3");
+    }
+
+    #[test]
+    fn queue_round_trips_through_json() {
+        let jobs = vec![job(0, DownloadJobState::Pending), job(1, DownloadJobState::Failed)];
+        let json = serde_json::to_string(&jobs).unwrap();
+        let parsed: Vec<Job> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].state, DownloadJobState::Failed);
+    }
+}
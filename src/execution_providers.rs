@@ -0,0 +1,85 @@
+// ABOUTME: Probes which ONNX Runtime execution providers are usable on this machine.
+// ABOUTME: Resolves ExecutionProviderChoice::Auto to the best provider actually available.
+
+use voxkey_ipc::ExecutionProviderChoice;
+
+/// Best-effort probe of which execution providers are actually usable on this
+/// machine. This checks for the OS/hardware markers each provider depends on
+/// rather than loading ONNX Runtime's provider libraries directly, so it can
+/// run cheaply at startup; a false positive just means ONNX Runtime falls
+/// back to CPU at model-load time instead of the settings GUI graying it out.
+pub fn available_providers() -> Vec<ExecutionProviderChoice> {
+    let mut available = vec![ExecutionProviderChoice::Cpu];
+
+    if std::path::Path::new("/proc/driver/nvidia").exists() {
+        available.push(ExecutionProviderChoice::Cuda);
+        // TensorRT is an optimization layer on top of the same NVIDIA driver/CUDA stack.
+        available.push(ExecutionProviderChoice::TensorRt);
+    }
+    if cfg!(target_os = "macos") {
+        available.push(ExecutionProviderChoice::CoreMl);
+    }
+    if cfg!(target_os = "windows") {
+        available.push(ExecutionProviderChoice::DirectMl);
+    }
+    if std::path::Path::new("/dev/kfd").exists() {
+        available.push(ExecutionProviderChoice::Rocm);
+    }
+
+    available
+}
+
+/// Resolve `Auto` to the best concrete provider actually available, preferring
+/// vendor-accelerated providers over CPU. A non-`Auto` choice passes through
+/// unchanged regardless of what's detected, so an explicit user choice always wins.
+pub fn resolve(
+    choice: ExecutionProviderChoice,
+    available: &[ExecutionProviderChoice],
+) -> ExecutionProviderChoice {
+    if choice != ExecutionProviderChoice::Auto {
+        return choice;
+    }
+    const PREFERENCE: &[ExecutionProviderChoice] = &[
+        ExecutionProviderChoice::TensorRt,
+        ExecutionProviderChoice::Cuda,
+        ExecutionProviderChoice::Rocm,
+        ExecutionProviderChoice::CoreMl,
+        ExecutionProviderChoice::DirectMl,
+        ExecutionProviderChoice::Cpu,
+    ];
+    PREFERENCE
+        .iter()
+        .copied()
+        .find(|p| available.contains(p))
+        .unwrap_or(ExecutionProviderChoice::Cpu)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_passes_through_explicit_choice() {
+        assert_eq!(
+            resolve(ExecutionProviderChoice::Cpu, &[ExecutionProviderChoice::Cuda]),
+            ExecutionProviderChoice::Cpu
+        );
+    }
+
+    #[test]
+    fn resolve_auto_prefers_cuda_over_cpu() {
+        let available = vec![ExecutionProviderChoice::Cpu, ExecutionProviderChoice::Cuda];
+        assert_eq!(resolve(ExecutionProviderChoice::Auto, &available), ExecutionProviderChoice::Cuda);
+    }
+
+    #[test]
+    fn resolve_auto_falls_back_to_cpu() {
+        let available = vec![ExecutionProviderChoice::Cpu];
+        assert_eq!(resolve(ExecutionProviderChoice::Auto, &available), ExecutionProviderChoice::Cpu);
+    }
+
+    #[test]
+    fn available_providers_always_includes_cpu() {
+        assert!(available_providers().contains(&ExecutionProviderChoice::Cpu));
+    }
+}
@@ -1,14 +1,18 @@
 // ABOUTME: Converts transcript text to keysym press/release events for keyboard injection.
 // ABOUTME: Maps Unicode codepoints to keysyms via libxkbcommon, handles special controls.
 
-use std::process::Stdio;
+use std::fmt;
 
 use tokio::sync::mpsc;
 use xkbcommon::xkb;
 use xkbcommon::xkb::keysyms;
 
-use crate::dbus::SharedState;
+use crate::clipboard::{self, ClipboardProvider};
+use crate::compose::ComposeFallback;
+use crate::dbus::{SavedClipboard, SharedState};
 use crate::desktop::DesktopController;
+use crate::keymap::{Level, LiveKeymap};
+use crate::text_input::TextInputController;
 
 /// Keysym constants for special control characters.
 const XKB_KEY_RETURN: i32 = 0xff0d;
@@ -18,8 +22,32 @@ const XKB_KEY_TAB: i32 = 0xff09;
 const XKB_KEY_CONTROL_L: i32 = 0xffe3;
 const XKB_KEY_V_LOWER: i32 = 0x0076;
 
-/// Small delay between keystrokes to avoid compositor dropping events.
-const KEYSTROKE_DELAY: std::time::Duration = std::time::Duration::from_millis(5);
+/// Keysym constant for holding Shift around a tap when `LiveKeymap` says the
+/// target keysym lives on the shift level of the compositor's active layout.
+const XKB_KEY_SHIFT_L: i32 = 0xffe1;
+
+/// Keysym constant for retracting previously-injected characters.
+const XKB_KEY_BACKSPACE: i32 = 0xff08;
+
+/// Errors raised while typing characters via `inject_text`. Distinguishes a
+/// portal/desktop-session failure (fatal — the streaming session should stop)
+/// from a non-fatal local failure that's safe to log and keep typing past.
+#[derive(Debug)]
+pub enum InjectionError {
+    Portal(Box<dyn std::error::Error + Send + Sync>),
+    Local(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl fmt::Display for InjectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InjectionError::Portal(e) => write!(f, "portal error: {e}"),
+            InjectionError::Local(e) => write!(f, "local error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for InjectionError {}
 
 /// Processes text injection requests serially via a channel.
 pub struct Injector {
@@ -29,18 +57,81 @@ pub struct Injector {
 impl Injector {
     /// Create an injector that sends keysym events through the given desktop controller.
     /// Spawns a background task that processes the injection queue serially.
+    /// `keymap` is the compositor's live keymap (see [`crate::keymap::LiveKeymap`])
+    /// and `compose` the dead-key fallback (see [`crate::compose::ComposeFallback`]),
+    /// both shared with the streaming injection paths so the fallback
+    /// keystroke path behaves identically everywhere, not just here. The
+    /// clipboard provider (see [`crate::clipboard`]) is detected once here,
+    /// from the configured preference and whatever's actually on `$PATH`.
     pub fn new(
         desktop: std::sync::Arc<DesktopController>,
         state_tx: mpsc::Sender<crate::state::Event>,
         shared: SharedState,
+        keymap: std::sync::Arc<Option<LiveKeymap>>,
+        compose: std::sync::Arc<Option<std::sync::Mutex<ComposeFallback>>>,
     ) -> Self {
         let (tx, mut rx) = mpsc::channel::<String>(32);
 
+        let clipboard_provider =
+            std::sync::Arc::new(clipboard::detect_provider(shared.config().injection.clipboard_provider));
+        match &*clipboard_provider {
+            Some(provider) => tracing::info!("Using {} for clipboard paste", provider.name()),
+            None => tracing::debug!(
+                "No clipboard provider found (wl-copy/xclip/xsel all missing from $PATH); \
+                 clipboard-paste output mode will fail"
+            ),
+        }
+
+        let text_input = match TextInputController::new() {
+            Ok(Some(controller)) => {
+                tracing::info!("zwp_text_input_v3 available; preferring direct text insertion");
+                Some(std::sync::Arc::new(controller))
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::debug!(
+                    "No zwp_text_input_v3 support ({e}); using clipboard/keystroke injection"
+                );
+                None
+            }
+        };
+
         tokio::spawn(async move {
             while let Some(text) = rx.recv().await {
                 let _ = state_tx.send(crate::state::Event::TranscriptReady).await;
 
-                match paste_text(&desktop, &text).await {
+                let committed_directly = text_input.as_ref().and_then(|controller| {
+                    match controller.commit_text(&text) {
+                        Ok(true) => Some(Ok(())),
+                        Ok(false) => None,
+                        Err(e) => {
+                            tracing::warn!(
+                                "Direct text-input commit failed ({e}), falling back to clipboard/keystrokes"
+                            );
+                            None
+                        }
+                    }
+                });
+
+                let result = match committed_directly {
+                    Some(result) => result,
+                    None => {
+                        let injection = shared.config().injection.clone();
+                        emit_text(
+                            &desktop,
+                            &shared,
+                            &keymap,
+                            &compose,
+                            clipboard_provider.as_ref().as_ref().map(|p| p.as_ref()),
+                            &text,
+                            injection.mode,
+                            injection.typing_delay_ms,
+                        )
+                        .await
+                    }
+                };
+
+                match result {
                     Ok(()) => {
                         let _ = state_tx.send(crate::state::Event::InjectionDone).await;
                     }
@@ -63,18 +154,78 @@ impl Injector {
     }
 }
 
-/// Paste text via the Wayland clipboard (wl-copy) and Ctrl+V through the portal.
+/// Emit a full block of text according to the configured [`voxkey_ipc::OutputMode`].
+#[allow(clippy::too_many_arguments)]
+async fn emit_text(
+    desktop: &DesktopController,
+    shared: &SharedState,
+    keymap: &Option<LiveKeymap>,
+    compose: &Option<std::sync::Mutex<ComposeFallback>>,
+    clipboard_provider: Option<&dyn ClipboardProvider>,
+    text: &str,
+    mode: voxkey_ipc::OutputMode,
+    typing_delay_ms: u32,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    match mode {
+        voxkey_ipc::OutputMode::ClipboardPaste => {
+            paste_text(desktop, shared, keymap, compose, clipboard_provider, text, typing_delay_ms).await
+        }
+        voxkey_ipc::OutputMode::Keystrokes => {
+            let typing_delay = std::time::Duration::from_millis(typing_delay_ms as u64);
+            inject_text(desktop, keymap, compose, text, typing_delay)
+                .await
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+        }
+        voxkey_ipc::OutputMode::Both => {
+            let typing_delay = std::time::Duration::from_millis(typing_delay_ms as u64);
+            if let Err(e) = inject_text(desktop, keymap, compose, text, typing_delay).await {
+                tracing::warn!("Keystroke injection failed ({e}), falling back to clipboard paste");
+                paste_text(desktop, shared, keymap, compose, clipboard_provider, text, typing_delay_ms).await
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+/// How long to wait for the compositor to report our clipboard write back
+/// before giving up on the paste and falling back to keystroke injection.
+const CLIPBOARD_ACK_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+/// How often to poll the clipboard provider while waiting for that acknowledgement.
+const CLIPBOARD_ACK_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// Paste text via the detected clipboard provider (see [`crate::clipboard`])
+/// and Ctrl+V through the portal. Saves whatever was on the clipboard
+/// beforehand and restores it once the paste completes, so dictation doesn't
+/// silently clobber a copied URL or snippet; the save/restore is best-effort
+/// and never fails the paste. Waits for the provider to report our own bytes
+/// back before sending Ctrl+V, so the paste can't race ahead of the
+/// compositor granting us the selection and fire against whatever was there
+/// before.
+#[allow(clippy::too_many_arguments)]
 async fn paste_text(
     desktop: &DesktopController,
+    shared: &SharedState,
+    keymap: &Option<LiveKeymap>,
+    compose: &Option<std::sync::Mutex<ComposeFallback>>,
+    clipboard_provider: Option<&dyn ClipboardProvider>,
     text: &str,
+    typing_delay_ms: u32,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    // Set clipboard content via wl-copy
-    let mut child = tokio::process::Command::new("wl-copy")
-        .stdin(Stdio::piped())
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
+    let Some(provider) = clipboard_provider else {
+        return Err(
+            "No clipboard tool available (checked wl-copy, xclip, xsel); \
+             install one or switch the injection mode to keystrokes"
+                .into(),
+        );
+    };
+
+    shared.set_saved_clipboard(capture_clipboard(provider).await);
+
+    // Set clipboard content via the detected provider.
+    let mut child = clipboard::copy_command(provider, None)
         .spawn()
-        .map_err(|e| format!("Failed to run wl-copy (is wl-clipboard installed?): {e}"))?;
+        .map_err(|e| format!("Failed to run {} (is it installed?): {e}", provider.name()))?;
 
     if let Some(mut stdin) = child.stdin.take() {
         use tokio::io::AsyncWriteExt;
@@ -83,35 +234,233 @@ async fn paste_text(
 
     let status = child.wait().await?;
     if !status.success() {
-        return Err("wl-copy failed".into());
+        return Err(format!("{} failed", provider.name()).into());
     }
 
-    // Brief pause to let the clipboard settle
-    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    if !wait_for_clipboard_ownership(provider, text).await {
+        tracing::warn!(
+            "Clipboard didn't report our content within {CLIPBOARD_ACK_TIMEOUT:?}, \
+             falling back to keystroke injection"
+        );
+        restore_clipboard(provider, shared.take_saved_clipboard()).await;
+        let typing_delay = std::time::Duration::from_millis(typing_delay_ms as u64);
+        return inject_text(desktop, keymap, compose, text, typing_delay)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>);
+    }
 
     // Simulate Ctrl+V
     desktop.press_keysym(XKB_KEY_CONTROL_L).await?;
     desktop.tap_keysym(XKB_KEY_V_LOWER).await?;
     desktop.release_keysym(XKB_KEY_CONTROL_L).await?;
 
+    restore_clipboard(provider, shared.take_saved_clipboard()).await;
+
+    Ok(())
+}
+
+/// Poll the clipboard provider until it reports back the exact bytes we just
+/// wrote, bounded by `CLIPBOARD_ACK_TIMEOUT`. Returns `false` on timeout.
+async fn wait_for_clipboard_ownership(provider: &dyn ClipboardProvider, text: &str) -> bool {
+    let deadline = std::time::Instant::now() + CLIPBOARD_ACK_TIMEOUT;
+    loop {
+        let output = clipboard::paste_command(provider, None).output().await;
+        if let Ok(output) = output {
+            if output.status.success() && output.stdout == text.as_bytes() {
+                return true;
+            }
+        }
+        if std::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(CLIPBOARD_ACK_POLL_INTERVAL).await;
+    }
+}
+
+/// Best-effort snapshot of the clipboard's current content and primary MIME
+/// type, taken right before `paste_text` overwrites it. `None` if the
+/// clipboard is empty, couldn't be read (no clipboard manager running), or
+/// the provider can't enumerate MIME types at all (xsel) — the paste
+/// proceeds regardless.
+async fn capture_clipboard(provider: &dyn ClipboardProvider) -> Option<SavedClipboard> {
+    let types_output = clipboard::list_types_command(provider)?.output().await.ok()?;
+    if !types_output.status.success() {
+        return None;
+    }
+    let mime_type = String::from_utf8_lossy(&types_output.stdout)
+        .lines()
+        .next()?
+        .trim()
+        .to_string();
+    if mime_type.is_empty() {
+        return None;
+    }
+
+    let data_output = clipboard::paste_command(provider, Some(&mime_type)).output().await.ok()?;
+    if !data_output.status.success() {
+        return None;
+    }
+
+    Some(SavedClipboard { mime_type, data: data_output.stdout })
+}
+
+/// Best-effort restore of the snapshot `capture_clipboard` took — or clears
+/// the clipboard if there is no snapshot (it was empty, couldn't be read, or
+/// the provider can't enumerate MIME types). Failures are just logged; a
+/// stale clipboard is an annoyance, not worth failing the injection over.
+async fn restore_clipboard(provider: &dyn ClipboardProvider, saved: Option<SavedClipboard>) {
+    let result = match saved {
+        Some(saved) => restore_saved_clipboard(provider, saved).await,
+        None => clear_clipboard(provider).await,
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to restore clipboard after dictation paste: {e}");
+    }
+}
+
+async fn clear_clipboard(provider: &dyn ClipboardProvider) -> std::io::Result<()> {
+    let mut child = clipboard::clear_command(provider).spawn()?;
+
+    // `Xclip::clear_command` clears by copying an empty selection, so it
+    // expects stdin to be closed; other providers' real clear commands just
+    // ignore it.
+    if let Some(mut stdin) = child.stdin.take() {
+        use tokio::io::AsyncWriteExt;
+        stdin.write_all(&[]).await?;
+    }
+
+    child.wait().await.map(|_| ())
+}
+
+async fn restore_saved_clipboard(
+    provider: &dyn ClipboardProvider,
+    saved: SavedClipboard,
+) -> std::io::Result<()> {
+    let mut child = clipboard::copy_command(provider, Some(&saved.mime_type)).spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        use tokio::io::AsyncWriteExt;
+        stdin.write_all(&saved.data).await?;
+    }
+
+    child.wait().await?;
     Ok(())
 }
 
 /// Inject the given text by mapping each character to a keysym and sending press/release.
+/// `typing_delay` paces keystrokes to avoid the compositor dropping events.
+/// When `keymap` is available, a keysym that only lives on the shift level of
+/// the compositor's active layout is sent with Shift held, instead of relying
+/// on the portal backend to resolve the bare keysym against whatever layout
+/// it has loaded — which is the step that misfires on non-US layouts. When
+/// the compositor's layout has no key for a character's keysym at all,
+/// `compose` is tried as a dead-key fallback before falling back to tapping
+/// the unreachable keysym directly.
 pub async fn inject_text(
     desktop: &DesktopController,
+    keymap: &Option<LiveKeymap>,
+    compose: &Option<std::sync::Mutex<ComposeFallback>>,
     text: &str,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    typing_delay: std::time::Duration,
+) -> Result<(), InjectionError> {
     for ch in text.chars() {
         let keysym = char_to_keysym(ch);
 
-        if keysym == 0 {
-            tracing::debug!("Skipping character with no keysym: U+{:04X}", ch as u32);
+        if keysym != 0 && !needs_compose_fallback(keymap, keysym) {
+            tap_with_shift(desktop, keymap, keysym, typing_delay).await?;
             continue;
         }
 
-        desktop.tap_keysym(keysym).await?;
-        tokio::time::sleep(KEYSTROKE_DELAY).await;
+        let sequence = compose
+            .as_ref()
+            .and_then(|c| c.lock().unwrap().sequence_for_char(ch));
+
+        match sequence {
+            Some(sequence) => {
+                for keysym in sequence {
+                    tap_with_shift(desktop, keymap, keysym, typing_delay).await?;
+                }
+            }
+            None if keysym != 0 => {
+                // No compose fallback available (or the locale's compose
+                // rules don't produce this char) — best effort: tap the
+                // keysym directly even though this layout has no key for it.
+                tap_with_shift(desktop, keymap, keysym, typing_delay).await?;
+            }
+            None => {
+                tracing::debug!("Skipping character with no keysym: U+{:04X}", ch as u32);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `keysym` is worth trying the compose fallback for: the keymap is
+/// known and has no key (base or shift level) that produces it on the
+/// compositor's active layout, so tapping it directly would either misfire
+/// or rely on the portal backend resolving a keysym its own layout can't
+/// reach. `keymap == None` (no live keymap available at all) can't tell us
+/// this, so it's left to the existing direct-tap path as before.
+fn needs_compose_fallback(keymap: &Option<LiveKeymap>, keysym: i32) -> bool {
+    matches!(keymap, Some(km) if km.level_for_keysym(keysym).is_none())
+}
+
+/// Tap a single keysym, holding Shift around it first if `keymap` says the
+/// keysym only lives on the shift level of the compositor's active layout.
+async fn tap_with_shift(
+    desktop: &DesktopController,
+    keymap: &Option<LiveKeymap>,
+    keysym: i32,
+    typing_delay: std::time::Duration,
+) -> Result<(), InjectionError> {
+    let shift = matches!(
+        keymap.as_ref().and_then(|k| k.level_for_keysym(keysym)),
+        Some(Level::Shift)
+    );
+
+    if shift {
+        desktop
+            .press_keysym(XKB_KEY_SHIFT_L)
+            .await
+            .map_err(InjectionError::Portal)?;
+    }
+
+    desktop
+        .tap_keysym(keysym)
+        .await
+        .map_err(InjectionError::Portal)?;
+
+    if shift {
+        desktop
+            .release_keysym(XKB_KEY_SHIFT_L)
+            .await
+            .map_err(InjectionError::Portal)?;
+    }
+
+    tokio::time::sleep(typing_delay).await;
+    Ok(())
+}
+
+/// Delete `count` characters before the cursor via Backspace keysyms. Used to
+/// retract previously-injected volatile text before typing a correction.
+///
+/// Backspace failures are surfaced as `InjectionError::Local` rather than
+/// `Portal`: some injection targets accept ordinary keysyms but reject or
+/// ignore Backspace (no text field focused, a field that doesn't support
+/// retraction, etc), and that's exactly the non-fatal case callers should
+/// degrade to append-only mode for instead of tearing down the session.
+pub async fn inject_backspaces(
+    desktop: &DesktopController,
+    count: usize,
+    typing_delay: std::time::Duration,
+) -> Result<(), InjectionError> {
+    for _ in 0..count {
+        desktop
+            .tap_keysym(XKB_KEY_BACKSPACE)
+            .await
+            .map_err(InjectionError::Local)?;
+        tokio::time::sleep(typing_delay).await;
     }
 
     Ok(())
@@ -0,0 +1,179 @@
+// ABOUTME: Acquires the compositor's live keyboard keymap over wl_keyboard.
+// ABOUTME: Used by injector::inject_text to find which level a keysym lives on.
+
+use std::collections::HashMap;
+use std::os::fd::{AsRawFd, OwnedFd};
+
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::wl_keyboard::{self, WlKeyboard};
+use wayland_client::protocol::wl_registry::WlRegistry;
+use wayland_client::protocol::wl_seat::WlSeat;
+use wayland_client::{delegate_noop, Connection, Dispatch, QueueHandle};
+use xkbcommon::xkb;
+
+type DynError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Which shift level produces a keysym on the compositor's active keymap.
+/// Only base and shift levels are resolved — levels reached via AltGr or
+/// other modifiers aren't tracked, since `inject_text` has no way to
+/// synthesize those modifiers today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Base,
+    Shift,
+}
+
+#[derive(Default)]
+struct KeymapState {
+    keymap: Option<(OwnedFd, u32)>,
+}
+
+/// A flattened keysym-to-level lookup built once from the compositor's
+/// active keymap. The raw `xkb::Keymap`/`xkb::Context` aren't kept around
+/// (they're FFI wrappers with no business outliving the roundtrip that
+/// built them) — just this plain table, so it can be shared across the
+/// injector and streaming injection tasks like any other piece of state.
+pub struct LiveKeymap {
+    levels: HashMap<i32, Level>,
+}
+
+impl LiveKeymap {
+    /// Connect to the Wayland display, bind a keyboard off the default seat,
+    /// and wait for its `Keymap` event. Returns `Ok(None)` if no seat or
+    /// keyboard is advertised, or the keymap isn't in the XKB v1 text
+    /// format, so callers fall back to layout-unaware injection.
+    pub fn new() -> Result<Option<Self>, DynError> {
+        let conn = Connection::connect_to_env()?;
+        let (globals, mut queue) = registry_queue_init::<KeymapState>(&conn)?;
+        let qh = queue.handle();
+
+        let Ok(seat) = globals.bind::<WlSeat, _, _>(&qh, 1..=9, ()) else {
+            return Ok(None);
+        };
+        let _keyboard = seat.get_keyboard(&qh, ());
+
+        let mut state = KeymapState::default();
+        // The compositor sends Keymap immediately after the keyboard is
+        // bound, so a single roundtrip is enough to receive it.
+        queue.roundtrip(&mut state)?;
+
+        let Some((fd, size)) = state.keymap else {
+            return Ok(None);
+        };
+
+        let buffer = map_keymap_fd(fd, size as usize)?;
+        let context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+        let keymap_string = std::str::from_utf8(trim_trailing_nul(&buffer))?.to_string();
+        let keymap = xkb::Keymap::new_from_string(
+            &context,
+            keymap_string,
+            xkb::KEYMAP_FORMAT_TEXT_V1,
+            xkb::KEYMAP_COMPILE_NO_FLAGS,
+        )
+        .ok_or("compositor sent a keymap libxkbcommon couldn't parse")?;
+
+        Ok(Some(Self { levels: build_level_table(&keymap) }))
+    }
+
+    /// Which level produces `keysym` on the layout this table was built
+    /// from. `None` if the keysym isn't reachable via the base or shift
+    /// level — e.g. it's behind AltGr, or absent from this layout entirely —
+    /// in which case `inject_text` tries the compose fallback before tapping
+    /// it unshifted.
+    pub fn level_for_keysym(&self, keysym: i32) -> Option<Level> {
+        self.levels.get(&keysym).copied()
+    }
+}
+
+/// Scan every keycode's levels on layout 0 (single-layout assumption;
+/// multi-layout switching isn't tracked) and record which level each keysym
+/// comes from. Base-level keysyms are recorded first so that a keysym
+/// reachable at the base level on any key is never shadowed by a
+/// shift-level match on some other key.
+fn build_level_table(keymap: &xkb::Keymap) -> HashMap<i32, Level> {
+    let mut levels = HashMap::new();
+    for level_kind in [Level::Base, Level::Shift] {
+        let level = match level_kind {
+            Level::Base => 0,
+            Level::Shift => 1,
+        };
+        for code in keymap.min_keycode().raw()..=keymap.max_keycode().raw() {
+            let keycode = xkb::Keycode::new(code);
+            if level >= keymap.num_levels_for_key(keycode, 0) {
+                continue;
+            }
+            for sym in keymap.key_get_syms_by_level(keycode, 0, level) {
+                levels.entry(sym.raw() as i32).or_insert(level_kind);
+            }
+        }
+    }
+    levels
+}
+
+/// Strip the trailing NUL (and anything past it) that the compositor pads
+/// the keymap buffer with, so the remainder parses as a valid XKB string.
+fn trim_trailing_nul(buffer: &[u8]) -> &[u8] {
+    let end = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+    &buffer[..end]
+}
+
+/// Read the compositor's keymap out of the fd sent with the `Keymap` event.
+/// Newer protocol versions require a read-only `MAP_PRIVATE` mapping rather
+/// than reading the fd as a pipe, so we mmap it, copy the bytes out, and
+/// unmap immediately rather than holding the mapping open.
+fn map_keymap_fd(fd: OwnedFd, size: usize) -> Result<Vec<u8>, DynError> {
+    // Safety: `fd` and `size` come straight from the compositor's `Keymap`
+    // event and are valid for the lifetime of this call; the mapping is
+    // read-only and private, so we can never corrupt the compositor's copy.
+    let ptr = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            size,
+            libc::PROT_READ,
+            libc::MAP_PRIVATE,
+            fd.as_raw_fd(),
+            0,
+        )
+    };
+    if ptr == libc::MAP_FAILED {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    let data = unsafe { std::slice::from_raw_parts(ptr as *const u8, size) }.to_vec();
+    unsafe {
+        libc::munmap(ptr, size);
+    }
+    Ok(data)
+}
+
+impl Dispatch<WlKeyboard, ()> for KeymapState {
+    fn event(
+        state: &mut Self,
+        _keyboard: &WlKeyboard,
+        event: wl_keyboard::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        if let wl_keyboard::Event::Keymap { format, fd, size } = event {
+            // Only the XKB v1 text format is understood here; anything else
+            // (e.g. `NoKeymap`) is treated the same as receiving no keymap.
+            if format.into_result() == Ok(wl_keyboard::KeymapFormat::XkbV1) {
+                state.keymap = Some((fd, size));
+            }
+        }
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for KeymapState {
+    fn event(
+        _state: &mut Self,
+        _registry: &WlRegistry,
+        _event: wayland_client::protocol::wl_registry::Event,
+        _data: &GlobalListContents,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+delegate_noop!(KeymapState: ignore WlSeat);
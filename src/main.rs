@@ -1,31 +1,50 @@
 // ABOUTME: Entry point for the voxkey Wayland dictation daemon.
 // ABOUTME: Wires portal sessions, audio recording, transcription, and text injection into an event loop.
 
+mod clipboard;
+mod compose;
 mod config;
+mod control;
 mod dbus;
 mod desktop;
+mod download_manager;
+mod execution_providers;
 mod injector;
+mod keymap;
+mod metrics;
+mod mqtt;
+mod parakeet_streaming;
 mod persistence;
 mod portal;
 mod recorder;
 mod registry;
+mod resample;
+mod secret_store;
 mod shortcuts;
 mod state;
 mod streaming;
+mod text_input;
 mod transcriber;
+mod vad;
+mod whisper_candle;
 
 use std::sync::Arc;
 
 use futures_util::StreamExt;
+use rand::Rng;
 use tokio::sync::{mpsc, oneshot};
 use tokio::task::JoinHandle;
 
-use config::Config;
+use clap::Parser;
+
+use compose::ComposeFallback;
+use config::{Cli, Command, Config, RecoveryConfig};
 use dbus::{DaemonInterface, SharedState};
 use desktop::DesktopController;
 use injector::Injector;
+use keymap::LiveKeymap;
 use recorder::Recorder;
-use shortcuts::ShortcutController;
+use shortcuts::{DictationEvent, ShortcutController};
 use state::{Event, State};
 use transcriber::Transcriber;
 
@@ -33,6 +52,14 @@ type DynError = Box<dyn std::error::Error + Send + Sync>;
 
 #[tokio::main]
 async fn main() {
+    if let Some(Command::Ctl { action }) = Cli::parse().command {
+        if let Err(e) = control::run_ctl_client(action).await {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     tracing_subscriber::fmt()
         .with_writer(std::io::stderr)
         .init();
@@ -64,6 +91,20 @@ async fn run() -> Result<(), DynError> {
 
     let shared = SharedState::new(config.clone());
 
+    Config::reload_on_signal(shared.clone())?;
+    tracing::info!("SIGUSR1 config hot-reload handler installed");
+
+    if config.metrics.enabled {
+        shared.metrics().serve(config.metrics.listen_addr.clone());
+        tracing::info!("Metrics endpoint listening on {}", config.metrics.listen_addr);
+    }
+    if let Some(textfile_path) = &config.metrics.textfile_path {
+        shared
+            .metrics()
+            .spawn_textfile_export(std::path::PathBuf::from(textfile_path), std::time::Duration::from_secs(15));
+        tracing::info!("Metrics textfile collector export enabled at {textfile_path}");
+    }
+
     // Register app_id with the portal and get the shared connection
     let connection = registry::connect_and_register().await?;
 
@@ -75,6 +116,16 @@ async fn run() -> Result<(), DynError> {
     connection.request_name(voxkey_ipc::BUS_NAME).await?;
     tracing::info!("D-Bus interface registered at {}", voxkey_ipc::BUS_NAME);
 
+    dbus::spawn_input_device_watcher(shared.clone(), connection.clone());
+    dbus::spawn_download_queue_watcher(shared.clone(), connection.clone());
+    dbus::spawn_event_bridge(shared.clone(), connection.clone());
+
+    if config.control_socket.enabled {
+        let socket_path = config.control_socket_path();
+        tracing::info!("Control socket gateway enabled at {}", socket_path.display());
+        control::spawn(shared.clone(), connection.clone(), socket_path);
+    }
+
     // Capability checks (using the same connection)
     portal::check_capabilities(connection.clone()).await.map_err(|e| -> DynError {
         tracing::error!("Portal capability check failed: {e}");
@@ -92,22 +143,47 @@ enum SessionOutcome {
 
 /// Run the daemon with automatic session recovery on portal errors.
 async fn run_with_recovery(connection: zbus::Connection, shared: SharedState) -> Result<(), DynError> {
+    let mut consecutive_failures: u32 = 0;
+
     loop {
-        let config = shared.config();
+        let config = (*shared.config()).clone();
         tokio::select! {
-            result = run_session(&config, connection.clone(), &shared) => {
+            result = run_session(config, connection.clone(), &shared) => {
                 match result {
                     Ok(SessionOutcome::Restart) => {
                         tracing::info!("Restarting session for shortcut change");
+                        consecutive_failures = 0;
                     }
                     Err(e) => {
                         tracing::error!("Session error: {e}");
-                        shared.set_portal_connected(false);
-                        DaemonInterface::notify_portal_connected(&connection).await;
-                        update_state(State::RecoveringSession, &shared, &connection).await;
-                        tracing::info!("Attempting session recovery in 2 seconds...");
-                        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-                        update_state(State::Idle, &shared, &connection).await;
+                        shared.metrics().record_session_recovery();
+                        shared.metrics().set_portal_connected(false);
+                        shared.set_portal_connected_and_publish(false);
+                        update_state(State::RecoveringSession, &shared).await;
+
+                        consecutive_failures += 1;
+                        let recovery = shared.config().recovery.clone();
+                        if recovery.max_attempts > 0 && consecutive_failures >= recovery.max_attempts {
+                            let message = format!(
+                                "Session recovery failed {consecutive_failures} times in a row"
+                            );
+                            tracing::error!("{message}");
+                            shared.set_last_error_and_publish(message);
+                            if recovery.exit_after_max_attempts {
+                                return Err("Exceeded max session recovery attempts".into());
+                            }
+                        }
+
+                        let delay = backoff_delay(&recovery, consecutive_failures);
+                        tracing::info!("Attempting session recovery in {delay:?} (attempt {consecutive_failures})");
+                        tokio::select! {
+                            _ = tokio::time::sleep(delay) => {}
+                            _ = shared.shutdown_requested() => {
+                                tracing::info!("Shutdown requested via D-Bus during recovery wait");
+                                return Ok(());
+                            }
+                        }
+                        update_state(State::Idle, &shared).await;
                     }
                 }
             }
@@ -119,9 +195,21 @@ async fn run_with_recovery(connection: zbus::Connection, shared: SharedState) ->
     }
 }
 
+/// Compute the delay before the next session-recovery attempt: exponential
+/// backoff from `recovery.base_secs`, capped at `recovery.cap_secs`, with up
+/// to `±20%` jitter so a flapping portal doesn't cause synchronized retries.
+fn backoff_delay(recovery: &RecoveryConfig, attempt: u32) -> std::time::Duration {
+    let exponent = attempt.saturating_sub(1).min(31);
+    let uncapped = recovery.base_secs * 2f64.powi(exponent as i32);
+    let capped = uncapped.min(recovery.cap_secs).max(0.0);
+    let jitter = rand::thread_rng().gen_range(-0.2..=0.2);
+    let jittered = (capped * (1.0 + jitter)).max(0.0);
+    std::time::Duration::from_secs_f64(jittered)
+}
+
 /// Run a single daemon session. Returns Ok(Restart) when a config change requires
 /// session recreation, Err on portal/session errors.
-async fn run_session(config: &Config, connection: zbus::Connection, shared: &SharedState) -> Result<SessionOutcome, DynError> {
+async fn run_session(mut config: Config, connection: zbus::Connection, shared: &SharedState) -> Result<SessionOutcome, DynError> {
     // Load restore token
     let token_path = config.token_path();
     let restore_token = persistence::load_restore_token(&token_path);
@@ -156,151 +244,121 @@ async fn run_session(config: &Config, connection: zbus::Connection, shared: &Sha
     }
 
     let desktop = Arc::new(desktop);
-    let recorder = Recorder::new(&config.audio);
-    let transcriber = Transcriber::from_config(&config.transcriber);
+
+    // The compositor's live keymap, used to keep keystroke injection correct
+    // on non-US layouts; shared between the injector and the streaming paths
+    // below so the same lookup backs every fallback keystroke. `None` if the
+    // compositor doesn't hand us a keyboard/keymap (e.g. no physical keyboard
+    // on the session), in which case injection falls back to unshifted taps.
+    let keymap = Arc::new(LiveKeymap::new().unwrap_or_else(|e| {
+        tracing::debug!("No live keymap available ({e}); keystroke injection won't be layout-aware");
+        None
+    }));
+
+    // Dead-key/compose fallback for characters `inject_text`'s direct-keysym
+    // path can't map (accented letters, etc). `None` if the locale has no
+    // compose table at all, in which case those characters are tapped by
+    // their own keysym without modifiers (or dropped if they have none).
+    let compose = Arc::new(
+        ComposeFallback::new()
+            .unwrap_or_else(|e| {
+                tracing::debug!("No compose table available ({e}); dead-key composition disabled");
+                None
+            })
+            .map(std::sync::Mutex::new),
+    );
+
+    let mut recorder = Recorder::new(&config.audio);
+    let mut transcriber = Transcriber::from_config(&config.transcriber);
+    shared.set_resolved_execution_provider(
+        transcriber.resolved_execution_provider().map(|p| p.config_name().to_string()).unwrap_or_default(),
+    );
+    DaemonInterface::notify_resolved_execution_provider(&connection).await;
+    shared.set_mqtt(mqtt::MqttBridge::connect(&config.mqtt, shared.clone()));
+
+    // Live config updates (e.g. from a SIGUSR1 reload). Settings that don't
+    // require re-negotiating the portal session are applied in place below;
+    // a shortcut change still needs a session restart to rebind.
+    let mut config_rx = shared.subscribe_config();
 
     // State management channel
     let (state_tx, mut state_rx) = mpsc::channel::<Event>(32);
 
     // Injector with its own background task
-    let injector = Injector::new(desktop.clone(), state_tx.clone());
+    let injector = Injector::new(desktop.clone(), state_tx.clone(), shared.clone(), keymap.clone(), compose.clone());
+
+    // Debounced, tap-toggle-aware Start/Stop stream derived from the
+    // shortcut's raw Activated/Deactivated portal signals.
+    let mut dictation = shortcuts.dictation_stream(&config.shortcut).await?;
 
-    // Signal streams
-    let mut activated = shortcuts.activated_stream().await?;
-    let mut deactivated = shortcuts.deactivated_stream().await?;
+    // Lets the control-socket gateway (if enabled) request Start/Stop on
+    // this session the same way the physical shortcut does.
+    let (control_tx, mut control_rx) = mpsc::unbounded_channel::<DictationEvent>();
+    shared.register_external_dictation_sender(control_tx);
 
-    shared.set_portal_connected(true);
-    DaemonInterface::notify_portal_connected(&connection).await;
+    shared.metrics().set_portal_connected(true);
+    shared.set_portal_connected_and_publish(true);
 
     let mut current_state = State::Idle;
-    update_state(current_state, shared, &connection).await;
+    update_state(current_state, shared).await;
 
     let mut recording_handle: Option<recorder::RecordingHandle> = None;
     let mut streaming_handle: Option<StreamingState> = None;
 
-    let shortcut_id = config.shortcut.id.clone();
-
-    // Toggle mode: press shortcut once to start, press again to stop.
-    // GNOME sends Activated every ~30ms as key repeat while held.
-    // A gap above REPEAT_THRESHOLD between consecutive Activated signals
-    // indicates a new intentional press rather than key repeat.
-    const REPEAT_THRESHOLD: std::time::Duration = std::time::Duration::from_millis(100);
-    let mut last_activated = std::time::Instant::now();
+    // Periodically checks the current state against State::poll_timeout so a
+    // wedged Transcribing/Injecting/RecoveringSession state self-heals even
+    // if the operation it's waiting on never reports back.
+    let mut state_watchdog_interval = tokio::time::interval(std::time::Duration::from_secs(1));
 
     loop {
         tokio::select! {
-            // Shortcut activated (pressed or repeat)
-            Some(signal) = activated.next() => {
-                tracing::debug!("Activated signal received: shortcut_id={:?}", signal.shortcut_id());
-                if signal.shortcut_id() != shortcut_id {
-                    continue;
-                }
-
-                if current_state == State::Recording || current_state == State::Streaming {
-                    let now = std::time::Instant::now();
-                    let gap = now.duration_since(last_activated);
-                    last_activated = now;
-                    if gap <= REPEAT_THRESHOLD {
-                        continue; // key repeat, ignore
-                    }
-                    // New press detected → stop
-                    if current_state == State::Recording {
-                        stop_recording(
-                            &mut current_state,
-                            &mut recording_handle,
-                            &transcriber,
-                            &injector,
-                            shared,
-                            &connection,
-                        ).await;
-                    } else {
-                        stop_streaming(&mut current_state, &mut streaming_handle, shared, &connection).await;
-                    }
-                    continue;
-                }
-
-                match current_state.transition(&Event::Activated) {
-                    Some(new_state) => {
-                        last_activated = std::time::Instant::now();
-
-                        if transcriber.is_streaming() {
-                            // Streaming flow: start audio + WebSocket session
-                            match recorder.start_streaming() {
-                                Ok(mut handle) => {
-                                    let audio_rx = handle.take_rx().expect("rx already taken");
-                                    let (stop_tx, stop_rx) = oneshot::channel();
-                                    let task = tokio::spawn({
-                                        let rt_config = config.transcriber.mistral_realtime.clone();
-                                        let sample_rate = config.audio.sample_rate;
-                                        let desktop = desktop.clone();
-                                        let state_tx = state_tx.clone();
-                                        let shared = shared.clone();
-                                        let connection = connection.clone();
-                                        async move {
-                                            if let Err(e) = streaming::run_streaming_session(
-                                                &rt_config,
-                                                sample_rate,
-                                                audio_rx,
-                                                desktop,
-                                                state_tx.clone(),
-                                                stop_rx,
-                                                shared.clone(),
-                                                connection.clone(),
-                                            ).await {
-                                                tracing::error!("Streaming session error: {e}");
-                                                shared.set_last_error(format!("Streaming error: {e}"));
-                                                DaemonInterface::notify_last_error(&connection).await;
-                                                let _ = state_tx.send(Event::InjectionDone).await;
-                                            }
-                                        }
-                                    });
-                                    streaming_handle = Some(StreamingState {
-                                        recording: handle,
-                                        stop_tx: Some(stop_tx),
-                                        task,
-                                    });
-                                    current_state = State::Streaming;
-                                    shared.set_last_error(String::new());
-                                    DaemonInterface::notify_last_error(&connection).await;
-                                    update_state(current_state, shared, &connection).await;
-                                }
-                                Err(e) => {
-                                    tracing::error!("Failed to start streaming recording: {e}");
-                                    shared.set_last_error(format!("Failed to start streaming: {e}"));
-                                    DaemonInterface::notify_last_error(&connection).await;
-                                    current_state = State::Idle;
-                                    update_state(current_state, shared, &connection).await;
-                                }
-                            }
-                        } else {
-                            // Batch flow: start recording to WAV
-                            current_state = new_state;
-                            update_state(current_state, shared, &connection).await;
-
-                            match recorder.start() {
-                                Ok(handle) => {
-                                    recording_handle = Some(handle);
-                                    shared.set_last_error(String::new());
-                                    DaemonInterface::notify_last_error(&connection).await;
-                                }
-                                Err(e) => {
-                                    tracing::error!("Failed to start recording: {e}");
-                                    shared.set_last_error(format!("Failed to start recording: {e}"));
-                                    DaemonInterface::notify_last_error(&connection).await;
-                                    current_state = State::Idle;
-                                    update_state(current_state, shared, &connection).await;
-                                }
-                            }
-                        }
-                    }
-                    None => {
-                        tracing::debug!("Ignoring Activated in state {current_state}");
-                    }
+            // Debounced shortcut press/release, already repeat- and
+            // bounce-filtered and tap-toggle-latched by `dictation_stream`.
+            // `None` means the portal dropped the GlobalShortcuts session
+            // (compositor restart, logout); bail out so `run_with_recovery`
+            // rebinds it rather than silently going deaf to the shortcut.
+            dictation_event = dictation.next() => match dictation_event {
+                None => return Err("GlobalShortcuts session ended".into()),
+                Some(event) => {
+                    handle_dictation_event(
+                        event,
+                        &mut current_state,
+                        &mut recording_handle,
+                        &mut streaming_handle,
+                        &recorder,
+                        &transcriber,
+                        &config,
+                        &desktop,
+                        &keymap,
+                        &compose,
+                        &state_tx,
+                        &injector,
+                        shared,
+                        &connection,
+                    ).await;
                 }
             }
 
-            // Shortcut deactivated (released) — ignored in toggle mode, must drain the stream
-            Some(_signal) = deactivated.next() => {}
+            // Same Start/Stop handling as above, but requested over the
+            // control socket instead of the physical shortcut.
+            Some(event) = control_rx.recv() => {
+                handle_dictation_event(
+                    event,
+                    &mut current_state,
+                    &mut recording_handle,
+                    &mut streaming_handle,
+                    &recorder,
+                    &transcriber,
+                    &config,
+                    &desktop,
+                    &keymap,
+                    &compose,
+                    &state_tx,
+                    &injector,
+                    shared,
+                    &connection,
+                ).await;
+            }
 
             // State machine events from injector or streaming session
             Some(event) = state_rx.recv() => {
@@ -309,7 +367,93 @@ async fn run_session(config: &Config, connection: zbus::Connection, shared: &Sha
                         streaming_handle = None;
                     }
                     current_state = new_state;
-                    update_state(current_state, shared, &connection).await;
+                    update_state(current_state, shared).await;
+                }
+            }
+
+            // Voice-activity detection declared end-of-speech during batch recording
+            _ = async {
+                match recording_handle.as_mut() {
+                    Some(handle) => handle.wait_for_endpoint().await,
+                    None => std::future::pending::<()>().await,
+                }
+            }, if recording_handle.is_some() => {
+                tracing::info!("VAD detected end of speech, auto-stopping recording");
+                stop_recording(
+                    &mut current_state,
+                    &mut recording_handle,
+                    &transcriber,
+                    &config.transcriber,
+                    &injector,
+                    shared,
+                    &config.history_path(),
+                ).await;
+            }
+
+            // Voice-activity detection declared end-of-speech during streaming
+            _ = async {
+                match streaming_handle.as_mut() {
+                    Some(handle) => handle.recording.wait_for_endpoint().await,
+                    None => std::future::pending::<()>().await,
+                }
+            }, if streaming_handle.is_some() => {
+                tracing::info!("VAD detected end of speech, auto-stopping streaming session");
+                stop_streaming(&mut current_state, &mut streaming_handle, shared).await;
+            }
+
+            // Recording-start watchdog: the batch capture device produced no
+            // audio within its deadline (e.g. a Wayland/PipeWire stream that
+            // opened but never delivered samples).
+            _ = async {
+                match recording_handle.as_mut() {
+                    Some(handle) => handle.wait_for_dead_air().await,
+                    None => std::future::pending::<()>().await,
+                }
+            }, if recording_handle.is_some() => {
+                tracing::error!("Recording watchdog: no audio received from capture device, aborting");
+                if let Some(handle) = recording_handle.take() {
+                    let entry = persistence::HistoryEntry::new(handle.started_at(), transcriber.engine_label(), None, false);
+                    let _ = persistence::append_history_entry(&config.history_path(), &entry);
+                }
+                shared.set_last_error_and_publish("audio device produced no data".to_string());
+                current_state = State::Idle;
+                update_state(current_state, shared).await;
+            }
+
+            // Recording-start watchdog: the streaming capture device produced
+            // no audio within its deadline.
+            _ = async {
+                match streaming_handle.as_mut() {
+                    Some(handle) => handle.recording.wait_for_dead_air().await,
+                    None => std::future::pending::<()>().await,
+                }
+            }, if streaming_handle.is_some() => {
+                tracing::error!("Recording watchdog: no audio received from capture device, aborting");
+                if let Some(mut handle) = streaming_handle.take() {
+                    let entry = persistence::HistoryEntry::new(
+                        handle.recording.started_at(),
+                        transcriber.engine_label(),
+                        None,
+                        false,
+                    );
+                    let _ = persistence::append_history_entry(&config.history_path(), &entry);
+                    handle.recording.stop();
+                    if let Some(stop_tx) = handle.stop_tx.take() {
+                        let _ = stop_tx.send(());
+                    }
+                }
+                shared.set_last_error_and_publish("audio device produced no data".to_string());
+                current_state = State::Idle;
+                update_state(current_state, shared).await;
+            }
+
+            // State-machine watchdog: force a stuck transient state onward
+            // so a hung transcriber or stalled portal can't wedge the daemon.
+            _ = state_watchdog_interval.tick() => {
+                if let Some(new_state) = current_state.poll_timeout(shared.time_in_state()) {
+                    tracing::error!("State watchdog: {current_state} exceeded its deadline, forcing {new_state}");
+                    current_state = new_state;
+                    update_state(current_state, shared).await;
                 }
             }
 
@@ -318,6 +462,24 @@ async fn run_session(config: &Config, connection: zbus::Connection, shared: &Sha
                 tracing::info!("Session restart requested");
                 return Ok(SessionOutcome::Restart);
             }
+
+            // Live config update (SIGUSR1 reload or D-Bus reload_config)
+            Ok(()) = config_rx.changed() => {
+                let new_config = config_rx.borrow_and_update().clone();
+                if new_config.shortcut.id != config.shortcut.id || new_config.shortcut.trigger != config.shortcut.trigger {
+                    tracing::info!("Shortcut config changed, requesting session restart to rebind");
+                    shared.request_session_restart();
+                    continue;
+                }
+                recorder = Recorder::new(&new_config.audio);
+                transcriber = Transcriber::from_config(&new_config.transcriber);
+                shared.set_resolved_execution_provider(
+                    transcriber.resolved_execution_provider().map(|p| p.config_name().to_string()).unwrap_or_default(),
+                );
+                DaemonInterface::notify_resolved_execution_provider(&connection).await;
+                config = new_config;
+                tracing::info!("Applied hot-reloaded configuration");
+            }
         }
     }
 }
@@ -335,66 +497,265 @@ async fn stop_recording(
     current_state: &mut State,
     recording_handle: &mut Option<recorder::RecordingHandle>,
     transcriber: &Transcriber,
+    transcriber_config: &voxkey_ipc::TranscriberConfig,
     injector: &Injector,
     shared: &SharedState,
-    connection: &zbus::Connection,
+    history_path: &std::path::Path,
 ) {
     *current_state = State::Transcribing;
-    update_state(*current_state, shared, connection).await;
+    update_state(*current_state, shared).await;
+    let stopped_at = std::time::Instant::now();
 
     if let Some(handle) = recording_handle.take() {
+        let started_at = handle.started_at();
         match handle.stop() {
             Ok(audio_path) => {
-                match transcriber.transcribe(&audio_path).await {
-                    Ok(transcript) => {
+                shared.metrics().record_audio_captured(stopped_at.duration_since(started_at));
+                match transcriber
+                    .transcribe_with_fallback(&transcriber_config.fallback, transcriber_config, &audio_path)
+                    .await
+                {
+                    Ok((transcript, engine_label)) => {
+                        shared.metrics().record_model_invocation(engine_label);
+                        if engine_label != transcriber.engine_label() {
+                            // Reuse the existing last_error/toast_overlay pipeline to let the
+                            // GUI surface the degradation, rather than adding a separate signal.
+                            shared.set_last_error_and_publish(format!("Primary transcriber failed, fell back to {engine_label}"));
+                        }
                         if transcript.is_empty() {
                             tracing::info!("Empty transcript, returning to idle");
+                            let entry = persistence::HistoryEntry::new(started_at, engine_label, None, false);
+                            let _ = persistence::append_history_entry(history_path, &entry);
                             *current_state = State::Idle;
-                            update_state(*current_state, shared, connection).await;
+                            update_state(*current_state, shared).await;
                         } else {
-                            shared.set_last_transcript(transcript.clone());
-                            DaemonInterface::notify_last_transcript(connection).await;
-                            if let Err(e) = injector.enqueue(transcript).await {
+                            shared.metrics().record_transcript(&transcript, stopped_at.elapsed());
+                            shared.set_last_transcript_and_publish(transcript.clone());
+                            if let Err(e) = injector.enqueue(transcript.clone()).await {
                                 tracing::error!("Failed to enqueue text: {e}");
-                                shared.set_last_error(format!("Failed to enqueue text: {e}"));
-                                DaemonInterface::notify_last_error(connection).await;
+                                shared.metrics().record_injection_failure();
+                                shared.set_last_error_and_publish(format!("Failed to enqueue text: {e}"));
+                                let entry = persistence::HistoryEntry::new(
+                                    started_at,
+                                    engine_label,
+                                    Some(transcript),
+                                    false,
+                                );
+                                let _ = persistence::append_history_entry(history_path, &entry);
                                 *current_state = State::Idle;
-                                update_state(*current_state, shared, connection).await;
+                                update_state(*current_state, shared).await;
+                            } else {
+                                shared.metrics().record_injection(&transcript);
+                                let entry = persistence::HistoryEntry::new(
+                                    started_at,
+                                    engine_label,
+                                    Some(transcript),
+                                    true,
+                                );
+                                let _ = persistence::append_history_entry(history_path, &entry);
                             }
                         }
                     }
                     Err(e) => {
                         tracing::error!("Transcription failed: {e}");
-                        shared.set_last_error(format!("Transcription failed: {e}"));
-                        DaemonInterface::notify_last_error(connection).await;
+                        shared.metrics().record_transcription_failure();
+                        shared.set_last_error_and_publish(format!("Transcription failed: {e}"));
+                        let entry = persistence::HistoryEntry::new(started_at, transcriber.engine_label(), None, false);
+                        let _ = persistence::append_history_entry(history_path, &entry);
                         let _ = std::fs::remove_file(&audio_path);
                         *current_state = State::Idle;
-                        update_state(*current_state, shared, connection).await;
+                        update_state(*current_state, shared).await;
                     }
                 }
             }
             Err(e) => {
                 tracing::error!("Failed to stop recording: {e}");
-                shared.set_last_error(format!("Failed to stop recording: {e}"));
-                DaemonInterface::notify_last_error(connection).await;
+                shared.set_last_error_and_publish(format!("Failed to stop recording: {e}"));
                 *current_state = State::Idle;
-                update_state(*current_state, shared, connection).await;
+                update_state(*current_state, shared).await;
             }
         }
     }
 }
 
+/// Apply a debounced `DictationEvent`, whichever of the physical shortcut or
+/// the control-socket gateway produced it: start or stop a batch/streaming
+/// recording, mirroring the state transitions the shortcut's hold-to-dictate
+/// press/release used to drive inline.
+#[allow(clippy::too_many_arguments)]
+async fn handle_dictation_event(
+    event: DictationEvent,
+    current_state: &mut State,
+    recording_handle: &mut Option<recorder::RecordingHandle>,
+    streaming_handle: &mut Option<StreamingState>,
+    recorder: &Recorder,
+    transcriber: &Transcriber,
+    config: &Config,
+    desktop: &Arc<DesktopController>,
+    keymap: &Arc<Option<LiveKeymap>>,
+    compose: &Arc<Option<std::sync::Mutex<ComposeFallback>>>,
+    state_tx: &mpsc::Sender<Event>,
+    injector: &Injector,
+    shared: &SharedState,
+    connection: &zbus::Connection,
+) {
+    match event {
+        DictationEvent::Stop => {
+            if *current_state == State::Recording {
+                stop_recording(
+                    current_state,
+                    recording_handle,
+                    transcriber,
+                    &config.transcriber,
+                    injector,
+                    shared,
+                    &config.history_path(),
+                ).await;
+            } else if *current_state == State::Streaming {
+                stop_streaming(current_state, streaming_handle, shared).await;
+            }
+        }
+        DictationEvent::Start => match current_state.transition(&Event::Activated) {
+            Some(new_state) => {
+                if transcriber.is_streaming() {
+                    // Streaming flow: start audio + WebSocket session
+                    match recorder.start_streaming(&config.vad, &config.watchdog) {
+                        Ok(mut handle) => {
+                            shared.metrics().record_recording_started();
+                            let started_at = handle.started_at();
+                            let audio_rx = handle.take_rx().expect("rx already taken");
+                            let (stop_tx, stop_rx) = oneshot::channel();
+                            let parakeet_streaming_params = match transcriber {
+                                Transcriber::ParakeetStreaming { model_name, execution_provider } => {
+                                    Some((model_name.clone(), *execution_provider))
+                                }
+                                _ => None,
+                            };
+                            let task = tokio::spawn({
+                                let mistral_realtime_config = config.transcriber.mistral_realtime.clone();
+                                let sample_rate = config.audio.sample_rate;
+                                let typing_delay = std::time::Duration::from_millis(
+                                    config.injection.typing_delay_ms as u64,
+                                );
+                                let capture_dir = config.capture.enabled.then(|| config.capture_directory());
+                                let history_path = config.history_path();
+                                let engine_label = transcriber.engine_label();
+                                let latency_warn_threshold = std::time::Duration::from_millis(
+                                    config.latency.warn_threshold_ms,
+                                );
+                                let desktop = desktop.clone();
+                                let keymap = keymap.clone();
+                                let compose = compose.clone();
+                                let state_tx = state_tx.clone();
+                                let shared = shared.clone();
+                                let connection = connection.clone();
+                                async move {
+                                    let session = match parakeet_streaming_params {
+                                        Some((model_name, execution_provider)) => {
+                                            parakeet_streaming::run_streaming_session(
+                                                &model_name,
+                                                execution_provider,
+                                                sample_rate,
+                                                audio_rx,
+                                                desktop,
+                                                keymap,
+                                                compose,
+                                                state_tx.clone(),
+                                                stop_rx,
+                                                shared.clone(),
+                                                connection.clone(),
+                                                typing_delay,
+                                                started_at,
+                                                history_path.clone(),
+                                                engine_label,
+                                                latency_warn_threshold,
+                                            ).await
+                                        }
+                                        None => {
+                                            streaming::run_streaming_session(
+                                                &mistral_realtime_config,
+                                                sample_rate,
+                                                audio_rx,
+                                                desktop,
+                                                keymap,
+                                                compose,
+                                                state_tx.clone(),
+                                                stop_rx,
+                                                shared.clone(),
+                                                connection.clone(),
+                                                typing_delay,
+                                                capture_dir,
+                                                started_at,
+                                                history_path.clone(),
+                                                engine_label,
+                                                latency_warn_threshold,
+                                            ).await
+                                        }
+                                    };
+                                    if let Err(e) = session {
+                                        tracing::error!("Streaming session error: {e}");
+                                        shared.metrics().record_streaming_error();
+                                        shared.set_last_error_and_publish(format!("Streaming error: {e}"));
+                                        let entry = persistence::HistoryEntry::new(started_at, engine_label, None, false);
+                                        let _ = persistence::append_history_entry(&history_path, &entry);
+                                        let _ = state_tx.send(Event::InjectionDone).await;
+                                    }
+                                }
+                            });
+                            *streaming_handle = Some(StreamingState {
+                                recording: handle,
+                                stop_tx: Some(stop_tx),
+                                task,
+                            });
+                            *current_state = State::Streaming;
+                            shared.set_last_error_and_publish(String::new());
+                            update_state(*current_state, shared).await;
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to start streaming recording: {e}");
+                            shared.set_last_error_and_publish(format!("Failed to start streaming: {e}"));
+                            *current_state = State::Idle;
+                            update_state(*current_state, shared).await;
+                        }
+                    }
+                } else {
+                    // Batch flow: start recording to WAV
+                    *current_state = new_state;
+                    update_state(*current_state, shared).await;
+
+                    match recorder.start(&config.vad, &config.watchdog) {
+                        Ok(handle) => {
+                            shared.metrics().record_recording_started();
+                            *recording_handle = Some(handle);
+                            shared.set_last_error_and_publish(String::new());
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to start recording: {e}");
+                            shared.set_last_error_and_publish(format!("Failed to start recording: {e}"));
+                            *current_state = State::Idle;
+                            update_state(*current_state, shared).await;
+                        }
+                    }
+                }
+            }
+            None => {
+                tracing::debug!("Ignoring Start in state {current_state}");
+            }
+        },
+    }
+}
+
 /// Stop streaming audio capture and signal the WebSocket session to drain.
 async fn stop_streaming(
     current_state: &mut State,
     streaming_handle: &mut Option<StreamingState>,
     shared: &SharedState,
-    connection: &zbus::Connection,
 ) {
     *current_state = State::Transcribing;
-    update_state(*current_state, shared, connection).await;
+    update_state(*current_state, shared).await;
 
     if let Some(mut handle) = streaming_handle.take() {
+        shared.metrics().record_audio_captured(handle.recording.started_at().elapsed());
         handle.recording.stop();
         if let Some(stop_tx) = handle.stop_tx.take() {
             let _ = stop_tx.send(());
@@ -403,9 +764,11 @@ async fn stop_streaming(
     }
 }
 
-/// Log state change, update shared D-Bus state, and emit PropertiesChanged.
-async fn update_state(state: State, shared: &SharedState, connection: &zbus::Connection) {
+/// Log state change, update shared D-Bus state, and publish `DaemonEvent::StateChanged`
+/// for `spawn_event_bridge` (and any other subscriber) to act on.
+async fn update_state(state: State, shared: &SharedState) {
     shared.set_state(state);
+    shared.metrics().set_state(state);
     eprintln!("STATE: {state}");
-    DaemonInterface::notify_state(connection).await;
+    shared.publish_event(dbus::DaemonEvent::StateChanged(state));
 }
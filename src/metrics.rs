@@ -0,0 +1,358 @@
+// ABOUTME: Process-wide counters for dictation throughput and failure rates.
+// ABOUTME: Served as Prometheus text exposition format over a small read-only HTTP endpoint, gated behind the "metrics" feature.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::state::State;
+
+/// Cheaply cloneable handle to the daemon's metrics. All recording methods
+/// are safe to call from any task, and all but `record_model_invocation`
+/// (which takes a short mutex lock) are lock-free.
+#[derive(Clone, Default)]
+pub struct Metrics {
+    inner: Arc<Counters>,
+}
+
+#[derive(Default)]
+struct Counters {
+    recordings_started: AtomicU64,
+    transcripts_produced: AtomicU64,
+    words_transcribed: AtomicU64,
+    words_injected: AtomicU64,
+    characters_injected: AtomicU64,
+    injection_failures: AtomicU64,
+    transcription_failures: AtomicU64,
+    streaming_errors: AtomicU64,
+    session_recoveries: AtomicU64,
+    transcription_latency_ms_sum: AtomicU64,
+    transcription_latency_count: AtomicU64,
+    streaming_latency_ms_sum: AtomicU64,
+    streaming_latency_count: AtomicU64,
+    audio_capture_ms_sum: AtomicU64,
+    current_state: AtomicU64,
+    portal_connected: AtomicU64,
+    /// Invocation counts keyed by `Transcriber::engine_label()`, since model
+    /// names are dynamic and don't fit the otherwise all-atomic layout above.
+    model_invocations: Mutex<HashMap<String, u64>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A batch or streaming recording was started.
+    pub fn record_recording_started(&self) {
+        self.inner.recordings_started.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A non-empty transcript was produced, `latency` after the recording was stopped.
+    pub fn record_transcript(&self, text: &str, latency: Duration) {
+        self.inner.transcripts_produced.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .words_transcribed
+            .fetch_add(text.split_whitespace().count() as u64, Ordering::Relaxed);
+        self.inner
+            .transcription_latency_ms_sum
+            .fetch_add(latency.as_millis() as u64, Ordering::Relaxed);
+        self.inner.transcription_latency_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A transcript's text was successfully enqueued for injection.
+    pub fn record_injection(&self, text: &str) {
+        self.inner
+            .words_injected
+            .fetch_add(text.split_whitespace().count() as u64, Ordering::Relaxed);
+        self.inner
+            .characters_injected
+            .fetch_add(text.chars().count() as u64, Ordering::Relaxed);
+    }
+
+    /// Transcription failed outright (distinct from a failed injection of an
+    /// already-produced transcript).
+    pub fn record_transcription_failure(&self) {
+        self.inner.transcription_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// `duration` of audio was captured by a recording that has now stopped.
+    pub fn record_audio_captured(&self, duration: Duration) {
+        self.inner
+            .audio_capture_ms_sum
+            .fetch_add(duration.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// A transcriber engine (see `Transcriber::engine_label`) was invoked.
+    pub fn record_model_invocation(&self, engine_label: &str) {
+        let mut counts = self.inner.model_invocations.lock().unwrap();
+        *counts.entry(engine_label.to_string()).or_insert(0) += 1;
+    }
+
+    /// A streaming transcript's text was injected `latency` after the audio
+    /// that produced it was captured (end-to-end capture-to-injection delay).
+    pub fn record_streaming_latency(&self, latency: Duration) {
+        self.inner
+            .streaming_latency_ms_sum
+            .fetch_add(latency.as_millis() as u64, Ordering::Relaxed);
+        self.inner.streaming_latency_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Text injection failed after a transcript was ready.
+    pub fn record_injection_failure(&self) {
+        self.inner.injection_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A streaming transcription session ended in error.
+    pub fn record_streaming_error(&self) {
+        self.inner.streaming_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The portal session was lost and a recovery attempt started.
+    pub fn record_session_recovery(&self) {
+        self.inner.session_recoveries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Reflect the daemon's current `State` as a gauge.
+    pub fn set_state(&self, state: State) {
+        self.inner.current_state.store(state_gauge(state), Ordering::Relaxed);
+    }
+
+    /// Reflect whether the portal session is currently connected, as a gauge.
+    pub fn set_portal_connected(&self, connected: bool) {
+        self.inner.portal_connected.store(connected as u64, Ordering::Relaxed);
+    }
+
+    /// Total non-empty transcripts produced (empty ones are not counted).
+    pub fn transcriptions_total(&self) -> u64 {
+        self.inner.transcripts_produced.load(Ordering::Relaxed)
+    }
+
+    /// Total seconds of audio captured across all recordings, as a fraction.
+    pub fn audio_seconds_total(&self) -> f64 {
+        self.inner.audio_capture_ms_sum.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+
+    /// Total words successfully injected.
+    pub fn words_injected_total(&self) -> u64 {
+        self.inner.words_injected.load(Ordering::Relaxed)
+    }
+
+    /// Total characters successfully injected.
+    pub fn characters_injected_total(&self) -> u64 {
+        self.inner.characters_injected.load(Ordering::Relaxed)
+    }
+
+    /// Total outright transcription failures.
+    pub fn transcription_failures_total(&self) -> u64 {
+        self.inner.transcription_failures.load(Ordering::Relaxed)
+    }
+
+    /// Invocation counts keyed by `Transcriber::engine_label()`.
+    pub fn model_invocations(&self) -> HashMap<String, u64> {
+        self.inner.model_invocations.lock().unwrap().clone()
+    }
+
+    /// Render all metrics in Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let c = &self.inner;
+        let mut out = format!(
+            "# TYPE voxkey_recordings_started_total counter\n\
+             voxkey_recordings_started_total {recordings_started}\n\
+             # TYPE voxkey_transcripts_produced_total counter\n\
+             voxkey_transcripts_produced_total {transcripts_produced}\n\
+             # TYPE voxkey_words_transcribed_total counter\n\
+             voxkey_words_transcribed_total {words_transcribed}\n\
+             # TYPE voxkey_words_injected_total counter\n\
+             voxkey_words_injected_total {words_injected}\n\
+             # TYPE voxkey_characters_injected_total counter\n\
+             voxkey_characters_injected_total {characters_injected}\n\
+             # TYPE voxkey_injection_failures_total counter\n\
+             voxkey_injection_failures_total {injection_failures}\n\
+             # TYPE voxkey_transcription_failures_total counter\n\
+             voxkey_transcription_failures_total {transcription_failures}\n\
+             # TYPE voxkey_streaming_errors_total counter\n\
+             voxkey_streaming_errors_total {streaming_errors}\n\
+             # TYPE voxkey_session_recoveries_total counter\n\
+             voxkey_session_recoveries_total {session_recoveries}\n\
+             # TYPE voxkey_transcription_latency_ms_sum counter\n\
+             voxkey_transcription_latency_ms_sum {latency_sum}\n\
+             # TYPE voxkey_transcription_latency_ms_count counter\n\
+             voxkey_transcription_latency_ms_count {latency_count}\n\
+             # TYPE voxkey_streaming_latency_ms_sum counter\n\
+             voxkey_streaming_latency_ms_sum {streaming_latency_sum}\n\
+             # TYPE voxkey_streaming_latency_ms_count counter\n\
+             voxkey_streaming_latency_ms_count {streaming_latency_count}\n\
+             # TYPE voxkey_audio_seconds_captured_total counter\n\
+             voxkey_audio_seconds_captured_total {audio_seconds}\n\
+             # TYPE voxkey_state gauge\n\
+             voxkey_state {state}\n\
+             # TYPE voxkey_portal_connected gauge\n\
+             voxkey_portal_connected {portal_connected}\n",
+            recordings_started = c.recordings_started.load(Ordering::Relaxed),
+            transcripts_produced = c.transcripts_produced.load(Ordering::Relaxed),
+            words_transcribed = c.words_transcribed.load(Ordering::Relaxed),
+            words_injected = c.words_injected.load(Ordering::Relaxed),
+            characters_injected = c.characters_injected.load(Ordering::Relaxed),
+            injection_failures = c.injection_failures.load(Ordering::Relaxed),
+            transcription_failures = c.transcription_failures.load(Ordering::Relaxed),
+            streaming_errors = c.streaming_errors.load(Ordering::Relaxed),
+            session_recoveries = c.session_recoveries.load(Ordering::Relaxed),
+            latency_sum = c.transcription_latency_ms_sum.load(Ordering::Relaxed),
+            latency_count = c.transcription_latency_count.load(Ordering::Relaxed),
+            streaming_latency_sum = c.streaming_latency_ms_sum.load(Ordering::Relaxed),
+            streaming_latency_count = c.streaming_latency_count.load(Ordering::Relaxed),
+            audio_seconds = self.audio_seconds_total(),
+            state = c.current_state.load(Ordering::Relaxed),
+            portal_connected = c.portal_connected.load(Ordering::Relaxed),
+        );
+
+        if let Ok(counts) = c.model_invocations.lock() {
+            if !counts.is_empty() {
+                out.push_str("# TYPE voxkey_model_invocations_total counter\n");
+                let mut models: Vec<&String> = counts.keys().collect();
+                models.sort();
+                for model in models {
+                    out.push_str(&format!(
+                        "voxkey_model_invocations_total{{model=\"{model}\"}} {}\n",
+                        counts[model]
+                    ));
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Render [`Metrics::render`] to `path` via a temp file + rename, so a
+    /// node_exporter-style textfile collector never observes a partial write.
+    pub fn write_textfile(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, self.render())?;
+        std::fs::rename(&tmp_path, path)
+    }
+
+    /// Periodically call [`Metrics::write_textfile`] in a background task,
+    /// logging (without stopping) on a write failure.
+    pub fn spawn_textfile_export(&self, path: std::path::PathBuf, interval: Duration) {
+        let metrics = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = metrics.write_textfile(&path) {
+                    tracing::warn!("Failed to write metrics textfile to {}: {e}", path.display());
+                }
+            }
+        });
+    }
+
+    /// Bind `addr` and serve [`Metrics::render`] as `GET /metrics`, forever,
+    /// in a background task. Only available with the `metrics` feature;
+    /// otherwise a no-op so call sites don't need to be `cfg`-gated.
+    #[cfg(feature = "metrics")]
+    pub fn serve(&self, addr: String) {
+        let metrics = self.clone();
+        tokio::spawn(async move {
+            let listener = match tokio::net::TcpListener::bind(&addr).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    tracing::error!("Failed to bind metrics endpoint on {addr}: {e}");
+                    return;
+                }
+            };
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    continue;
+                };
+                let body = metrics.render();
+                tokio::spawn(async move {
+                    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+                    let mut discard = [0u8; 1024];
+                    let _ = socket.read(&mut discard).await;
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body,
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    pub fn serve(&self, _addr: String) {}
+}
+
+fn state_gauge(state: State) -> u64 {
+    match state {
+        State::Idle => 0,
+        State::Recording => 1,
+        State::Streaming => 2,
+        State::Transcribing => 3,
+        State::Injecting => 4,
+        State::RecoveringSession => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_reflects_recorded_counters() {
+        let metrics = Metrics::new();
+        metrics.record_recording_started();
+        metrics.record_transcript("four words here total", Duration::from_millis(250));
+        metrics.record_injection("four words here");
+        metrics.record_injection_failure();
+        metrics.record_transcription_failure();
+        metrics.record_streaming_error();
+        metrics.record_session_recovery();
+        metrics.record_streaming_latency(Duration::from_millis(400));
+        metrics.record_audio_captured(Duration::from_millis(1500));
+        metrics.record_model_invocation("parakeet");
+        metrics.record_model_invocation("parakeet");
+        metrics.set_state(State::Recording);
+        metrics.set_portal_connected(true);
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("voxkey_recordings_started_total 1"));
+        assert!(rendered.contains("voxkey_transcripts_produced_total 1"));
+        assert!(rendered.contains("voxkey_words_transcribed_total 4"));
+        assert!(rendered.contains("voxkey_words_injected_total 3"));
+        assert!(rendered.contains("voxkey_characters_injected_total 15"));
+        assert!(rendered.contains("voxkey_injection_failures_total 1"));
+        assert!(rendered.contains("voxkey_transcription_failures_total 1"));
+        assert!(rendered.contains("voxkey_streaming_errors_total 1"));
+        assert!(rendered.contains("voxkey_session_recoveries_total 1"));
+        assert!(rendered.contains("voxkey_transcription_latency_ms_sum 250"));
+        assert!(rendered.contains("voxkey_transcription_latency_ms_count 1"));
+        assert!(rendered.contains("voxkey_streaming_latency_ms_sum 400"));
+        assert!(rendered.contains("voxkey_streaming_latency_ms_count 1"));
+        assert!(rendered.contains("voxkey_audio_seconds_captured_total 1.5"));
+        assert!(rendered.contains("voxkey_state 1"));
+        assert!(rendered.contains("voxkey_portal_connected 1"));
+        assert!(rendered.contains("voxkey_model_invocations_total{model=\"parakeet\"} 2"));
+    }
+
+    #[test]
+    fn write_textfile_survives_missing_parent_being_present() {
+        let dir = std::env::temp_dir().join(format!(
+            "voxkey-metrics-test-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("voxkey.prom");
+
+        let metrics = Metrics::new();
+        metrics.record_recording_started();
+        metrics.write_textfile(&path).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("voxkey_recordings_started_total 1"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,5 +1,8 @@
 // ABOUTME: Downloads Parakeet ONNX model files from HuggingFace to the local data directory.
-// ABOUTME: Supports progress callbacks and cancellation for GUI integration.
+// ABOUTME: Supports progress callbacks, resume, checksum verification, and cancellation for GUI integration.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
 
 use tokio::sync::watch;
 
@@ -15,14 +18,45 @@ const MODEL_FILES: &[&str] = &[
     "tokens.txt",
 ];
 
+/// Known-good per-file SHA-256 digests for the built-in models, keyed by
+/// model name and then file name. Populated as upstream releases are pinned
+/// and verified; an empty slice (or a missing file entry) means "no built-in
+/// digest yet" and per-file verification is skipped for that file, falling
+/// back to the catalog-supplied whole-directory `sha256_override` check below.
+const MODEL_MANIFEST: &[(&str, &[(&str, &str)])] = &[];
+
+fn expected_file_sha256(model_name: &str, file_name: &str) -> Option<&'static str> {
+    MODEL_MANIFEST
+        .iter()
+        .find(|(name, _)| *name == model_name)
+        .and_then(|(_, files)| files.iter().find(|(f, _)| *f == file_name))
+        .map(|(_, hash)| *hash)
+}
+
 #[derive(Debug, Clone)]
 pub enum DownloadStatus {
-    /// Download in progress. Percent is 0-100 across all files.
-    InProgress(u8),
-    /// Download completed successfully.
+    /// Download in progress. `percent` is 0-100 across all files;
+    /// `bytes_per_sec` is the average throughput since the download started;
+    /// `current_file` is how many of `total_files` have finished downloading so
+    /// far (files are fetched concurrently, so there's no single "current" one).
+    InProgress {
+        percent: u8,
+        downloaded_bytes: u64,
+        total_bytes: u64,
+        bytes_per_sec: f64,
+        current_file: usize,
+        total_files: usize,
+    },
+    /// All files downloaded; verifying against the catalog's expected checksum.
+    Verifying,
+    /// Download completed and, if a checksum was provided, verified.
     Complete,
+    /// Downloaded files don't match the expected checksum.
+    ChecksumFailed { expected: String, actual: String },
     /// Download failed.
     Failed(String),
+    /// Cancelled by the user before completion.
+    Cancelled,
 }
 
 fn base_url(model_name: &str) -> Result<&'static str, String> {
@@ -33,81 +67,346 @@ fn base_url(model_name: &str) -> Result<&'static str, String> {
     }
 }
 
-/// Start downloading a model. Returns a watch receiver for progress updates.
-/// The download runs on a tokio task.
+/// Start downloading a model. Returns a watch receiver for progress updates
+/// and a cancellation flag the caller can set to abort the download at the
+/// next checkpoint. The download runs on a tokio task. `url_override`
+/// replaces the built-in HuggingFace base URL (from the settings GUI's
+/// provider/model catalog); `sha256_override`, if given, is verified against
+/// the downloaded files once complete. The worker sends its own terminal
+/// status (`Complete`, `ChecksumFailed`, `Failed`, or `Cancelled`), so callers
+/// only need to watch the receiver.
 pub fn start_download(
     model_name: String,
-) -> watch::Receiver<DownloadStatus> {
-    let (tx, rx) = watch::channel(DownloadStatus::InProgress(0));
+    url_override: Option<String>,
+    sha256_override: Option<String>,
+) -> (watch::Receiver<DownloadStatus>, Arc<AtomicBool>) {
+    let (tx, rx) = watch::channel(DownloadStatus::InProgress {
+        percent: 0,
+        downloaded_bytes: 0,
+        total_bytes: 0,
+        bytes_per_sec: 0.0,
+        current_file: 0,
+        total_files: MODEL_FILES.len(),
+    });
+    let cancel = Arc::new(AtomicBool::new(false));
+    let cancel_task = cancel.clone();
     tokio::spawn(async move {
-        match download_model(&model_name, &tx).await {
-            Ok(()) => { let _ = tx.send(DownloadStatus::Complete); }
-            Err(e) => { let _ = tx.send(DownloadStatus::Failed(e.to_string())); }
+        if let Err(e) = download_model(&model_name, url_override, sha256_override, &tx, &cancel_task).await {
+            let _ = tx.send(DownloadStatus::Failed(e.to_string()));
         }
     });
-    rx
+    (rx, cancel)
+}
+
+/// Outcome of fetching a single file, distinct from `Err` so a caller can
+/// tell "stopped because of cancellation/a sibling's failure" apart from
+/// "something actually went wrong downloading this file".
+enum FileOutcome {
+    Done,
+    Stopped,
 }
 
 async fn download_model(
     model_name: &str,
+    url_override: Option<String>,
+    sha256_override: Option<String>,
     progress: &watch::Sender<DownloadStatus>,
+    cancel: &Arc<AtomicBool>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let base = base_url(model_name)?;
+    let base = match url_override {
+        Some(ref url) => url.as_str(),
+        None => base_url(model_name)?,
+    };
     let dest_dir = crate::models::model_dir(model_name);
     std::fs::create_dir_all(&dest_dir)?;
 
     let client = reqwest::Client::new();
     let total_files = MODEL_FILES.len();
 
-    for (i, file_name) in MODEL_FILES.iter().enumerate() {
-        let url = format!("{base}/{file_name}");
-        let dest_path = dest_dir.join(file_name);
+    let downloaded_bytes: Vec<AtomicU64> = (0..total_files).map(|_| AtomicU64::new(0)).collect();
+    let total_bytes: Vec<AtomicU64> = (0..total_files).map(|_| AtomicU64::new(0)).collect();
+    let file_done: Vec<AtomicBool> = (0..total_files).map(|_| AtomicBool::new(false)).collect();
+    let completed_files = AtomicU64::new(0);
+    // Set when one file's download fails, so the other in-flight fetches
+    // stop at their next checkpoint instead of wasting bandwidth.
+    let abort_siblings = AtomicBool::new(false);
 
-        // Skip already-downloaded files
-        if dest_path.exists() {
-            let pct = ((i + 1) * 100 / total_files) as u8;
-            let _ = progress.send(DownloadStatus::InProgress(pct));
-            continue;
+    let already_downloaded: u64 = MODEL_FILES
+        .iter()
+        .map(|f| dest_dir.join(f).metadata().map(|m| m.len()).unwrap_or(0))
+        .sum();
+    let start_time = std::time::Instant::now();
+
+    use futures_util::StreamExt;
+
+    let results = futures_util::stream::iter(MODEL_FILES.iter().enumerate())
+        .map(|(idx, file_name)| {
+            download_one_file(
+                idx,
+                file_name,
+                base,
+                &dest_dir,
+                model_name,
+                &client,
+                &downloaded_bytes,
+                &total_bytes,
+                &file_done,
+                &completed_files,
+                cancel,
+                &abort_siblings,
+                start_time,
+                already_downloaded,
+                progress,
+                total_files,
+            )
+        })
+        .buffer_unordered(total_files)
+        .collect::<Vec<_>>()
+        .await;
+
+    if cancel.load(Ordering::Relaxed) {
+        let _ = progress.send(DownloadStatus::Cancelled);
+        return Ok(());
+    }
+    // Any per-file error, a per-file checksum mismatch included (which has
+    // already sent ChecksumFailed), propagates so `start_download` sends Failed.
+    for result in results {
+        result?;
+    }
+    if abort_siblings.load(Ordering::Relaxed) {
+        // Every path that sets `abort_siblings` also returns an error, which
+        // the loop above has already propagated.
+        return Ok(());
+    }
+
+    if let Some(expected) = sha256_override {
+        let _ = progress.send(DownloadStatus::Verifying);
+        let actual = hash_model_files(&dest_dir)?;
+        if !actual.eq_ignore_ascii_case(&expected) {
+            let _ = progress.send(DownloadStatus::ChecksumFailed { expected, actual });
+            return Ok(());
         }
+    }
 
+    let _ = progress.send(DownloadStatus::Complete);
+    Ok(())
+}
+
+/// Fetch a single model file, resuming from any existing `.part` file and
+/// verifying it against `MODEL_MANIFEST` once written. Updates `downloaded_bytes`
+/// and `total_bytes` at `idx` as the transfer progresses and emits an
+/// aggregate `InProgress` status across all files after every update.
+#[allow(clippy::too_many_arguments)]
+async fn download_one_file(
+    idx: usize,
+    file_name: &str,
+    base: &str,
+    dest_dir: &std::path::Path,
+    model_name: &str,
+    client: &reqwest::Client,
+    downloaded_bytes: &[AtomicU64],
+    total_bytes: &[AtomicU64],
+    file_done: &[AtomicBool],
+    completed_files: &AtomicU64,
+    cancel: &Arc<AtomicBool>,
+    abort_siblings: &AtomicBool,
+    start_time: std::time::Instant,
+    session_start_bytes: u64,
+    progress: &watch::Sender<DownloadStatus>,
+    total_files: usize,
+) -> Result<FileOutcome, Box<dyn std::error::Error + Send + Sync>> {
+    let should_stop = || cancel.load(Ordering::Relaxed) || abort_siblings.load(Ordering::Relaxed);
+    if should_stop() {
+        return Ok(FileOutcome::Stopped);
+    }
+
+    let url = format!("{base}/{file_name}");
+    let dest_path = dest_dir.join(file_name);
+
+    if let Ok(response) = client.head(&url).send().await {
+        total_bytes[idx].store(response.content_length().unwrap_or(0), Ordering::Relaxed);
+    }
+
+    // Already fully downloaded and finalized in a previous run. Re-verify
+    // against the manifest rather than trusting existence alone — a
+    // truncated or corrupted file from an earlier interrupted run should
+    // not silently pass as downloaded.
+    if dest_path.exists() {
+        if let Some(expected) = expected_file_sha256(model_name, file_name) {
+            let _ = progress.send(DownloadStatus::Verifying);
+            let actual = hash_file(&dest_path)?;
+            if !actual.eq_ignore_ascii_case(expected) {
+                let _ = tokio::fs::remove_file(&dest_path).await;
+                let _ = progress.send(DownloadStatus::ChecksumFailed {
+                    expected: expected.to_string(),
+                    actual,
+                });
+                abort_siblings.store(true, Ordering::Relaxed);
+                return Err(format!("{file_name} failed checksum verification").into());
+            }
+        }
+
+        let len = dest_path.metadata().map(|m| m.len()).unwrap_or(0);
+        downloaded_bytes[idx].store(len, Ordering::Relaxed);
+        file_done[idx].store(true, Ordering::Relaxed);
+        completed_files.fetch_add(1, Ordering::Relaxed);
+        emit_progress(downloaded_bytes, total_bytes, file_done, start_time, session_start_bytes, progress, total_files, completed_files);
+        return Ok(FileOutcome::Done);
+    }
+
+    let part_path = dest_path.with_extension("part");
+    let resume_from = part_path.metadata().map(|m| m.len()).unwrap_or(0);
+    downloaded_bytes[idx].store(resume_from, Ordering::Relaxed);
+
+    let mut request = client.get(&url);
+    if resume_from > 0 {
+        tracing::info!("Resuming {file_name} from byte {resume_from}");
+        request = request.header(reqwest::header::RANGE, format!("bytes={resume_from}-"));
+    } else {
         tracing::info!("Downloading {file_name} from {url}");
+    }
 
-        let response = client.get(&url).send().await?;
-        if !response.status().is_success() {
-            return Err(format!("HTTP {} downloading {url}", response.status()).into());
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        abort_siblings.store(true, Ordering::Relaxed);
+        return Err(format!("HTTP {} downloading {url}", response.status()).into());
+    }
+    let resumed = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    if !resumed {
+        downloaded_bytes[idx].store(0, Ordering::Relaxed);
+    }
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&part_path)
+        .await?;
+
+    use futures_util::StreamExt;
+    use tokio::io::AsyncWriteExt;
+
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        if should_stop() {
+            drop(file);
+            return Ok(FileOutcome::Stopped);
         }
 
-        let total_size = response.content_length();
-        let mut stream = response.bytes_stream();
-        let tmp_path = dest_path.with_extension("part");
-        let mut file = tokio::fs::File::create(&tmp_path).await?;
-        let mut downloaded: u64 = 0;
-
-        use futures_util::StreamExt;
-        use tokio::io::AsyncWriteExt;
-
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk?;
-            file.write_all(&chunk).await?;
-            downloaded += chunk.len() as u64;
-
-            // Report progress: spread across all files
-            if let Some(total) = total_size {
-                let file_pct = downloaded as f64 / total as f64;
-                let overall = (i as f64 + file_pct) / total_files as f64;
-                let _ = progress.send(DownloadStatus::InProgress((overall * 100.0) as u8));
-            }
+        let chunk = chunk?;
+        file.write_all(&chunk).await?;
+        downloaded_bytes[idx].fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        emit_progress(downloaded_bytes, total_bytes, file_done, start_time, session_start_bytes, progress, total_files, completed_files);
+    }
+
+    file.flush().await?;
+    drop(file);
+    tokio::fs::rename(&part_path, &dest_path).await?;
+
+    if let Some(expected) = expected_file_sha256(model_name, file_name) {
+        let _ = progress.send(DownloadStatus::Verifying);
+        let actual = hash_file(&dest_path)?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            let _ = tokio::fs::remove_file(&dest_path).await;
+            let _ = progress.send(DownloadStatus::ChecksumFailed {
+                expected: expected.to_string(),
+                actual,
+            });
+            abort_siblings.store(true, Ordering::Relaxed);
+            return Err(format!("{file_name} failed checksum verification").into());
         }
+    }
+
+    file_done[idx].store(true, Ordering::Relaxed);
+    completed_files.fetch_add(1, Ordering::Relaxed);
+    emit_progress(downloaded_bytes, total_bytes, file_done, start_time, session_start_bytes, progress, total_files, completed_files);
+    Ok(FileOutcome::Done)
+}
 
-        file.flush().await?;
-        drop(file);
-        tokio::fs::rename(&tmp_path, &dest_path).await?;
+/// Aggregate progress across all files: `sum(downloaded) / sum(total)`. A
+/// file whose total length is unknown (no `Content-Length`) only contributes
+/// once it finishes, so a long unknown-size download doesn't make the
+/// reported percentage swing around as its bytes accumulate with no matching
+/// denominator.
+#[allow(clippy::too_many_arguments)]
+fn emit_progress(
+    downloaded_bytes: &[AtomicU64],
+    total_bytes: &[AtomicU64],
+    file_done: &[AtomicBool],
+    start_time: std::time::Instant,
+    session_start_bytes: u64,
+    progress: &watch::Sender<DownloadStatus>,
+    total_files: usize,
+    completed_files: &AtomicU64,
+) {
+    let mut downloaded = 0u64;
+    let mut total = 0u64;
+    for i in 0..downloaded_bytes.len() {
+        let file_total = total_bytes[i].load(Ordering::Relaxed);
+        let done = file_done[i].load(Ordering::Relaxed);
+        if file_total == 0 && !done {
+            continue;
+        }
+        let file_downloaded = downloaded_bytes[i].load(Ordering::Relaxed);
+        downloaded += file_downloaded;
+        total += if file_total > 0 { file_total } else { file_downloaded };
     }
 
-    Ok(())
+    let elapsed = start_time.elapsed().as_secs_f64();
+    let bytes_per_sec = if elapsed > 0.0 {
+        downloaded.saturating_sub(session_start_bytes) as f64 / elapsed
+    } else {
+        0.0
+    };
+    let percent = if total > 0 {
+        ((downloaded as f64 / total as f64) * 100.0).min(100.0) as u8
+    } else {
+        0
+    };
+    let _ = progress.send(DownloadStatus::InProgress {
+        percent,
+        downloaded_bytes: downloaded,
+        total_bytes: total,
+        bytes_per_sec,
+        current_file: completed_files.load(Ordering::Relaxed) as usize,
+        total_files,
+    });
+}
+
+/// SHA-256 of the downloaded model files concatenated in `MODEL_FILES` order,
+/// matching the single checksum a catalog entry carries for a model package.
+fn hash_model_files(dir: &std::path::Path) -> Result<String, std::io::Error> {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for file_name in MODEL_FILES {
+        hasher.update(std::fs::read(dir.join(file_name))?);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// SHA-256 of a single downloaded file, for per-file `MODEL_MANIFEST` checks.
+fn hash_file(path: &std::path::Path) -> Result<String, std::io::Error> {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(std::fs::read(path)?);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// SHA-256 of a fully-downloaded model's files, for comparison against a
+/// catalog's expected checksum to detect a published update. Returns `None`
+/// if the model isn't fully downloaded yet.
+pub fn installed_sha256(model_name: &str) -> Option<String> {
+    if !crate::models::is_model_available(model_name) {
+        return None;
+    }
+    hash_model_files(&crate::models::model_dir(model_name)).ok()
 }
 
-/// Delete a downloaded model's directory.
+/// Delete a downloaded model's directory, including any stale `.part` files
+/// left behind by an interrupted download.
 pub fn delete_model(model_name: &str) -> Result<(), std::io::Error> {
     let dir = crate::models::model_dir(model_name);
     if dir.exists() {
@@ -139,4 +438,70 @@ mod tests {
     fn delete_model_ignores_nonexistent_dir() {
         assert!(delete_model("nonexistent-model-xyz").is_ok());
     }
+
+    #[test]
+    fn expected_file_sha256_is_none_without_a_manifest_entry() {
+        assert_eq!(expected_file_sha256("parakeet-tdt-0.6b-v2", "encoder.int8.onnx"), None);
+    }
+
+    #[test]
+    fn hash_file_matches_expected_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("encoder.int8.onnx");
+        std::fs::write(&path, b"fake").unwrap();
+        let hash = hash_file(&path).unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, "b5d54c39e66671c9731b9f471e585d8262cd4f54963f0c93082d8dcf334d4c78");
+        assert_eq!(hash, hash_file(&path).unwrap());
+    }
+
+    #[test]
+    fn hash_model_files_matches_expected_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        for file_name in MODEL_FILES {
+            std::fs::write(dir.path().join(file_name), b"fake").unwrap();
+        }
+        let hash = hash_model_files(dir.path()).unwrap();
+        assert_eq!(hash.len(), 64);
+        // SHA-256 of "fake" repeated once per file in MODEL_FILES order
+        assert_eq!(hash, "8defa1fb2dfdb66a54881e43280b0686525aa2685a2ee48416c72b1255e6b064");
+        // Stable across repeated hashing of identical contents
+        assert_eq!(hash, hash_model_files(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn emit_progress_excludes_unfinished_unknown_size_files() {
+        let (tx, rx) = watch::channel(DownloadStatus::Verifying);
+        let downloaded_bytes = [AtomicU64::new(50), AtomicU64::new(1_000)];
+        // File 0 has no known total yet and hasn't finished, so it should be
+        // excluded entirely; file 1 has a known total of 1000 and is done.
+        let total_bytes = [AtomicU64::new(0), AtomicU64::new(1_000)];
+        let file_done = [AtomicBool::new(false), AtomicBool::new(true)];
+        let completed_files = AtomicU64::new(1);
+
+        emit_progress(
+            &downloaded_bytes,
+            &total_bytes,
+            &file_done,
+            std::time::Instant::now(),
+            0,
+            &tx,
+            2,
+            &completed_files,
+        );
+
+        match rx.borrow().clone() {
+            DownloadStatus::InProgress {
+                downloaded_bytes,
+                total_bytes,
+                percent,
+                ..
+            } => {
+                assert_eq!(downloaded_bytes, 1_000);
+                assert_eq!(total_bytes, 1_000);
+                assert_eq!(percent, 100);
+            }
+            other => panic!("expected InProgress, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,201 @@
+// ABOUTME: Optional MQTT bridge mirroring daemon state and transcription events for headless integration.
+// ABOUTME: Publishes a retained state topic plus transcript/error/download topics and accepts reload/clear/quit commands.
+
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::dbus::SharedState;
+use crate::state::State;
+use voxkey_ipc::MqttConfig;
+
+type DynError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Handle to a running MQTT bridge. Publishing methods are fire-and-forget;
+/// failures are logged but never bubble up to the daemon event loop.
+pub struct MqttBridge {
+    client: rumqttc::AsyncClient,
+    topic_prefix: String,
+}
+
+impl MqttBridge {
+    /// Connect to the broker described by `config` and start the background
+    /// event loop and command subscriber. Returns `None` when MQTT is disabled
+    /// or the broker URL can't be parsed.
+    pub fn connect(config: &MqttConfig, shared: SharedState) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+
+        let (host, port, url_prefix) = match parse_broker_url(&config.url) {
+            Ok(parts) => parts,
+            Err(e) => {
+                tracing::error!("Invalid MQTT broker URL {:?}: {e}", config.url);
+                return None;
+            }
+        };
+        let topic_prefix = if config.topic_prefix.is_empty() {
+            url_prefix.unwrap_or_else(|| "voxkey".to_string())
+        } else {
+            config.topic_prefix.clone()
+        };
+
+        let mut options = rumqttc::MqttOptions::new("voxkey-daemon", host.clone(), port);
+        options.set_keep_alive(Duration::from_secs(30));
+
+        let (client, mut eventloop) = rumqttc::AsyncClient::new(options, 16);
+
+        let command_topic = format!("{topic_prefix}/command");
+        let subscribe_client = client.clone();
+        tokio::spawn(async move {
+            if let Err(e) = subscribe_client
+                .subscribe(&command_topic, rumqttc::QoS::AtLeastOnce)
+                .await
+            {
+                tracing::warn!("Failed to subscribe to MQTT command topic: {e}");
+            }
+        });
+
+        tokio::spawn(async move {
+            loop {
+                match eventloop.poll().await {
+                    Ok(rumqttc::Event::Incoming(rumqttc::Packet::Publish(publish))) => {
+                        handle_command(&publish.payload, &shared).await;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::warn!("MQTT connection error: {e}");
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                }
+            }
+        });
+
+        tracing::info!("MQTT bridge connected to {host}:{port} (prefix {topic_prefix})");
+
+        Some(Self { client, topic_prefix })
+    }
+
+    /// Publish the current daemon state, retained so new subscribers see it immediately.
+    pub async fn publish_state(&self, state: State) {
+        self.publish_retained("state", state.to_string().as_bytes())
+            .await;
+    }
+
+    /// Publish a completed transcript.
+    pub async fn publish_transcript(&self, text: &str) {
+        self.publish("transcript", text.as_bytes()).await;
+    }
+
+    /// Publish an error message.
+    pub async fn publish_error(&self, message: &str) {
+        self.publish("error", message.as_bytes()).await;
+    }
+
+    /// Publish model download progress as JSON `{"model": ..., "percent": ...}`.
+    pub async fn publish_download_progress(&self, model_name: &str, percent: u8) {
+        let payload = serde_json::json!({ "model": model_name, "percent": percent }).to_string();
+        self.publish("download_progress", payload.as_bytes()).await;
+    }
+
+    async fn publish(&self, topic_suffix: &str, payload: &[u8]) {
+        let topic = format!("{}/{topic_suffix}", self.topic_prefix);
+        if let Err(e) = self
+            .client
+            .publish(&topic, rumqttc::QoS::AtLeastOnce, false, payload)
+            .await
+        {
+            tracing::warn!("Failed to publish MQTT message to {topic}: {e}");
+        }
+    }
+
+    async fn publish_retained(&self, topic_suffix: &str, payload: &[u8]) {
+        let topic = format!("{}/{topic_suffix}", self.topic_prefix);
+        if let Err(e) = self
+            .client
+            .publish(&topic, rumqttc::QoS::AtLeastOnce, true, payload)
+            .await
+        {
+            tracing::warn!("Failed to publish retained MQTT message to {topic}: {e}");
+        }
+    }
+}
+
+/// Handle a message on `<prefix>/command`, reusing the same config/shutdown
+/// handling as the equivalent D-Bus methods. Unknown commands are logged and ignored.
+async fn handle_command(payload: &[u8], shared: &SharedState) {
+    let Ok(command) = std::str::from_utf8(payload) else {
+        tracing::warn!("Ignoring non-UTF-8 MQTT command payload");
+        return;
+    };
+
+    match command.trim() {
+        "reload_config" => match Config::load() {
+            Ok(config) => {
+                shared.update_config(config);
+                tracing::info!("Configuration reloaded via MQTT command");
+            }
+            Err(e) => tracing::error!("Failed to reload config via MQTT: {e}"),
+        },
+        "clear_restore_token" => {
+            let token_path = shared.config().token_path();
+            if token_path.exists() {
+                match std::fs::remove_file(&token_path) {
+                    Ok(()) => tracing::info!("Restore token cleared via MQTT command"),
+                    Err(e) => tracing::error!("Failed to clear restore token via MQTT: {e}"),
+                }
+            }
+        }
+        "quit" => {
+            tracing::info!("Quit requested via MQTT command");
+            shared.request_shutdown();
+        }
+        other => {
+            tracing::warn!("Unknown MQTT command: {other}");
+        }
+    }
+}
+
+/// Parse a `mqtt://host[:port][/prefix]` URL into (host, port, optional prefix),
+/// following the common broker-URL convention where the path is a topic prefix.
+fn parse_broker_url(url: &str) -> Result<(String, u16, Option<String>), DynError> {
+    let rest = url.strip_prefix("mqtt://").ok_or("expected mqtt:// scheme")?;
+    let (authority, path) = match rest.split_once('/') {
+        Some((authority, path)) => (authority, Some(path)),
+        None => (rest, None),
+    };
+    let (host, port) = match authority.split_once(':') {
+        Some((host, port)) => (
+            host.to_string(),
+            port.parse::<u16>().map_err(|e| format!("invalid port: {e}"))?,
+        ),
+        None => (authority.to_string(), 1883),
+    };
+    let prefix = path.filter(|p| !p.is_empty()).map(|p| p.to_string());
+    Ok((host, port, prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_broker_url_with_port_and_prefix() {
+        let (host, port, prefix) = parse_broker_url("mqtt://broker.local:1884/home/voxkey").unwrap();
+        assert_eq!(host, "broker.local");
+        assert_eq!(port, 1884);
+        assert_eq!(prefix.as_deref(), Some("home/voxkey"));
+    }
+
+    #[test]
+    fn parse_broker_url_defaults_port_and_prefix() {
+        let (host, port, prefix) = parse_broker_url("mqtt://localhost").unwrap();
+        assert_eq!(host, "localhost");
+        assert_eq!(port, 1883);
+        assert_eq!(prefix, None);
+    }
+
+    #[test]
+    fn parse_broker_url_rejects_wrong_scheme() {
+        assert!(parse_broker_url("http://localhost").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+// ABOUTME: Runs real-time streaming transcription locally via sherpa-rs's online transducer.
+// ABOUTME: Feeds incoming PCM chunks to the recognizer and injects text as hypotheses stabilize, with no network dependency.
+
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::compose::ComposeFallback;
+use crate::dbus::SharedState;
+use crate::desktop::DesktopController;
+use crate::keymap::LiveKeymap;
+use crate::persistence::{self, HistoryEntry};
+use crate::recorder::AudioChunk;
+use crate::state::Event;
+use crate::streaming::{apply_transcript_update, inject_or_log, record_latency};
+use voxkey_ipc::ExecutionProviderChoice;
+
+/// Run a local streaming transcription session using sherpa-rs's online
+/// transducer, reusing the same delta-injection/backspace-correction
+/// machinery as the Mistral realtime path in `streaming::run_streaming_session`.
+pub async fn run_streaming_session(
+    model_name: &str,
+    execution_provider: ExecutionProviderChoice,
+    sample_rate: u32,
+    mut audio_rx: mpsc::Receiver<AudioChunk>,
+    desktop: Arc<DesktopController>,
+    keymap: Arc<Option<LiveKeymap>>,
+    compose: Arc<Option<std::sync::Mutex<ComposeFallback>>>,
+    state_tx: mpsc::Sender<Event>,
+    stop_rx: oneshot::Receiver<()>,
+    shared: SharedState,
+    connection: zbus::Connection,
+    typing_delay: std::time::Duration,
+    started_at: std::time::Instant,
+    history_path: std::path::PathBuf,
+    engine_label: &'static str,
+    latency_warn_threshold: std::time::Duration,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let model_dir = crate::models::model_dir(model_name);
+    if !crate::models::is_model_available(model_name) {
+        return Err(format!(
+            "Parakeet model '{}' not found at {}. Download it from the Settings app.",
+            model_name, model_dir.display()
+        ).into());
+    }
+
+    let model_dir_str = model_dir.to_string_lossy().to_string();
+    let provider = execution_provider.onnx_provider_name().map(str::to_string);
+
+    let config = sherpa_rs::transducer::OnlineTransducerConfig {
+        encoder: format!("{model_dir_str}/encoder.int8.onnx"),
+        decoder: format!("{model_dir_str}/decoder.int8.onnx"),
+        joiner: format!("{model_dir_str}/joiner.int8.onnx"),
+        tokens: format!("{model_dir_str}/tokens.txt"),
+        model_type: "nemo_transducer".to_string(),
+        num_threads: 4,
+        sample_rate: sample_rate as i32,
+        feature_dim: 80,
+        provider,
+        ..Default::default()
+    };
+
+    tracing::info!("Creating streaming Parakeet recognizer (model={model_name})");
+    let mut recognizer = sherpa_rs::transducer::OnlineTransducerRecognizer::new(config)?;
+    let mut stream = recognizer.create_stream();
+
+    let mut accumulated_transcript = String::new();
+    let mut committed = String::new();
+    let mut volatile = String::new();
+    let mut backspace_supported = true;
+    let mut stop_rx = Some(stop_rx);
+    let mut latest_audio_at: Option<std::time::Instant> = None;
+
+    loop {
+        tokio::select! {
+            chunk = audio_rx.recv() => {
+                match chunk {
+                    Some(AudioChunk { samples, captured_at }) => {
+                        latest_audio_at = Some(captured_at);
+                        let floats: Vec<f32> = samples.iter().map(|&s| s as f32 / i16::MAX as f32).collect();
+                        stream.accept_waveform(sample_rate as i32, &floats);
+                        while recognizer.is_ready(&stream) {
+                            recognizer.decode(&mut stream);
+                        }
+
+                        let hypothesis = recognizer.get_result(&stream);
+                        let is_endpoint = recognizer.is_endpoint(&stream);
+
+                        apply_transcript_update(
+                            &desktop,
+                            &keymap,
+                            &compose,
+                            &mut committed,
+                            &mut volatile,
+                            &hypothesis,
+                            is_endpoint,
+                            typing_delay,
+                            &mut backspace_supported,
+                        ).await?;
+                        accumulated_transcript = format!("{committed}{volatile}");
+                        record_latency(&shared, &connection, latest_audio_at, latency_warn_threshold).await;
+
+                        if is_endpoint {
+                            tracing::debug!("Endpoint detected, resetting stream for next utterance");
+                            recognizer.reset(&mut stream);
+                            committed.clear();
+                            volatile.clear();
+                        }
+                    }
+                    None => {
+                        tracing::info!("Audio channel closed, finalizing streaming transcription");
+                        break;
+                    }
+                }
+            }
+
+            result = async { stop_rx.as_mut().unwrap().await }, if stop_rx.is_some() => {
+                let _ = result;
+                tracing::info!("Stop signal received, finalizing streaming transcription");
+                stop_rx = None;
+                break;
+            }
+        }
+    }
+
+    while recognizer.is_ready(&stream) {
+        recognizer.decode(&mut stream);
+    }
+    let hypothesis = recognizer.get_result(&stream);
+    if let Some(tail) = hypothesis.strip_prefix(committed.as_str()) {
+        if !tail.is_empty() {
+            inject_or_log(&desktop, &keymap, &compose, tail, typing_delay).await?;
+            accumulated_transcript.push_str(tail);
+            record_latency(&shared, &connection, latest_audio_at, latency_warn_threshold).await;
+        }
+    }
+
+    tracing::info!("Streaming transcription complete ({} chars)", accumulated_transcript.len());
+    let transcript_opt = (!accumulated_transcript.is_empty()).then_some(accumulated_transcript.clone());
+    if !accumulated_transcript.is_empty() {
+        shared.set_last_transcript_and_publish(accumulated_transcript);
+    }
+    let entry = HistoryEntry::new(started_at, engine_label, transcript_opt, true);
+    let _ = persistence::append_history_entry(&history_path, &entry);
+    let _ = state_tx.send(Event::InjectionDone).await;
+    Ok(())
+}
@@ -1,10 +1,13 @@
-// ABOUTME: Manages the RemoteDesktop restore token on disk.
-// ABOUTME: Handles saving with 0600 permissions, loading, rotation, and corrupt token recovery.
+// ABOUTME: Manages the RemoteDesktop restore token on disk, and an append-only
+// ABOUTME: local transcript history used for the dictation log and lifetime stats.
 
 use std::fs;
+use std::io::{BufRead, Write};
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 
+use serde::{Deserialize, Serialize};
+
 /// Load a restore token from disk, returning None if missing or unreadable.
 pub fn load_restore_token(path: &Path) -> Option<String> {
     match fs::read_to_string(path) {
@@ -44,3 +47,90 @@ pub fn save_restore_token(path: &Path, token: &str) -> Result<(), Box<dyn std::e
     tracing::info!("Saved restore token to {}", path.display());
     Ok(())
 }
+
+/// One completed (or failed) dictation attempt, appended to the transcript
+/// history after the recording stops and transcription/injection settles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Unix timestamp, in seconds, of when the entry was recorded.
+    pub timestamp: u64,
+    /// How long the capture ran for, from start of recording to this entry.
+    pub duration_ms: u64,
+    pub word_count: usize,
+    /// Transcriber engine used, e.g. "whisper-cpp" or "mistral-realtime" (see
+    /// `Transcriber::engine_label`).
+    pub engine: String,
+    /// For a batch recording, whether a non-empty transcript was produced and
+    /// handed off for injection. A streaming session that ends without error
+    /// is recorded as successful even when it produced no transcript.
+    pub success: bool,
+    /// The transcript text, when one was produced — kept even on injection
+    /// failure so the settings GUI can offer to recover it.
+    pub transcript: Option<String>,
+}
+
+impl HistoryEntry {
+    /// Build an entry from a recording's start time and its outcome.
+    /// `word_count` is derived from `transcript` when present.
+    pub fn new(started_at: std::time::Instant, engine: &str, transcript: Option<String>, success: bool) -> Self {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let word_count = transcript
+            .as_deref()
+            .map(|t| t.split_whitespace().count())
+            .unwrap_or(0);
+        Self {
+            timestamp,
+            duration_ms: started_at.elapsed().as_millis() as u64,
+            word_count,
+            engine: engine.to_string(),
+            success,
+            transcript,
+        }
+    }
+}
+
+/// Append one entry to the transcript history as a JSON line, creating the
+/// file (and its parent directory) if needed.
+pub fn append_history_entry(
+    path: &Path,
+    entry: &HistoryEntry,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+    let line = serde_json::to_string(entry)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+/// Read the most recent `limit` history entries, oldest first. Returns an
+/// empty vec if the file doesn't exist yet; lines that fail to parse are skipped.
+pub fn read_history(path: &Path, limit: usize) -> Vec<HistoryEntry> {
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    let entries: Vec<HistoryEntry> = std::io::BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect();
+    let start = entries.len().saturating_sub(limit);
+    entries[start..].to_vec()
+}
+
+/// Total word count across every recorded history entry, for lifetime stats.
+pub fn history_word_total(path: &Path) -> usize {
+    let Ok(file) = fs::File::open(path) else {
+        return 0;
+    };
+    std::io::BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<HistoryEntry>(&line).ok())
+        .map(|entry| entry.word_count)
+        .sum()
+}
@@ -6,12 +6,56 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
-use crate::config::AudioConfig;
+use crate::config::{AudioConfig, WatchdogConfig};
+use crate::resample::{downmix_to_mono, ResampleQuality, Resampler};
+use crate::vad::VoiceActivityDetector;
+use voxkey_ipc::VadConfig;
 
-/// Records audio from the default input device.
+/// Records audio from the configured (or default) input device.
 pub struct Recorder {
     sample_rate: u32,
     channels: u16,
+    device_name: Option<String>,
+    resample_quality: ResampleQuality,
+}
+
+/// One input device's name and the stream configurations it supports.
+pub struct InputDeviceInfo {
+    pub name: String,
+    pub supported_configs: Vec<cpal::SupportedStreamConfigRange>,
+}
+
+/// A chunk of resampled PCM audio tagged with the monotonic instant it was
+/// captured, so downstream streaming consumers can measure end-to-end
+/// capture-to-injection latency (RFC 6051-style absolute sender timestamps).
+pub struct AudioChunk {
+    pub samples: Vec<i16>,
+    pub captured_at: std::time::Instant,
+}
+
+/// Spawn a watchdog that fires the returned receiver if `audio_received` is
+/// still false after `watchdog_config.timeout_secs`. Returns the flag the
+/// audio callback must set on every invocation, and `None` for the receiver
+/// when the watchdog is disabled.
+fn spawn_dead_air_watchdog(
+    watchdog_config: &WatchdogConfig,
+) -> (Arc<AtomicBool>, Option<tokio::sync::oneshot::Receiver<()>>) {
+    let audio_received = Arc::new(AtomicBool::new(false));
+    if !watchdog_config.enabled {
+        return (audio_received, None);
+    }
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let flag = audio_received.clone();
+    let timeout = std::time::Duration::from_secs_f64(watchdog_config.timeout_secs);
+    tokio::spawn(async move {
+        tokio::time::sleep(timeout).await;
+        if !flag.load(Ordering::Relaxed) {
+            let _ = tx.send(());
+        }
+    });
+
+    (audio_received, Some(rx))
 }
 
 impl Recorder {
@@ -19,38 +63,131 @@ impl Recorder {
         Self {
             sample_rate: config.sample_rate,
             channels: config.channels,
+            device_name: config.device.clone(),
+            resample_quality: config.resample_quality,
         }
     }
 
-    /// Start streaming audio capture. Returns a handle with a channel receiver
-    /// that delivers raw PCM i16 chunks for real-time processing.
-    pub fn start_streaming(&self) -> Result<StreamingRecordingHandle, Box<dyn std::error::Error + Send + Sync>> {
+    /// List available input devices along with their supported stream configs,
+    /// for presenting a device picker in the settings GUI.
+    pub fn list_input_devices() -> Result<Vec<InputDeviceInfo>, Box<dyn std::error::Error + Send + Sync>> {
         let host = cpal::default_host();
-        let device = host
-            .default_input_device()
-            .ok_or("No default input device available")?;
+        let mut devices = Vec::new();
+        for device in host.input_devices()? {
+            let name = device.name().unwrap_or_else(|_| "Unknown device".to_string());
+            let supported_configs = device.supported_input_configs()?.collect();
+            devices.push(InputDeviceInfo { name, supported_configs });
+        }
+        Ok(devices)
+    }
 
-        tracing::info!("Streaming from: {}", device.name().unwrap_or_default());
+    /// Resolve the configured input device by name, falling back to the
+    /// host's default when unset or no longer present (e.g. unplugged).
+    fn resolve_device(&self) -> Result<cpal::Device, Box<dyn std::error::Error + Send + Sync>> {
+        let host = cpal::default_host();
+
+        if let Some(wanted) = self.device_name.as_deref() {
+            for device in host.input_devices()? {
+                if device.name().as_deref() == Ok(wanted) {
+                    return Ok(device);
+                }
+            }
+            tracing::warn!("Configured input device {wanted:?} not found, falling back to default");
+        }
+
+        host.default_input_device()
+            .ok_or_else(|| "No default input device available".into())
+    }
+
+    /// Query the device's native input capabilities rather than assuming it
+    /// accepts `self.sample_rate`/`self.channels` directly — many sound cards
+    /// only expose 44.1/48 kHz capture. The stream is opened at this native
+    /// config and resampled/downmixed to the target afterward.
+    fn negotiate_native_config(
+        &self,
+        device: &cpal::Device,
+    ) -> Result<cpal::SupportedStreamConfig, Box<dyn std::error::Error + Send + Sync>> {
+        device.default_input_config().map_err(|e| {
+            let name = device.name().unwrap_or_else(|_| "device".to_string());
+            format!("{name} has no usable input config: {e}").into()
+        })
+    }
+
+    /// Start streaming audio capture. Returns a handle with a channel receiver
+    /// that delivers raw PCM i16 chunks for real-time processing. When
+    /// `vad_config.enabled`, also runs voice-activity detection over the
+    /// captured audio and signals end-of-speech via
+    /// `StreamingRecordingHandle::wait_for_endpoint`.
+    pub fn start_streaming(
+        &self,
+        vad_config: &VadConfig,
+        watchdog_config: &WatchdogConfig,
+    ) -> Result<StreamingRecordingHandle, Box<dyn std::error::Error + Send + Sync>> {
+        let device = self.resolve_device()?;
+        let native_config = self.negotiate_native_config(&device)?;
+        let native_rate = native_config.sample_rate().0;
+        let native_channels = native_config.channels();
+
+        tracing::info!(
+            "Streaming from: {} ({} Hz, {} ch, resampling to {} Hz mono)",
+            device.name().unwrap_or_default(),
+            native_rate,
+            native_channels,
+            self.sample_rate,
+        );
 
         let desired_config = cpal::StreamConfig {
-            channels: self.channels,
-            sample_rate: cpal::SampleRate(self.sample_rate),
+            channels: native_channels,
+            sample_rate: cpal::SampleRate(native_rate),
             buffer_size: cpal::BufferSize::Default,
         };
 
-        let (tx, rx) = tokio::sync::mpsc::channel::<Vec<i16>>(64);
+        let (tx, rx) = tokio::sync::mpsc::channel::<AudioChunk>(64);
 
         let recording = Arc::new(AtomicBool::new(true));
         let recording_clone = recording.clone();
 
+        let (endpoint_tx, endpoint_rx) = if vad_config.enabled {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            (Some(tx), Some(rx))
+        } else {
+            (None, None)
+        };
+        let vad = vad_config
+            .enabled
+            .then(|| Mutex::new((VoiceActivityDetector::new(self.sample_rate, vad_config), endpoint_tx)));
+        let vad = Arc::new(vad);
+
+        let mut resampler = Resampler::new(native_rate, self.sample_rate, self.resample_quality);
+
+        let (audio_received, dead_air_rx) = spawn_dead_air_watchdog(watchdog_config);
+
         let stream = device.build_input_stream(
             &desired_config,
             move |data: &[i16], _: &cpal::InputCallbackInfo| {
                 if !recording_clone.load(Ordering::Relaxed) {
                     return;
                 }
+                audio_received.store(true, Ordering::Relaxed);
+                let mono = downmix_to_mono(data, native_channels);
+                let resampled = resampler.process(&mono);
+
                 // Drop chunks if receiver is behind — lossy is better than blocking audio
-                let _ = tx.try_send(data.to_vec());
+                let _ = tx.try_send(AudioChunk {
+                    samples: resampled.clone(),
+                    captured_at: std::time::Instant::now(),
+                });
+
+                if let Some(vad_state) = vad.as_ref() {
+                    if let Ok(mut guard) = vad_state.lock() {
+                        let (detector, endpoint_tx) = &mut *guard;
+                        if detector.push_samples(&resampled) {
+                            if let Some(tx) = endpoint_tx.take() {
+                                let _ = tx.send(());
+                            }
+                        }
+                    }
+                }
             },
             move |err| {
                 tracing::error!("Audio input error: {err}");
@@ -65,21 +202,36 @@ impl Recorder {
             stream: Some(stream),
             recording,
             rx: Some(rx),
+            endpoint_rx,
+            dead_air_rx,
+            started_at: std::time::Instant::now(),
         })
     }
 
     /// Start recording. Returns a handle that can be used to stop recording.
-    pub fn start(&self) -> Result<RecordingHandle, Box<dyn std::error::Error + Send + Sync>> {
-        let host = cpal::default_host();
-        let device = host
-            .default_input_device()
-            .ok_or("No default input device available")?;
+    /// When `vad_config.enabled`, also runs voice-activity detection over the
+    /// captured audio and signals end-of-speech via `RecordingHandle::wait_for_endpoint`.
+    pub fn start(
+        &self,
+        vad_config: &VadConfig,
+        watchdog_config: &WatchdogConfig,
+    ) -> Result<RecordingHandle, Box<dyn std::error::Error + Send + Sync>> {
+        let device = self.resolve_device()?;
+        let native_config = self.negotiate_native_config(&device)?;
+        let native_rate = native_config.sample_rate().0;
+        let native_channels = native_config.channels();
 
-        tracing::info!("Recording from: {}", device.name().unwrap_or_default());
+        tracing::info!(
+            "Recording from: {} ({} Hz, {} ch, resampling to {} Hz mono)",
+            device.name().unwrap_or_default(),
+            native_rate,
+            native_channels,
+            self.sample_rate,
+        );
 
         let desired_config = cpal::StreamConfig {
-            channels: self.channels,
-            sample_rate: cpal::SampleRate(self.sample_rate),
+            channels: native_channels,
+            sample_rate: cpal::SampleRate(native_rate),
             buffer_size: cpal::BufferSize::Default,
         };
 
@@ -104,19 +256,48 @@ impl Recorder {
         let recording_clone = recording.clone();
         let writer_clone = writer.clone();
 
+        let (endpoint_tx, endpoint_rx) = if vad_config.enabled {
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            (Some(tx), Some(rx))
+        } else {
+            (None, None)
+        };
+        let vad = vad_config
+            .enabled
+            .then(|| Mutex::new((VoiceActivityDetector::new(self.sample_rate, vad_config), endpoint_tx)));
+        let vad = Arc::new(vad);
+
+        let mut resampler = Resampler::new(native_rate, self.sample_rate, self.resample_quality);
+
+        let (audio_received, dead_air_rx) = spawn_dead_air_watchdog(watchdog_config);
+
         let stream = device.build_input_stream(
             &desired_config,
             move |data: &[i16], _: &cpal::InputCallbackInfo| {
                 if !recording_clone.load(Ordering::Relaxed) {
                     return;
                 }
+                audio_received.store(true, Ordering::Relaxed);
+                let mono = downmix_to_mono(data, native_channels);
+                let resampled = resampler.process(&mono);
+
                 if let Ok(mut guard) = writer_clone.lock() {
                     if let Some(ref mut w) = *guard {
-                        for &sample in data {
+                        for &sample in &resampled {
                             let _ = w.write_sample(sample);
                         }
                     }
                 }
+                if let Some(vad_state) = vad.as_ref() {
+                    if let Ok(mut guard) = vad_state.lock() {
+                        let (detector, endpoint_tx) = &mut *guard;
+                        if detector.push_samples(&resampled) {
+                            if let Some(tx) = endpoint_tx.take() {
+                                let _ = tx.send(());
+                            }
+                        }
+                    }
+                }
             },
             move |err| {
                 tracing::error!("Audio input error: {err}");
@@ -130,8 +311,14 @@ impl Recorder {
         Ok(RecordingHandle {
             stream: Some(stream),
             writer,
+            endpoint_rx,
+            dead_air_rx,
             recording,
             wav_path,
+            vad_config: vad_config.clone(),
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+            started_at: std::time::Instant::now(),
         })
     }
 }
@@ -140,15 +327,50 @@ impl Recorder {
 pub struct StreamingRecordingHandle {
     stream: Option<cpal::Stream>,
     recording: Arc<AtomicBool>,
-    rx: Option<tokio::sync::mpsc::Receiver<Vec<i16>>>,
+    rx: Option<tokio::sync::mpsc::Receiver<AudioChunk>>,
+    endpoint_rx: Option<tokio::sync::oneshot::Receiver<()>>,
+    dead_air_rx: Option<tokio::sync::oneshot::Receiver<()>>,
+    started_at: std::time::Instant,
 }
 
 impl StreamingRecordingHandle {
     /// Take the audio chunk receiver. Can only be called once.
-    pub fn take_rx(&mut self) -> Option<tokio::sync::mpsc::Receiver<Vec<i16>>> {
+    pub fn take_rx(&mut self) -> Option<tokio::sync::mpsc::Receiver<AudioChunk>> {
         self.rx.take()
     }
 
+    /// When this capture began, for computing a dictation history entry's
+    /// `duration_ms`.
+    pub fn started_at(&self) -> std::time::Instant {
+        self.started_at
+    }
+
+    /// Resolves when voice-activity detection declares end-of-speech.
+    /// Never resolves if VAD wasn't enabled for this session.
+    pub async fn wait_for_endpoint(&mut self) {
+        match self.endpoint_rx.as_mut() {
+            Some(rx) => {
+                let _ = rx.await;
+            }
+            None => std::future::pending::<()>().await,
+        }
+    }
+
+    /// Resolves if the watchdog detects no audio arrived from the capture
+    /// device within its deadline. Never resolves if the watchdog is
+    /// disabled, or once audio has started flowing.
+    pub async fn wait_for_dead_air(&mut self) {
+        let Some(rx) = self.dead_air_rx.as_mut() else {
+            return std::future::pending::<()>().await;
+        };
+        if rx.await.is_err() {
+            // Sender was dropped because audio started flowing, not because
+            // of a genuine dead-air timeout. Disable the watchdog for good.
+            self.dead_air_rx = None;
+            std::future::pending::<()>().await;
+        }
+    }
+
     /// Stop the audio capture stream.
     pub fn stop(&mut self) {
         self.recording.store(false, Ordering::Relaxed);
@@ -163,9 +385,47 @@ pub struct RecordingHandle {
     writer: Arc<Mutex<Option<hound::WavWriter<std::io::BufWriter<std::fs::File>>>>>,
     recording: Arc<AtomicBool>,
     wav_path: PathBuf,
+    endpoint_rx: Option<tokio::sync::oneshot::Receiver<()>>,
+    dead_air_rx: Option<tokio::sync::oneshot::Receiver<()>>,
+    vad_config: VadConfig,
+    sample_rate: u32,
+    channels: u16,
+    started_at: std::time::Instant,
 }
 
 impl RecordingHandle {
+    /// When this recording began, for computing a dictation history entry's
+    /// `duration_ms`.
+    pub fn started_at(&self) -> std::time::Instant {
+        self.started_at
+    }
+
+    /// Resolves when voice-activity detection declares end-of-speech.
+    /// Never resolves if VAD wasn't enabled for this recording.
+    pub async fn wait_for_endpoint(&mut self) {
+        match self.endpoint_rx.as_mut() {
+            Some(rx) => {
+                let _ = rx.await;
+            }
+            None => std::future::pending::<()>().await,
+        }
+    }
+
+    /// Resolves if the watchdog detects no audio arrived from the capture
+    /// device within its deadline. Never resolves if the watchdog is
+    /// disabled, or once audio has started flowing.
+    pub async fn wait_for_dead_air(&mut self) {
+        let Some(rx) = self.dead_air_rx.as_mut() else {
+            return std::future::pending::<()>().await;
+        };
+        if rx.await.is_err() {
+            // Sender was dropped because audio started flowing, not because
+            // of a genuine dead-air timeout. Disable the watchdog for good.
+            self.dead_air_rx = None;
+            std::future::pending::<()>().await;
+        }
+    }
+
     /// Stop recording and finalize the WAV file. Returns the path to the WAV file.
     /// Captures a short tail of audio before stopping to avoid cutting off the last words.
     pub async fn stop(mut self) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
@@ -184,7 +444,38 @@ impl RecordingHandle {
             }
         }
 
+        if self.vad_config.enabled {
+            if let Err(e) = self.trim_wav_silence() {
+                tracing::warn!("Failed to trim silence from recording: {e}");
+            }
+        }
+
         tracing::info!("Recording stopped, saved to: {}", self.wav_path.display());
         Ok(self.wav_path)
     }
+
+    /// Rewrite the WAV file with leading/trailing silence trimmed off, per VAD.
+    fn trim_wav_silence(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut reader = hound::WavReader::open(&self.wav_path)?;
+        let samples: Vec<i16> = reader.samples::<i16>().collect::<Result<_, _>>()?;
+
+        let (start, end) = crate::vad::trim_silence(&samples, self.sample_rate, &self.vad_config);
+        if start == 0 && end == samples.len() {
+            return Ok(());
+        }
+
+        let spec = hound::WavSpec {
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&self.wav_path, spec)?;
+        for &sample in &samples[start..end] {
+            writer.write_sample(sample)?;
+        }
+        writer.finalize()?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,203 @@
+// ABOUTME: Resamples captured PCM audio to the transcriber's target sample rate.
+// ABOUTME: Carries trailing input history across blocks so there's no discontinuity at block boundaries.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// Interpolation quality used by `Resampler`. `Linear` is cheap and adequate
+/// for speech; `Sinc` uses a windowed-sinc kernel for less aliasing at the
+/// cost of more CPU per sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResampleQuality {
+    Linear,
+    Sinc,
+}
+
+impl Default for ResampleQuality {
+    fn default() -> Self {
+        ResampleQuality::Linear
+    }
+}
+
+/// Half-width, in input samples, of the windowed-sinc kernel used at `Sinc` quality.
+const SINC_HALF_WIDTH: usize = 8;
+
+/// Resamples a stream of mono i16 PCM from one sample rate to another.
+/// Construct once per recording session and feed it sequential blocks in
+/// order — it carries trailing input history across `process` calls so the
+/// first output samples of a block never see a discontinuity at the seam.
+pub struct Resampler {
+    in_rate: f64,
+    out_rate: f64,
+    quality: ResampleQuality,
+    half_width: usize,
+    /// Trailing input samples from previous blocks, long enough to seed the
+    /// interpolation kernel for the next block's leading output samples.
+    history: VecDeque<f32>,
+    /// Position of the next output sample, in input-sample units, measured
+    /// from the start of `history`.
+    next_pos: f64,
+}
+
+impl Resampler {
+    pub fn new(in_rate: u32, out_rate: u32, quality: ResampleQuality) -> Self {
+        let half_width = match quality {
+            ResampleQuality::Linear => 1,
+            ResampleQuality::Sinc => SINC_HALF_WIDTH,
+        };
+        Self {
+            in_rate: in_rate as f64,
+            out_rate: out_rate as f64,
+            quality,
+            half_width,
+            history: std::iter::repeat(0.0f32).take(half_width).collect(),
+            next_pos: half_width as f64,
+        }
+    }
+
+    /// Resample one block of mono i16 samples, returning the resampled output.
+    /// Call in order; do not skip or reorder blocks.
+    pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
+        if self.in_rate == self.out_rate {
+            return input.to_vec();
+        }
+
+        let mut buffer: Vec<f32> = self.history.iter().copied().collect();
+        buffer.extend(input.iter().map(|&s| s as f32));
+
+        let step = self.in_rate / self.out_rate;
+        let mut output = Vec::new();
+        let mut pos = self.next_pos;
+
+        // Stop once the kernel would need samples beyond what's buffered —
+        // those output samples are produced on the next call instead, once
+        // fresh input has arrived to fill in the gap.
+        while pos + self.half_width as f64 + 1.0 < buffer.len() as f64 {
+            let sample = match self.quality {
+                ResampleQuality::Linear => linear_sample(&buffer, pos),
+                ResampleQuality::Sinc => sinc_sample(&buffer, pos, self.half_width),
+            };
+            output.push(sample.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16);
+            pos += step;
+        }
+
+        // Carry the tail of the buffer forward as history for the next
+        // block, rebasing the pending fractional position onto it.
+        let history_start = buffer.len().saturating_sub(self.half_width * 2);
+        self.next_pos = pos - history_start as f64;
+        self.history = buffer[history_start..].iter().copied().collect();
+
+        output
+    }
+}
+
+fn linear_sample(buffer: &[f32], pos: f64) -> f32 {
+    let i0 = pos.floor() as usize;
+    let frac = (pos - i0 as f64) as f32;
+    let s0 = buffer.get(i0).copied().unwrap_or(0.0);
+    let s1 = buffer.get(i0 + 1).copied().unwrap_or(s0);
+    s0 + (s1 - s0) * frac
+}
+
+fn sinc_sample(buffer: &[f32], pos: f64, half_width: usize) -> f32 {
+    let center = pos.floor() as isize;
+    let frac = pos - center as f64;
+    let mut acc = 0.0f32;
+    for k in -(half_width as isize)..=(half_width as isize) {
+        let idx = center + k;
+        if idx < 0 {
+            continue;
+        }
+        let Some(&sample) = buffer.get(idx as usize) else {
+            continue;
+        };
+        let x = frac - k as f64;
+        acc += sample * (sinc(x) * hann_taper(x, half_width as f64)) as f32;
+    }
+    acc
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        (std::f64::consts::PI * x).sin() / (std::f64::consts::PI * x)
+    }
+}
+
+fn hann_taper(x: f64, half_width: f64) -> f64 {
+    if x.abs() >= half_width {
+        0.0
+    } else {
+        0.5 * (1.0 + (std::f64::consts::PI * x / half_width).cos())
+    }
+}
+
+/// Downmix interleaved multi-channel i16 samples to mono by averaging channels.
+pub fn downmix_to_mono(samples: &[i16], channels: u16) -> Vec<i16> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+    let channels = channels as usize;
+    samples
+        .chunks(channels)
+        .map(|frame| {
+            let sum: i64 = frame.iter().map(|&s| s as i64).sum();
+            (sum / frame.len() as i64) as i16
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downmix_averages_stereo_channels() {
+        let samples = [100i16, 300, -200, 0];
+        assert_eq!(downmix_to_mono(&samples, 2), vec![200, -100]);
+    }
+
+    #[test]
+    fn downmix_is_noop_for_mono() {
+        let samples = [100i16, -50, 75];
+        assert_eq!(downmix_to_mono(&samples, 1), samples.to_vec());
+    }
+
+    #[test]
+    fn process_is_identity_when_rates_match() {
+        let mut resampler = Resampler::new(16000, 16000, ResampleQuality::Linear);
+        let input: Vec<i16> = (0..100).collect();
+        assert_eq!(resampler.process(&input), input);
+    }
+
+    #[test]
+    fn process_downsamples_to_roughly_the_expected_length() {
+        let mut resampler = Resampler::new(48000, 16000, ResampleQuality::Linear);
+        let input = vec![1000i16; 4800];
+        let output = resampler.process(&input);
+        let expected = input.len() / 3;
+        assert!((output.len() as i64 - expected as i64).abs() <= 2);
+    }
+
+    #[test]
+    fn process_carries_continuity_across_block_boundaries() {
+        // A continuous ramp resampled in two separate blocks should produce
+        // the same output as resampling it in one go, modulo the few samples
+        // right at the boundary that depend on when output catches up.
+        let sample_rate_in = 48000;
+        let sample_rate_out = 16000;
+        let ramp: Vec<i16> = (0..9600).map(|n| (n % 1000) as i16).collect();
+
+        let mut whole = Resampler::new(sample_rate_in, sample_rate_out, ResampleQuality::Sinc);
+        let whole_output = whole.process(&ramp);
+
+        let mut split = Resampler::new(sample_rate_in, sample_rate_out, ResampleQuality::Sinc);
+        let mut split_output = split.process(&ramp[..4800]);
+        split_output.extend(split.process(&ramp[4800..]));
+
+        assert!((whole_output.len() as i64 - split_output.len() as i64).abs() <= 2);
+    }
+}
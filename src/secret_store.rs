@@ -0,0 +1,34 @@
+// ABOUTME: Wraps the OS secret service (Secret Service / libsecret via the keyring crate) for API key storage.
+// ABOUTME: Every operation is best-effort; callers fall back to storing keys inline when no secret service is reachable.
+
+const SERVICE: &str = "voxkey";
+
+/// Store `secret` under `account` in the OS secret service. Returns an error
+/// (rather than panicking) when no secret service is reachable, so callers
+/// can fall back to keeping the value inline.
+pub fn store(account: &str, secret: &str) -> Result<(), String> {
+    keyring::Entry::new(SERVICE, account)
+        .and_then(|entry| entry.set_password(secret))
+        .map_err(|e| e.to_string())
+}
+
+/// Load a previously stored secret, or `None` if it was never stored or no
+/// secret service is reachable.
+pub fn load(account: &str) -> Option<String> {
+    keyring::Entry::new(SERVICE, account)
+        .ok()?
+        .get_password()
+        .ok()
+}
+
+/// Whether a secret is currently stored under `account`.
+pub fn has(account: &str) -> bool {
+    load(account).is_some()
+}
+
+/// Remove a previously stored secret, if any.
+pub fn clear(account: &str) -> Result<(), String> {
+    keyring::Entry::new(SERVICE, account)
+        .and_then(|entry| entry.delete_credential())
+        .map_err(|e| e.to_string())
+}
@@ -1,6 +1,11 @@
 // ABOUTME: Manages the GlobalShortcuts portal session for hold-to-dictate.
 // ABOUTME: Creates sessions, binds shortcuts, and provides Activated/Deactivated signal streams.
 
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
 use ashpd::desktop::global_shortcuts::{
     Activated, Deactivated, GlobalShortcuts, NewShortcut,
 };
@@ -11,6 +16,28 @@ use crate::config::ShortcutConfig;
 
 type DynError = Box<dyn std::error::Error + Send + Sync>;
 
+/// A debounced, tap-toggle-aware press/release of the dictation shortcut,
+/// produced by [`ShortcutController::dictation_stream`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictationEvent {
+    Start,
+    Stop,
+}
+
+/// Where a physical press currently stands.
+enum PressPhase {
+    /// Not pressed.
+    Idle,
+    /// Pressed; waiting up to `confirm` to tell a bounce, a tap, and a hold
+    /// apart before emitting anything.
+    Pending {
+        pressed_at: Instant,
+        confirm: Pin<Box<tokio::time::Sleep>>,
+    },
+    /// Confirmed hold: a `Start` has already been emitted for this press.
+    Holding,
+}
+
 /// Holds the GlobalShortcuts proxy and active session.
 pub struct ShortcutController {
     proxy: GlobalShortcuts,
@@ -72,6 +99,154 @@ impl ShortcutController {
     ) -> Result<impl Stream<Item = Deactivated> + '_, DynError> {
         Ok(self.proxy.receive_deactivated().await?)
     }
+
+    /// Derive a debounced `DictationEvent` stream from the shortcut's raw
+    /// Activated/Deactivated signals, gated by `config.min_hold_ms`/`tap_toggle_ms`.
+    /// See [`DictationEvents`] for the state machine.
+    pub async fn dictation_stream(
+        &self,
+        config: &ShortcutConfig,
+    ) -> Result<DictationEvents<'_>, DynError> {
+        let activated: Pin<Box<dyn Stream<Item = Activated> + '_>> =
+            Box::pin(self.activated_stream().await?);
+        let deactivated: Pin<Box<dyn Stream<Item = Deactivated> + '_>> =
+            Box::pin(self.deactivated_stream().await?);
+        Ok(DictationEvents {
+            activated,
+            deactivated,
+            shortcut_id: config.id.clone(),
+            min_hold: Duration::from_millis(config.min_hold_ms),
+            tap_toggle: Duration::from_millis(config.tap_toggle_ms),
+            phase: PressPhase::Idle,
+            latched: false,
+            activated_done: false,
+            deactivated_done: false,
+        })
+    }
+}
+
+/// Turns a shortcut's raw Activated/Deactivated signals into clean
+/// `DictationEvent::{Start, Stop}` events:
+///
+/// - GNOME repeats `Activated` roughly every 30ms while the shortcut is held;
+///   since a press only moves `phase` out of `Idle` once, repeats are
+///   naturally ignored without a separate gap-timing heuristic.
+/// - A press is held in `Pending` until `min_hold_ms`/`tap_toggle_ms` confirm
+///   what it is, or it's released first:
+///   - Released before `min_hold_ms`: a bounce, discarded with no events.
+///   - Released before `tap_toggle_ms` (but past `min_hold_ms`): a tap, which
+///     flips the latched "continuous dictation" state.
+///   - Still held once `tap_toggle_ms` (or `min_hold_ms`, if tap-toggle is
+///     disabled) elapses: a hold, so `Start` fires now and `Stop` follows on
+///     release — the original hold-to-dictate behavior.
+/// - If the portal drops the GlobalShortcuts session (compositor restart,
+///   logout), both underlying signal streams end and this stream ends too,
+///   so the caller can tell a dead session apart from an idle one.
+pub struct DictationEvents<'a> {
+    activated: Pin<Box<dyn Stream<Item = Activated> + 'a>>,
+    deactivated: Pin<Box<dyn Stream<Item = Deactivated> + 'a>>,
+    shortcut_id: String,
+    min_hold: Duration,
+    tap_toggle: Duration,
+    phase: PressPhase,
+    /// Set once a tap has latched continuous dictation on; a second tap ends it.
+    latched: bool,
+    /// The portal closed `receive_activated`/`receive_deactivated` on us —
+    /// the GlobalShortcuts session died (compositor restart, logout). Once
+    /// both signal streams have ended, this stream ends too, rather than
+    /// polling forever, so the caller can tell a dead session apart from a
+    /// simple lull between presses.
+    activated_done: bool,
+    deactivated_done: bool,
+}
+
+impl Stream for DictationEvents<'_> {
+    type Item = DictationEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let PressPhase::Pending { confirm, .. } = &mut this.phase {
+                if confirm.as_mut().poll(cx).is_ready() {
+                    this.phase = PressPhase::Holding;
+                    return Poll::Ready(Some(DictationEvent::Start));
+                }
+            }
+
+            let mut progressed = false;
+
+            if !this.activated_done {
+                match this.activated.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(signal)) => {
+                        progressed = true;
+                        if signal.shortcut_id() == this.shortcut_id && matches!(this.phase, PressPhase::Idle) {
+                            let confirm_after = if this.tap_toggle > Duration::ZERO {
+                                this.tap_toggle
+                            } else {
+                                this.min_hold
+                            };
+                            if confirm_after.is_zero() {
+                                this.phase = PressPhase::Holding;
+                                return Poll::Ready(Some(DictationEvent::Start));
+                            }
+                            this.phase = PressPhase::Pending {
+                                pressed_at: Instant::now(),
+                                confirm: Box::pin(tokio::time::sleep(confirm_after)),
+                            };
+                        }
+                    }
+                    Poll::Ready(None) => {
+                        progressed = true;
+                        this.activated_done = true;
+                    }
+                    Poll::Pending => {}
+                }
+            }
+
+            if !this.deactivated_done {
+                match this.deactivated.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(signal)) => {
+                        progressed = true;
+                        if signal.shortcut_id() == this.shortcut_id {
+                            match std::mem::replace(&mut this.phase, PressPhase::Idle) {
+                                PressPhase::Idle => {}
+                                PressPhase::Pending { pressed_at, .. } => {
+                                    let held = pressed_at.elapsed();
+                                    if held >= this.min_hold && this.tap_toggle > Duration::ZERO {
+                                        this.latched = !this.latched;
+                                        return Poll::Ready(Some(if this.latched {
+                                            DictationEvent::Start
+                                        } else {
+                                            DictationEvent::Stop
+                                        }));
+                                    }
+                                    // Below min_hold_ms (or tap-toggle disabled
+                                    // and still below it): a bounce, discard silently.
+                                }
+                                PressPhase::Holding => {
+                                    this.latched = false;
+                                    return Poll::Ready(Some(DictationEvent::Stop));
+                                }
+                            }
+                        }
+                    }
+                    Poll::Ready(None) => {
+                        progressed = true;
+                        this.deactivated_done = true;
+                    }
+                    Poll::Pending => {}
+                }
+            }
+
+            if this.activated_done && this.deactivated_done {
+                return Poll::Ready(None);
+            }
+
+            if !progressed {
+                return Poll::Pending;
+            }
+        }
+    }
 }
 
 /// Format a shortcut config as a GVariant text value for GNOME's dconf schema.
@@ -116,6 +291,8 @@ mod tests {
             id: "dictate_hold".to_string(),
             description: "Dictate".to_string(),
             trigger: "<Super>t".to_string(),
+            min_hold_ms: 0,
+            tap_toggle_ms: 0,
         };
 
         let value = format_dconf_value(&config);
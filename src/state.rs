@@ -2,6 +2,7 @@
 // ABOUTME: Prevents race conditions by enforcing valid state transitions only.
 
 use std::fmt;
+use std::time::Duration;
 
 /// The daemon's operational states.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,6 +15,37 @@ pub enum State {
     RecoveringSession,
 }
 
+/// For `SharedState`'s lock-free `AtomicU8` storage.
+impl From<State> for u8 {
+    fn from(state: State) -> u8 {
+        match state {
+            State::Idle => 0,
+            State::Recording => 1,
+            State::Streaming => 2,
+            State::Transcribing => 3,
+            State::Injecting => 4,
+            State::RecoveringSession => 5,
+        }
+    }
+}
+
+/// For `SharedState`'s lock-free `AtomicU8` storage.
+impl TryFrom<u8> for State {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(State::Idle),
+            1 => Ok(State::Recording),
+            2 => Ok(State::Streaming),
+            3 => Ok(State::Transcribing),
+            4 => Ok(State::Injecting),
+            5 => Ok(State::RecoveringSession),
+            _ => Err(()),
+        }
+    }
+}
+
 impl fmt::Display for State {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -92,6 +124,35 @@ impl State {
             _ => None,
         }
     }
+
+    /// Maximum time this state may be held before the watchdog forces it to
+    /// move on, or `None` if the state waits on the user rather than on some
+    /// external operation completing (so it has no natural deadline).
+    fn timeout(self) -> Option<Duration> {
+        match self {
+            State::Idle | State::Recording | State::Streaming => None,
+            State::Transcribing => Some(Duration::from_secs(30)),
+            State::Injecting => Some(Duration::from_secs(10)),
+            State::RecoveringSession => Some(Duration::from_secs(5)),
+        }
+    }
+
+    /// Called periodically by the daemon's select loop with how long the
+    /// current state has been held. Returns the state to force a transition
+    /// to if this state has exceeded its deadline — `RecoveringSession` for a
+    /// wedged transient state (e.g. a transcriber that never replies), or
+    /// `Idle` if `RecoveringSession` itself never resolves — so a stuck
+    /// session always self-heals instead of wedging the daemon permanently.
+    pub fn poll_timeout(self, elapsed: Duration) -> Option<State> {
+        let timeout = self.timeout()?;
+        if elapsed < timeout {
+            return None;
+        }
+        match self {
+            State::RecoveringSession => Some(State::Idle),
+            _ => Some(State::RecoveringSession),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -134,4 +195,26 @@ mod tests {
             Some(State::Idle)
         );
     }
+
+    #[test]
+    fn recording_has_no_timeout() {
+        assert_eq!(State::Recording.poll_timeout(Duration::from_secs(3600)), None);
+    }
+
+    #[test]
+    fn transcribing_times_out_to_recovering_session() {
+        assert_eq!(
+            State::Transcribing.poll_timeout(Duration::from_secs(30)),
+            Some(State::RecoveringSession)
+        );
+        assert_eq!(State::Transcribing.poll_timeout(Duration::from_secs(29)), None);
+    }
+
+    #[test]
+    fn recovering_session_times_out_to_idle() {
+        assert_eq!(
+            State::RecoveringSession.poll_timeout(Duration::from_secs(5)),
+            Some(State::Idle)
+        );
+    }
 }
@@ -1,6 +1,7 @@
 // ABOUTME: Manages real-time streaming transcription via Mistral's WebSocket API.
 // ABOUTME: Sends PCM audio chunks and injects text deltas as they arrive.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use base64::Engine;
@@ -8,11 +9,34 @@ use futures_util::{SinkExt, StreamExt};
 use tokio::sync::{mpsc, oneshot};
 use tokio_tungstenite::tungstenite;
 
+use crate::compose::ComposeFallback;
 use crate::dbus::{DaemonInterface, SharedState};
 use crate::desktop::DesktopController;
 use crate::injector;
+use crate::keymap::LiveKeymap;
+use crate::persistence::{self, HistoryEntry};
+use crate::recorder::AudioChunk;
 use crate::state::Event;
-use voxkey_ipc::MistralRealtimeConfig;
+use voxkey_ipc::{MistralRealtimeConfig, StabilityLevel};
+
+/// How long a volatile (uncommitted) transcript tail can go unchanged before
+/// it's promoted to committed, absent an explicit `stable` flag from the server.
+const STABILITY_WINDOW: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// How much recently-sent PCM audio is retained so it can be replayed to a
+/// freshly reconnected session after a dropped connection.
+const REPLAY_BUFFER_SECONDS: u32 = 5;
+
+/// Base delay for the exponential reconnect backoff; doubles per attempt and
+/// is capped at a few seconds so a flaky connection doesn't stall dictation.
+const RECONNECT_BASE_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+const RECONNECT_MAX_DELAY: std::time::Duration = std::time::Duration::from_secs(8);
+
+type WsStream = tokio_tungstenite::WebSocketStream<
+    tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+>;
+type WsSink = futures_util::stream::SplitSink<WsStream, tungstenite::Message>;
+type WsSource = futures_util::stream::SplitStream<WsStream>;
 
 /// Run a streaming transcription session over WebSocket.
 ///
@@ -21,94 +45,91 @@ use voxkey_ipc::MistralRealtimeConfig;
 pub async fn run_streaming_session(
     config: &MistralRealtimeConfig,
     sample_rate: u32,
-    mut audio_rx: mpsc::Receiver<Vec<i16>>,
+    mut audio_rx: mpsc::Receiver<AudioChunk>,
     desktop: Arc<DesktopController>,
+    keymap: Arc<Option<LiveKeymap>>,
+    compose: Arc<Option<std::sync::Mutex<ComposeFallback>>>,
     state_tx: mpsc::Sender<Event>,
     stop_rx: oneshot::Receiver<()>,
     shared: SharedState,
     connection: zbus::Connection,
     typing_delay: std::time::Duration,
+    capture_dir: Option<PathBuf>,
+    started_at: std::time::Instant,
+    history_path: PathBuf,
+    engine_label: &'static str,
+    latency_warn_threshold: std::time::Duration,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let base_url = if config.endpoint.is_empty() {
-        MistralRealtimeConfig::DEFAULT_ENDPOINT
-    } else {
-        &config.endpoint
-    };
-    let url = format!("{base_url}?model={}", config.model);
+    let (mut ws_sink, mut ws_source) = connect_and_handshake(config, sample_rate).await?;
 
-    // Extract host from wss://host/... for the Host header
-    let host = url.split("://")
-        .nth(1)
-        .and_then(|rest| rest.split('/').next())
-        .unwrap_or("api.mistral.ai");
-
-    let request = http::Request::builder()
-        .uri(&url)
-        .header("Authorization", format!("Bearer {}", config.api_key))
-        .header("Host", host)
-        .header("Connection", "Upgrade")
-        .header("Upgrade", "websocket")
-        .header("Sec-WebSocket-Version", "13")
-        .header(
-            "Sec-WebSocket-Key",
-            tungstenite::handshake::client::generate_key(),
-        )
-        .body(())?;
-
-    let (ws_stream, _response) = tokio_tungstenite::connect_async(request).await?;
-    let (mut ws_sink, mut ws_source) = ws_stream.split();
-
-    tracing::info!("WebSocket connected to Mistral Realtime API");
-
-    // Wait for session.created
-    loop {
-        match ws_source.next().await {
-            Some(Ok(tungstenite::Message::Text(text))) => {
-                let msg: ServerMessage = serde_json::from_str(&text)?;
-                if msg.r#type == "session.created" {
-                    tracing::info!("Streaming session created");
-                    break;
-                }
-            }
-            Some(Ok(_)) => continue,
-            Some(Err(e)) => return Err(e.into()),
-            None => return Err("WebSocket closed before session.created".into()),
-        }
-    }
-
-    // Send session.update with audio format
-    let session_update = SessionUpdate {
-        r#type: "session.update",
-        session: SessionConfig {
-            audio_format: AudioFormat {
-                encoding: "pcm_s16le",
-                sample_rate,
-            },
-        },
-    };
-    let update_json = serde_json::to_string(&session_update)?;
-    ws_sink
-        .send(tungstenite::Message::Text(update_json.into()))
-        .await?;
+    let mut capture_writer = capture_dir
+        .map(|dir| create_capture_writer(&dir, sample_rate))
+        .transpose()?;
 
     // Main loop
     let mut accumulated_transcript = String::new();
     let mut draining = false;
     let mut stop_rx = Some(stop_rx);
 
+    // Monotonically increasing index into the most recent partial result's
+    // items — everything before it has already been injected exactly once.
+    let mut emitted_index = 0usize;
+
+    // Trailing confirmation required before a stable item is trusted, per
+    // `config.stability` — the same knob sent to the server doubles as the
+    // local stabilization aggressiveness, trading latency for fewer corrections.
+    let confirmation_lag = confirmation_lag(config.stability);
+
+    // Most recent partial result's items, retained so a final `transcription.done`
+    // can flush any still-unemitted trailing items instead of dropping them.
+    let mut last_partial_items: Vec<TranscriptItem> = Vec::new();
+
+    // Committed/volatile split for stability-gated delta injection (see
+    // `apply_transcript_update`). `committed` has already been injected and is
+    // never revised; `volatile` is the currently-displayed tail that may still
+    // be retracted and retyped as the server's hypothesis changes. These (and
+    // `emitted_index` above) deliberately survive a reconnect — replayed audio
+    // reproduces deltas already reflected in `committed`/`emitted_index`, so
+    // the existing prefix-diffing naturally skips re-injecting them.
+    let mut committed = String::new();
+    let mut volatile = String::new();
+    let mut volatile_changed_at = tokio::time::Instant::now();
+    let mut backspace_supported = true;
+
+    // Ring buffer of the most recent audio sent to the server, replayed to a
+    // freshly reconnected session after a dropped connection.
+    let mut replay_buffer: std::collections::VecDeque<i16> = std::collections::VecDeque::new();
+    let replay_capacity = (REPLAY_BUFFER_SECONDS * sample_rate) as usize;
+
+    // Capture instant of the most recently received audio chunk, used to
+    // measure how far behind speech the text we're about to inject lags.
+    let mut latest_audio_at: Option<std::time::Instant> = None;
+
     loop {
         tokio::select! {
             // Audio chunk from recorder
             chunk = audio_rx.recv(), if !draining => {
                 match chunk {
-                    Some(samples) => {
-                        let encoded = encode_pcm_samples(&samples);
-                        let msg = AudioAppend {
-                            r#type: "input_audio.append",
-                            audio: &encoded,
-                        };
-                        let json = serde_json::to_string(&msg)?;
-                        ws_sink.send(tungstenite::Message::Text(json.into())).await?;
+                    Some(AudioChunk { samples, captured_at }) => {
+                        latest_audio_at = Some(captured_at);
+                        replay_buffer.extend(samples.iter().copied());
+                        while replay_buffer.len() > replay_capacity {
+                            replay_buffer.pop_front();
+                        }
+
+                        if let Some(writer) = capture_writer.as_mut() {
+                            for &sample in &samples {
+                                let _ = writer.write_sample(sample);
+                            }
+                        }
+
+                        let message = build_audio_message(&samples, config.binary_audio)?;
+                        if ws_sink.send(message).await.is_err() {
+                            tracing::warn!("Lost connection sending audio, attempting reconnect");
+                            let (sink, source) = reconnect_with_backoff(config, sample_rate, &replay_buffer).await?;
+                            ws_sink = sink;
+                            ws_source = source;
+                        }
                     }
                     None => {
                         // Audio channel closed — treat as stop
@@ -120,6 +141,14 @@ pub async fn run_streaming_session(
                 }
             }
 
+            // Volatile transcript tail aged past the stability window without
+            // a further update — promote it to committed.
+            _ = tokio::time::sleep_until(volatile_changed_at + STABILITY_WINDOW), if !volatile.is_empty() => {
+                tracing::debug!("Volatile transcript tail stabilized by timeout, committing");
+                committed.push_str(&volatile);
+                volatile.clear();
+            }
+
             // Stop signal from main loop (key released)
             result = async { stop_rx.as_mut().unwrap().await }, if stop_rx.is_some() && !draining => {
                 let _ = result;
@@ -137,25 +166,90 @@ pub async fn run_streaming_session(
                         let msg: ServerMessage = serde_json::from_str(&text)?;
                         match msg.r#type.as_str() {
                             "transcription.text.delta" => {
-                                if let Some(delta) = msg.text {
-                                    match injector::inject_text(&desktop, &delta, typing_delay).await {
-                                        Ok(()) => {}
-                                        Err(injector::InjectionError::Portal(e)) => {
-                                            return Err(format!("Portal error during streaming injection: {e}").into());
-                                        }
-                                        Err(injector::InjectionError::Local(e)) => {
-                                            tracing::error!("Failed to inject text delta: {e}");
+                                match msg.full_text {
+                                    Some(full_text) => {
+                                        // Snapshot-style update carrying a running full text and
+                                        // a stability flag — reconcile against the displayed tail.
+                                        apply_transcript_update(
+                                            &desktop,
+                                            &keymap,
+                                            &compose,
+                                            &mut committed,
+                                            &mut volatile,
+                                            &full_text,
+                                            msg.stable.unwrap_or(false),
+                                            typing_delay,
+                                            &mut backspace_supported,
+                                        ).await?;
+                                        volatile_changed_at = tokio::time::Instant::now();
+                                    }
+                                    None => {
+                                        // Flat delta without stability info — inject as-is and
+                                        // commit immediately since there's nothing to walk back.
+                                        if let Some(delta) = msg.text {
+                                            inject_or_log(&desktop, &keymap, &compose, &delta, typing_delay).await?;
+                                            committed.push_str(&delta);
                                         }
                                     }
-                                    accumulated_transcript.push_str(&delta);
                                 }
+                                accumulated_transcript = format!("{committed}{volatile}");
+                                record_latency(&shared, &connection, latest_audio_at, latency_warn_threshold).await;
+                            }
+                            "transcription.text.partial" => {
+                                let Some(items) = msg.items else { continue };
+
+                                // Walk forward from the emitted index, injecting (and
+                                // advancing past) every item already marked stable so
+                                // each word is typed exactly once. `confirmation_lag`
+                                // extra items (stable or not) must already follow before we
+                                // trust it, per the configured stabilization aggressiveness.
+                                // A revised hypothesis can come back shorter than what
+                                // we've already emitted (or this can be the first
+                                // partial of a new segment before `done` resets the
+                                // index) — clamp so the slice below can't panic.
+                                if emitted_index > items.len() {
+                                    emitted_index = items.len();
+                                }
+
+                                while emitted_index + confirmation_lag < items.len()
+                                    && items[emitted_index].stable
+                                {
+                                    let word = items[emitted_index].text.clone();
+                                    inject_or_log(&desktop, &keymap, &compose, &word, typing_delay).await?;
+                                    accumulated_transcript.push_str(&word);
+                                    emitted_index += 1;
+                                }
+
+                                // Not-yet-stable trailing items are held back and
+                                // surfaced as a live preview instead of injected.
+                                let preview: String = items[emitted_index.min(items.len())..]
+                                    .iter()
+                                    .map(|item| item.text.as_str())
+                                    .collect();
+                                DaemonInterface::notify_transcription_partial(&connection, &preview).await;
+                                last_partial_items = items;
+                                record_latency(&shared, &connection, latest_audio_at, latency_warn_threshold).await;
                             }
                             "transcription.done" => {
+                                // The result is final — any items still held back as
+                                // unstable are now trustworthy, so flush them before
+                                // resetting the index for the (nonexistent) next utterance.
+                                for item in &last_partial_items[emitted_index..] {
+                                    inject_or_log(&desktop, &keymap, &compose, &item.text, typing_delay).await?;
+                                    accumulated_transcript.push_str(&item.text);
+                                }
+                                emitted_index = 0;
+                                last_partial_items.clear();
+                                record_latency(&shared, &connection, latest_audio_at, latency_warn_threshold).await;
+
                                 tracing::info!("Streaming transcription complete ({} chars)", accumulated_transcript.len());
+                                let transcript_opt = (!accumulated_transcript.is_empty()).then_some(accumulated_transcript.clone());
                                 if !accumulated_transcript.is_empty() {
-                                    shared.set_last_transcript(accumulated_transcript);
-                                    DaemonInterface::notify_last_transcript(&connection).await;
+                                    shared.set_last_transcript_and_publish(accumulated_transcript);
                                 }
+                                let entry = HistoryEntry::new(started_at, engine_label, transcript_opt, true);
+                                let _ = persistence::append_history_entry(&history_path, &entry);
+                                finalize_capture(capture_writer.take());
                                 let _ = state_tx.send(Event::InjectionDone).await;
                                 return Ok(());
                             }
@@ -170,23 +264,48 @@ pub async fn run_streaming_session(
                         }
                     }
                     Some(Ok(tungstenite::Message::Close(_))) => {
-                        tracing::info!("WebSocket closed by server");
-                        if !accumulated_transcript.is_empty() {
-                            shared.set_last_transcript(accumulated_transcript);
-                            DaemonInterface::notify_last_transcript(&connection).await;
+                        if draining {
+                            tracing::info!("WebSocket closed by server");
+                            let transcript_opt = (!accumulated_transcript.is_empty()).then_some(accumulated_transcript.clone());
+                            if !accumulated_transcript.is_empty() {
+                                shared.set_last_transcript_and_publish(accumulated_transcript);
+                            }
+                            let entry = HistoryEntry::new(started_at, engine_label, transcript_opt, true);
+                            let _ = persistence::append_history_entry(&history_path, &entry);
+                            finalize_capture(capture_writer.take());
+                            let _ = state_tx.send(Event::InjectionDone).await;
+                            return Ok(());
                         }
-                        let _ = state_tx.send(Event::InjectionDone).await;
-                        return Ok(());
+                        tracing::warn!("WebSocket closed unexpectedly mid-dictation, attempting reconnect");
+                        let (sink, source) = reconnect_with_backoff(config, sample_rate, &replay_buffer).await?;
+                        ws_sink = sink;
+                        ws_source = source;
                     }
                     Some(Ok(_)) => continue,
                     Some(Err(e)) => {
-                        tracing::error!("WebSocket error: {e}");
-                        return Err(e.into());
+                        if draining {
+                            tracing::error!("WebSocket error: {e}");
+                            return Err(e.into());
+                        }
+                        tracing::warn!("WebSocket error mid-dictation, attempting reconnect: {e}");
+                        let (sink, source) = reconnect_with_backoff(config, sample_rate, &replay_buffer).await?;
+                        ws_sink = sink;
+                        ws_source = source;
                     }
                     None => {
-                        tracing::info!("WebSocket stream ended");
-                        let _ = state_tx.send(Event::InjectionDone).await;
-                        return Ok(());
+                        if draining {
+                            tracing::info!("WebSocket stream ended");
+                            let transcript_opt = (!accumulated_transcript.is_empty()).then_some(accumulated_transcript.clone());
+                            let entry = HistoryEntry::new(started_at, engine_label, transcript_opt, true);
+                            let _ = persistence::append_history_entry(&history_path, &entry);
+                            finalize_capture(capture_writer.take());
+                            let _ = state_tx.send(Event::InjectionDone).await;
+                            return Ok(());
+                        }
+                        tracing::warn!("WebSocket stream ended unexpectedly mid-dictation, attempting reconnect");
+                        let (sink, source) = reconnect_with_backoff(config, sample_rate, &replay_buffer).await?;
+                        ws_sink = sink;
+                        ws_source = source;
                     }
                 }
             }
@@ -194,12 +313,355 @@ pub async fn run_streaming_session(
     }
 }
 
+/// Open the WebSocket connection, wait for `session.created`, and send the
+/// initial `session.update`. Used both for the first connection and for each
+/// reconnect attempt after a dropped connection.
+async fn connect_and_handshake(
+    config: &MistralRealtimeConfig,
+    sample_rate: u32,
+) -> Result<(WsSink, WsSource), Box<dyn std::error::Error + Send + Sync>> {
+    let base_url = if config.endpoint.is_empty() {
+        MistralRealtimeConfig::DEFAULT_ENDPOINT
+    } else {
+        &config.endpoint
+    };
+    let url = format!("{base_url}?model={}", config.model);
+
+    // Extract host from wss://host/... for the Host header
+    let host = url.split("://")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .unwrap_or("api.mistral.ai");
+
+    let request = http::Request::builder()
+        .uri(&url)
+        .header("Authorization", format!("Bearer {}", config.api_key))
+        .header("Host", host)
+        .header("Connection", "Upgrade")
+        .header("Upgrade", "websocket")
+        .header("Sec-WebSocket-Version", "13")
+        .header(
+            "Sec-WebSocket-Key",
+            tungstenite::handshake::client::generate_key(),
+        )
+        .body(())?;
+
+    let connector = build_tls_connector(config)?;
+    let (ws_stream, _response) =
+        tokio_tungstenite::connect_async_tls_with_config(request, None, false, connector).await?;
+    let (mut ws_sink, mut ws_source) = ws_stream.split();
+
+    tracing::info!("WebSocket connected to Mistral Realtime API");
+
+    // Wait for session.created
+    loop {
+        match ws_source.next().await {
+            Some(Ok(tungstenite::Message::Text(text))) => {
+                let msg: ServerMessage = serde_json::from_str(&text)?;
+                if msg.r#type == "session.created" {
+                    tracing::info!("Streaming session created");
+                    break;
+                }
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(e.into()),
+            None => return Err("WebSocket closed before session.created".into()),
+        }
+    }
+
+    // Send session.update with audio format
+    let session_update = SessionUpdate {
+        r#type: "session.update",
+        session: SessionConfig {
+            audio_format: AudioFormat {
+                encoding: "pcm_s16le",
+                sample_rate,
+            },
+            stability: stability_str(config.stability),
+            binary_audio: config.binary_audio,
+        },
+    };
+    let update_json = serde_json::to_string(&session_update)?;
+    ws_sink
+        .send(tungstenite::Message::Text(update_json.into()))
+        .await?;
+
+    Ok((ws_sink, ws_source))
+}
+
+/// Build a custom TLS connector for `config.tls_ca_path`/`config.tls_insecure`,
+/// or `None` to let `connect_async_tls_with_config` fall back to the default
+/// system trust store. Used for self-hosted, Mistral-compatible endpoints on
+/// a private or self-signed certificate.
+fn build_tls_connector(
+    config: &MistralRealtimeConfig,
+) -> Result<Option<tokio_tungstenite::Connector>, Box<dyn std::error::Error + Send + Sync>> {
+    if config.tls_ca_path.is_none() && !config.tls_insecure {
+        return Ok(None);
+    }
+
+    let mut builder = native_tls::TlsConnector::builder();
+
+    if let Some(ca_path) = &config.tls_ca_path {
+        let pem = std::fs::read(ca_path)
+            .map_err(|e| format!("Failed to read TLS CA at {ca_path:?}: {e}"))?;
+        builder.add_root_certificate(native_tls::Certificate::from_pem(&pem)?);
+    }
+
+    if config.tls_insecure {
+        tracing::warn!(
+            "tls_insecure is enabled — skipping TLS certificate verification for the realtime endpoint. \
+             Only use this against a trusted LAN/self-hosted endpoint."
+        );
+        builder.danger_accept_invalid_certs(true);
+        builder.danger_accept_invalid_hostnames(true);
+    }
+
+    Ok(Some(tokio_tungstenite::Connector::NativeTls(builder.build()?)))
+}
+
+/// Reconnect after a dropped connection: re-run the connect + handshake
+/// sequence with exponential backoff, then replay the buffered audio so the
+/// server catches back up to where the dropped connection left off. Gives up
+/// after `config.max_reconnect_attempts` failed attempts.
+async fn reconnect_with_backoff(
+    config: &MistralRealtimeConfig,
+    sample_rate: u32,
+    replay_buffer: &std::collections::VecDeque<i16>,
+) -> Result<(WsSink, WsSource), Box<dyn std::error::Error + Send + Sync>> {
+    let max_attempts = config.max_reconnect_attempts.max(1);
+    let mut last_err: Box<dyn std::error::Error + Send + Sync> = "reconnect attempts exhausted".into();
+
+    for attempt in 1..=max_attempts {
+        if attempt > 1 {
+            let delay = RECONNECT_BASE_DELAY
+                .saturating_mul(1u32 << (attempt - 2).min(6))
+                .min(RECONNECT_MAX_DELAY);
+            tracing::info!("Reconnect attempt {attempt}/{max_attempts} in {delay:?}");
+            tokio::time::sleep(delay).await;
+        }
+
+        match connect_and_handshake(config, sample_rate).await {
+            Ok((mut ws_sink, ws_source)) => {
+                if !replay_buffer.is_empty() {
+                    let samples: Vec<i16> = replay_buffer.iter().copied().collect();
+                    let message = build_audio_message(&samples, config.binary_audio)?;
+                    ws_sink.send(message).await?;
+                    tracing::info!("Replayed {} buffered samples after reconnect", samples.len());
+                }
+                tracing::info!("Reconnected after {attempt} attempt(s)");
+                return Ok((ws_sink, ws_source));
+            }
+            Err(e) => {
+                tracing::warn!("Reconnect attempt {attempt}/{max_attempts} failed: {e}");
+                last_err = e;
+            }
+        }
+    }
+
+    Err(last_err)
+}
+
 /// Encode i16 PCM samples as little-endian bytes then base64.
 fn encode_pcm_samples(samples: &[i16]) -> String {
     let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
     base64::engine::general_purpose::STANDARD.encode(&bytes)
 }
 
+type CaptureWriter = hound::WavWriter<std::io::BufWriter<std::fs::File>>;
+
+/// Open a WAV writer capturing the exact PCM stream sent to the realtime API
+/// for this session, named by session start time, under `dir`.
+fn create_capture_writer(
+    dir: &std::path::Path,
+    sample_rate: u32,
+) -> Result<CaptureWriter, Box<dyn std::error::Error + Send + Sync>> {
+    std::fs::create_dir_all(dir)?;
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let path = dir.join(format!("voxkey-streaming-{millis}.wav"));
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let writer = hound::WavWriter::create(&path, spec)?;
+    tracing::info!("Capturing streaming session audio to {}", path.display());
+    Ok(writer)
+}
+
+/// Finalize a capture writer, logging (without failing the session) if the
+/// WAV header can't be completed.
+fn finalize_capture(writer: Option<CaptureWriter>) {
+    if let Some(writer) = writer {
+        if let Err(e) = writer.finalize() {
+            tracing::warn!("Failed to finalize streaming capture WAV: {e}");
+        }
+    }
+}
+
+/// Build the WebSocket message carrying an audio chunk: a raw little-endian
+/// binary frame when `binary_audio` is negotiated, or the base64-in-JSON
+/// `input_audio.append` message otherwise.
+fn build_audio_message(
+    samples: &[i16],
+    binary_audio: bool,
+) -> Result<tungstenite::Message, serde_json::Error> {
+    if binary_audio {
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        Ok(tungstenite::Message::Binary(bytes.into()))
+    } else {
+        let encoded = encode_pcm_samples(samples);
+        let msg = AudioAppend {
+            r#type: "input_audio.append",
+            audio: &encoded,
+        };
+        Ok(tungstenite::Message::Text(serde_json::to_string(&msg)?.into()))
+    }
+}
+
+/// Record the delay between the most recently captured audio chunk and the
+/// moment its transcript was (or started to be) injected, surfacing it via
+/// `SharedState`/metrics and warning if it exceeds `warn_threshold`.
+pub(crate) async fn record_latency(
+    shared: &SharedState,
+    connection: &zbus::Connection,
+    latest_audio_at: Option<std::time::Instant>,
+    warn_threshold: std::time::Duration,
+) {
+    let Some(captured_at) = latest_audio_at else { return };
+    let latency = captured_at.elapsed();
+    shared.metrics().record_streaming_latency(latency);
+    shared.set_last_latency_ms(latency.as_millis() as u64);
+    DaemonInterface::notify_last_latency(connection).await;
+    if latency > warn_threshold {
+        tracing::warn!(
+            "Streaming injection latency {}ms exceeds threshold {}ms",
+            latency.as_millis(),
+            warn_threshold.as_millis(),
+        );
+    }
+}
+
+/// Inject text, returning a fatal error on a portal/desktop-session failure
+/// and logging (without failing the session) on a non-fatal local error.
+pub(crate) async fn inject_or_log(
+    desktop: &DesktopController,
+    keymap: &Option<LiveKeymap>,
+    compose: &Option<std::sync::Mutex<ComposeFallback>>,
+    text: &str,
+    typing_delay: std::time::Duration,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    match injector::inject_text(desktop, keymap, compose, text, typing_delay).await {
+        Ok(()) => Ok(()),
+        Err(injector::InjectionError::Portal(e)) => {
+            Err(format!("Portal error during streaming injection: {e}").into())
+        }
+        Err(injector::InjectionError::Local(e)) => {
+            tracing::error!("Failed to inject text: {e}");
+            Ok(())
+        }
+    }
+}
+
+/// Reconcile the on-screen volatile tail with a new stability-tagged update.
+/// Computes the longest common prefix between the previous tail and the new
+/// one, backspaces past the divergence point, and types the new suffix.
+/// Promotes `volatile` into `committed` once the server marks the text
+/// stable. Falls back to append-only (no backspacing, immediate commit) once
+/// the injection target proves it can't retract characters.
+pub(crate) async fn apply_transcript_update(
+    desktop: &DesktopController,
+    keymap: &Option<LiveKeymap>,
+    compose: &Option<std::sync::Mutex<ComposeFallback>>,
+    committed: &mut String,
+    volatile: &mut String,
+    full_text: &str,
+    is_stable: bool,
+    typing_delay: std::time::Duration,
+    backspace_supported: &mut bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let new_tail = full_text.strip_prefix(committed.as_str()).unwrap_or(full_text);
+
+    if !*backspace_supported {
+        if let Some(extra) = new_tail.strip_prefix(volatile.as_str()) {
+            if !extra.is_empty() {
+                inject_or_log(desktop, keymap, compose, extra, typing_delay).await?;
+            }
+        }
+        committed.push_str(new_tail);
+        volatile.clear();
+        return Ok(());
+    }
+
+    let old_chars: Vec<char> = volatile.chars().collect();
+    let new_chars: Vec<char> = new_tail.chars().collect();
+    let lcp = old_chars
+        .iter()
+        .zip(new_chars.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let to_delete = old_chars.len() - lcp;
+
+    if to_delete > 0 {
+        match injector::inject_backspaces(desktop, to_delete, typing_delay).await {
+            Ok(()) => {}
+            Err(injector::InjectionError::Portal(e)) => {
+                return Err(format!("Portal error during backspace correction: {e}").into());
+            }
+            Err(injector::InjectionError::Local(e)) => {
+                tracing::warn!(
+                    "Backspace unsupported by injection target, switching to append-only: {e}"
+                );
+                *backspace_supported = false;
+                // Can't trust what's left on screen after a partial backspace
+                // failure — treat the old tail as committed and let the next
+                // update's append-only path pick up from there.
+                committed.push_str(volatile);
+                volatile.clear();
+                return Ok(());
+            }
+        }
+    }
+
+    let suffix: String = new_chars[lcp..].iter().collect();
+    if !suffix.is_empty() {
+        inject_or_log(desktop, keymap, compose, &suffix, typing_delay).await?;
+    }
+    *volatile = new_tail.to_string();
+
+    if is_stable {
+        committed.push_str(volatile);
+        volatile.clear();
+    }
+
+    Ok(())
+}
+
+/// Map a stability knob to the value the realtime API expects.
+fn stability_str(level: StabilityLevel) -> &'static str {
+    match level {
+        StabilityLevel::Low => "low",
+        StabilityLevel::Medium => "medium",
+        StabilityLevel::High => "high",
+    }
+}
+
+/// Extra trailing items that must follow a stable item before it's trusted
+/// and injected, per stabilization aggressiveness. Zero at `Low` commits the
+/// instant the server marks an item stable (lowest latency); `High` waits for
+/// two further items to arrive first, trading latency for fewer corrections.
+fn confirmation_lag(level: StabilityLevel) -> usize {
+    match level {
+        StabilityLevel::Low => 0,
+        StabilityLevel::Medium => 1,
+        StabilityLevel::High => 2,
+    }
+}
+
 // -- Client -> Server message types --
 
 #[derive(serde::Serialize)]
@@ -211,6 +673,8 @@ struct SessionUpdate<'a> {
 #[derive(serde::Serialize)]
 struct SessionConfig<'a> {
     audio_format: AudioFormat<'a>,
+    stability: &'a str,
+    binary_audio: bool,
 }
 
 #[derive(serde::Serialize)]
@@ -227,11 +691,35 @@ struct AudioAppend<'a> {
 
 // -- Server -> Client message types --
 
+/// One word/token of a streaming transcript, as reported by partial results.
+/// `stable` means the provider no longer expects to revise this item.
+#[derive(serde::Deserialize, Debug, Clone)]
+struct TranscriptItem {
+    #[allow(dead_code)]
+    #[serde(default)]
+    start_time: f64,
+    #[allow(dead_code)]
+    #[serde(default)]
+    end_time: f64,
+    text: String,
+    #[serde(default)]
+    stable: bool,
+}
+
 #[derive(serde::Deserialize)]
 struct ServerMessage {
     r#type: String,
     #[serde(default)]
     text: Option<String>,
+    #[serde(default)]
+    items: Option<Vec<TranscriptItem>>,
+    /// Running full transcript text for a `transcription.text.delta` snapshot
+    /// update, as opposed to a flat incremental `text` delta.
+    #[serde(default)]
+    full_text: Option<String>,
+    /// Whether the server no longer expects to revise this update's text.
+    #[serde(default)]
+    stable: Option<bool>,
 }
 
 #[cfg(test)]
@@ -273,6 +761,8 @@ mod tests {
                     encoding: "pcm_s16le",
                     sample_rate: 16000,
                 },
+                stability: "medium",
+                binary_audio: false,
             },
         };
         let json = serde_json::to_string(&update).unwrap();
@@ -280,6 +770,48 @@ mod tests {
         assert_eq!(parsed["type"], "session.update");
         assert_eq!(parsed["session"]["audio_format"]["encoding"], "pcm_s16le");
         assert_eq!(parsed["session"]["audio_format"]["sample_rate"], 16000);
+        assert_eq!(parsed["session"]["stability"], "medium");
+        assert_eq!(parsed["session"]["binary_audio"], false);
+    }
+
+    #[test]
+    fn build_audio_message_binary_mode_sends_raw_le_bytes() {
+        let samples: Vec<i16> = vec![256, -1];
+        let message = build_audio_message(&samples, true).unwrap();
+        match message {
+            tungstenite::Message::Binary(bytes) => {
+                assert_eq!(bytes.as_ref(), &[0x00, 0x01, 0xFF, 0xFF]);
+            }
+            other => panic!("Expected a binary frame, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn build_audio_message_default_mode_sends_base64_json() {
+        let samples: Vec<i16> = vec![256];
+        let message = build_audio_message(&samples, false).unwrap();
+        match message {
+            tungstenite::Message::Text(text) => {
+                let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+                assert_eq!(parsed["type"], "input_audio.append");
+                assert_eq!(parsed["audio"], encode_pcm_samples(&samples));
+            }
+            other => panic!("Expected a text frame, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn stability_str_maps_all_levels() {
+        assert_eq!(stability_str(StabilityLevel::Low), "low");
+        assert_eq!(stability_str(StabilityLevel::Medium), "medium");
+        assert_eq!(stability_str(StabilityLevel::High), "high");
+    }
+
+    #[test]
+    fn confirmation_lag_increases_with_stability() {
+        assert_eq!(confirmation_lag(StabilityLevel::Low), 0);
+        assert_eq!(confirmation_lag(StabilityLevel::Medium), 1);
+        assert_eq!(confirmation_lag(StabilityLevel::High), 2);
     }
 
     #[test]
@@ -310,6 +842,20 @@ mod tests {
         assert!(msg.text.is_none());
     }
 
+    #[test]
+    fn server_message_deserializes_partial_items() {
+        let json = r#"{"type":"transcription.text.partial","items":[
+            {"start_time":0.0,"end_time":0.4,"text":"hello ","stable":true},
+            {"start_time":0.4,"end_time":0.9,"text":"wor","stable":false}
+        ]}"#;
+        let msg: ServerMessage = serde_json::from_str(json).unwrap();
+        let items = msg.items.unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items[0].stable);
+        assert!(!items[1].stable);
+        assert_eq!(items[0].text, "hello ");
+    }
+
     #[test]
     fn server_message_deserializes_transcription_done() {
         let json = r#"{"type":"transcription.done"}"#;
@@ -317,6 +863,22 @@ mod tests {
         assert_eq!(msg.r#type, "transcription.done");
     }
 
+    #[test]
+    fn server_message_deserializes_delta_with_full_text_and_stable() {
+        let json = r#"{"type":"transcription.text.delta","full_text":"hello wor","stable":false}"#;
+        let msg: ServerMessage = serde_json::from_str(json).unwrap();
+        assert_eq!(msg.full_text.as_deref(), Some("hello wor"));
+        assert_eq!(msg.stable, Some(false));
+    }
+
+    #[test]
+    fn server_message_deserializes_legacy_delta_without_full_text() {
+        let json = r#"{"type":"transcription.text.delta","text":"hello "}"#;
+        let msg: ServerMessage = serde_json::from_str(json).unwrap();
+        assert!(msg.full_text.is_none());
+        assert!(msg.stable.is_none());
+    }
+
     #[test]
     fn server_message_deserializes_error() {
         let json = r#"{"type":"error","text":"invalid audio format"}"#;
@@ -0,0 +1,113 @@
+// ABOUTME: Commits transcript text directly via the Wayland zwp_text_input_v3 protocol.
+// ABOUTME: Bypasses the clipboard and synthetic keysyms for apps that intercept both.
+
+use std::sync::Mutex;
+
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::wl_registry::WlRegistry;
+use wayland_client::protocol::wl_seat::WlSeat;
+use wayland_client::{delegate_noop, Connection, Dispatch, EventQueue, QueueHandle};
+use wayland_protocols_misc::zwp_text_input_v3::client::zwp_text_input_manager_v3::ZwpTextInputManagerV3;
+use wayland_protocols_misc::zwp_text_input_v3::client::zwp_text_input_v3::{self, ZwpTextInputV3};
+
+type DynError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Tracks whether the compositor has handed the focused surface's input
+/// method to us (`enter`) or taken it away (`leave`), per the
+/// `zwp_text_input_v3` activation model.
+#[derive(Default)]
+struct TextInputState {
+    active: bool,
+}
+
+/// Commits transcript text straight into the focused input field over
+/// `zwp_text_input_v3`, bypassing the clipboard and synthetic keysyms
+/// entirely — correct insertion into IME-aware apps that intercept Ctrl+V or
+/// don't honor raw keysyms. Preferred by [`crate::injector::Injector`] over
+/// clipboard paste/keystroke injection whenever the compositor advertises
+/// the protocol and has activated an input method for the focused surface.
+pub struct TextInputController {
+    text_input: ZwpTextInputV3,
+    queue: Mutex<EventQueue<TextInputState>>,
+    state: Mutex<TextInputState>,
+}
+
+impl TextInputController {
+    /// Connect to the Wayland display and bind `zwp_text_input_manager_v3`.
+    /// Returns `Ok(None)` if the compositor doesn't advertise it, so callers
+    /// fall back to clipboard paste or synthetic keystrokes.
+    pub fn new() -> Result<Option<Self>, DynError> {
+        let conn = Connection::connect_to_env()?;
+        let (globals, mut queue) = registry_queue_init::<TextInputState>(&conn)?;
+        let qh = queue.handle();
+
+        let Ok(manager) = globals.bind::<ZwpTextInputManagerV3, _, _>(&qh, 1..=1, ()) else {
+            return Ok(None);
+        };
+        let Ok(seat) = globals.bind::<WlSeat, _, _>(&qh, 1..=9, ()) else {
+            return Ok(None);
+        };
+
+        let text_input = manager.get_text_input(&seat, &qh, ());
+        let mut state = TextInputState::default();
+        queue.roundtrip(&mut state)?;
+
+        Ok(Some(Self {
+            text_input,
+            queue: Mutex::new(queue),
+            state: Mutex::new(state),
+        }))
+    }
+
+    /// Commit `text` into the currently focused input field. Returns
+    /// `Ok(false)` (caller should fall back) if the compositor hasn't
+    /// activated an input method for the focused surface — e.g. a terminal
+    /// with no text-input support, or nothing focused yet.
+    pub fn commit_text(&self, text: &str) -> Result<bool, DynError> {
+        let mut queue = self.queue.lock().unwrap();
+        let mut state = self.state.lock().unwrap();
+        queue.dispatch_pending(&mut state)?;
+        if !state.active {
+            return Ok(false);
+        }
+
+        self.text_input.enable();
+        self.text_input.commit_string(text.to_string());
+        self.text_input.commit();
+        queue.roundtrip(&mut state)?;
+
+        Ok(true)
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for TextInputState {
+    fn event(
+        _state: &mut Self,
+        _registry: &WlRegistry,
+        _event: wayland_client::protocol::wl_registry::Event,
+        _data: &GlobalListContents,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpTextInputV3, ()> for TextInputState {
+    fn event(
+        state: &mut Self,
+        _text_input: &ZwpTextInputV3,
+        event: zwp_text_input_v3::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwp_text_input_v3::Event::Enter { .. } => state.active = true,
+            zwp_text_input_v3::Event::Leave { .. } => state.active = false,
+            _ => {}
+        }
+    }
+}
+
+delegate_noop!(TextInputState: ignore WlSeat);
+delegate_noop!(TextInputState: ignore ZwpTextInputManagerV3);
@@ -3,10 +3,13 @@
 
 use std::path::Path;
 use std::process::Stdio;
+use std::sync::{Arc, Mutex};
 use tokio::process::Command;
 
 use voxkey_ipc::{TranscriberConfig, TranscriberProvider};
 
+use crate::whisper_candle::CandleWhisperModel;
+
 /// Transcription backend selected by provider configuration.
 pub enum Transcriber {
     WhisperCpp {
@@ -27,16 +30,62 @@ pub enum Transcriber {
         model_name: String,
         execution_provider: voxkey_ipc::ExecutionProviderChoice,
     },
+    ParakeetStreaming {
+        model_name: String,
+        execution_provider: voxkey_ipc::ExecutionProviderChoice,
+    },
+    OpenAiCompatible {
+        client: reqwest::Client,
+        endpoint: String,
+        api_key: String,
+        model: String,
+    },
+    WhisperCandle {
+        model_path: String,
+        cache: Arc<Mutex<Option<CandleWhisperModel>>>,
+    },
 }
 
 impl Transcriber {
     /// Whether this transcriber uses the streaming (real-time) flow rather than batch.
     pub fn is_streaming(&self) -> bool {
-        matches!(self, Self::MistralRealtime { .. })
+        matches!(self, Self::MistralRealtime { .. } | Self::ParakeetStreaming { .. })
+    }
+
+    /// Short, stable identifier for the active provider, recorded in the
+    /// transcript history (see `crate::persistence::HistoryEntry`).
+    pub fn engine_label(&self) -> &'static str {
+        match self {
+            Self::WhisperCpp { .. } => "whisper-cpp",
+            Self::Mistral { .. } => "mistral",
+            Self::MistralRealtime { .. } => "mistral-realtime",
+            Self::Parakeet { .. } => "parakeet",
+            Self::ParakeetStreaming { .. } => "parakeet-streaming",
+            Self::OpenAiCompatible { .. } => "openai-compatible",
+            Self::WhisperCandle { .. } => "whisper-candle",
+        }
+    }
+
+    /// The execution provider Parakeet actually resolved to (after `Auto` was
+    /// resolved to a concrete choice in `from_config`), for reporting back to
+    /// the settings GUI. `None` for non-Parakeet providers.
+    pub fn resolved_execution_provider(&self) -> Option<voxkey_ipc::ExecutionProviderChoice> {
+        match self {
+            Self::Parakeet { execution_provider, .. }
+            | Self::ParakeetStreaming { execution_provider, .. } => Some(*execution_provider),
+            _ => None,
+        }
     }
 
     pub fn from_config(config: &TranscriberConfig) -> Self {
-        match config.provider {
+        Self::from_provider(config.provider, config)
+    }
+
+    /// Build a transcriber for a specific provider, pulling that provider's
+    /// settings out of `config`. Used both for the primary provider
+    /// (`from_config`) and to construct fallback-chain entries on demand.
+    pub fn from_provider(provider: TranscriberProvider, config: &TranscriberConfig) -> Self {
+        match provider {
             TranscriberProvider::WhisperCpp => Self::WhisperCpp {
                 command: config.whisper_cpp.command.clone(),
                 args: config.whisper_cpp.args.clone(),
@@ -53,7 +102,21 @@ impl Transcriber {
             },
             TranscriberProvider::Parakeet => Self::Parakeet {
                 model_name: config.parakeet.model.clone(),
-                execution_provider: config.parakeet.execution_provider,
+                execution_provider: resolve_execution_provider(config.parakeet.execution_provider),
+            },
+            TranscriberProvider::ParakeetStreaming => Self::ParakeetStreaming {
+                model_name: config.parakeet.model.clone(),
+                execution_provider: resolve_execution_provider(config.parakeet.execution_provider),
+            },
+            TranscriberProvider::OpenAiCompatible => Self::OpenAiCompatible {
+                client: reqwest::Client::new(),
+                endpoint: config.openai_compatible.endpoint.clone(),
+                api_key: config.openai_compatible.api_key.clone(),
+                model: config.openai_compatible.model.clone(),
+            },
+            TranscriberProvider::WhisperCandle => Self::WhisperCandle {
+                model_path: config.whisper_candle.model_path.clone(),
+                cache: Arc::new(Mutex::new(None)),
             },
         }
     }
@@ -64,7 +127,78 @@ impl Transcriber {
         &self,
         audio_path: &Path,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        let result = match self {
+        let result = self.transcribe_once(audio_path).await;
+
+        // Clean up the temp audio file regardless of outcome
+        if let Err(e) = tokio::fs::remove_file(audio_path).await {
+            tracing::warn!("Failed to remove temp audio file: {e}");
+        }
+
+        result
+    }
+
+    /// Try `self` first, then each provider in `fallback` in order, using the
+    /// settings each carries in `config`. Streaming-only providers are
+    /// skipped since this flow only supports batch (file-based) transcription.
+    /// Returns the transcript alongside the provider that actually produced
+    /// it, so the caller can tell the user when a fallback kicked in. The
+    /// temp audio file is removed exactly once, after the chain settles.
+    pub async fn transcribe_with_fallback(
+        &self,
+        fallback: &[TranscriberProvider],
+        config: &TranscriberConfig,
+        audio_path: &Path,
+    ) -> Result<(String, &'static str), Box<dyn std::error::Error + Send + Sync>> {
+        let mut last_err: Option<Box<dyn std::error::Error + Send + Sync>> = None;
+
+        let result = 'chain: {
+            match self.transcribe_once(audio_path).await {
+                Ok(transcript) => break 'chain Ok((transcript, self.engine_label())),
+                Err(e) => {
+                    tracing::warn!("{} failed, trying fallback: {e}", self.engine_label());
+                    last_err = Some(e);
+                }
+            }
+
+            for &provider in fallback {
+                if provider == config.provider {
+                    continue; // already tried as the primary above
+                }
+                let transcriber = Self::from_provider(provider, config);
+                if transcriber.is_streaming() {
+                    tracing::warn!(
+                        "Skipping {} in fallback chain: streaming providers aren't usable from the batch transcribe flow",
+                        transcriber.engine_label()
+                    );
+                    continue;
+                }
+                match transcriber.transcribe_once(audio_path).await {
+                    Ok(transcript) => break 'chain Ok((transcript, transcriber.engine_label())),
+                    Err(e) => {
+                        tracing::warn!("{} failed, trying next fallback: {e}", transcriber.engine_label());
+                        last_err = Some(e);
+                    }
+                }
+            }
+
+            Err(last_err.unwrap_or_else(|| "No transcriber providers configured".into()))
+        };
+
+        if let Err(e) = tokio::fs::remove_file(audio_path).await {
+            tracing::warn!("Failed to remove temp audio file: {e}");
+        }
+
+        result
+    }
+
+    /// The per-provider transcription dispatch, without temp-file cleanup
+    /// (shared by `transcribe` and `transcribe_with_fallback`, which each
+    /// clean up once the whole attempt — including any fallbacks — settles).
+    async fn transcribe_once(
+        &self,
+        audio_path: &Path,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        match self {
             Self::WhisperCpp { command, args } => {
                 transcribe_whisper_cpp(command, args, audio_path).await
             }
@@ -80,14 +214,19 @@ impl Transcriber {
             Self::Parakeet { model_name, execution_provider } => {
                 transcribe_parakeet(model_name, *execution_provider, audio_path).await
             }
-        };
-
-        // Clean up the temp audio file regardless of outcome
-        if let Err(e) = tokio::fs::remove_file(audio_path).await {
-            tracing::warn!("Failed to remove temp audio file: {e}");
+            Self::ParakeetStreaming { .. } => {
+                unreachable!("streaming transcriber uses run_streaming_session, not transcribe()")
+            }
+            Self::OpenAiCompatible {
+                client,
+                endpoint,
+                api_key,
+                model,
+            } => transcribe_openai_compatible(client, endpoint, api_key, model, audio_path).await,
+            Self::WhisperCandle { model_path, cache } => {
+                transcribe_whisper_candle(model_path, cache, audio_path).await
+            }
         }
-
-        result
     }
 }
 
@@ -185,6 +324,69 @@ async fn transcribe_mistral(
     Ok(transcript)
 }
 
+/// Response shape shared by OpenAI's audio transcription API and most
+/// compatible servers (whisper.cpp server, LocalAI, vLLM, ...).
+#[derive(serde::Deserialize)]
+struct OpenAiTranscriptionResponse {
+    text: String,
+}
+
+async fn transcribe_openai_compatible(
+    client: &reqwest::Client,
+    endpoint: &str,
+    api_key: &str,
+    model: &str,
+    audio_path: &Path,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let url = if endpoint.is_empty() {
+        voxkey_ipc::OpenAiCompatibleConfig::DEFAULT_ENDPOINT
+    } else {
+        endpoint
+    };
+    tracing::info!("Sending audio to OpenAI-compatible endpoint: {url}");
+
+    let file_bytes = tokio::fs::read(audio_path).await?;
+    let file_name = audio_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "audio.wav".to_string());
+
+    let file_part = reqwest::multipart::Part::bytes(file_bytes)
+        .file_name(file_name)
+        .mime_str("audio/wav")?;
+
+    let mut form = reqwest::multipart::Form::new().part("file", file_part);
+    if !model.is_empty() {
+        form = form.text("model", model.to_string());
+    }
+
+    let mut request = client.post(url).multipart(form);
+    if !api_key.is_empty() {
+        request = request.header("Authorization", format!("Bearer {api_key}"));
+    }
+
+    let response = request.send().await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("OpenAI-compatible API error ({status}): {body}").into());
+    }
+
+    let parsed: OpenAiTranscriptionResponse = response.json().await?;
+    let transcript = parsed.text.trim().to_string();
+    tracing::info!("Transcription complete ({} chars)", transcript.len());
+    Ok(transcript)
+}
+
+/// Resolve `Auto` to the best execution provider actually available on this
+/// machine; passes an explicit choice through unchanged.
+fn resolve_execution_provider(
+    choice: voxkey_ipc::ExecutionProviderChoice,
+) -> voxkey_ipc::ExecutionProviderChoice {
+    crate::execution_providers::resolve(choice, &crate::execution_providers::available_providers())
+}
+
 async fn transcribe_parakeet(
     model_name: &str,
     execution_provider: voxkey_ipc::ExecutionProviderChoice,
@@ -228,11 +430,7 @@ async fn transcribe_parakeet(
             }
         };
 
-        let provider = match ep {
-            voxkey_ipc::ExecutionProviderChoice::Cuda => Some("cuda".to_string()),
-            voxkey_ipc::ExecutionProviderChoice::Cpu => Some("cpu".to_string()),
-            voxkey_ipc::ExecutionProviderChoice::Auto => None,
-        };
+        let provider = ep.onnx_provider_name().map(str::to_string);
 
         let config = sherpa_rs::transducer::TransducerConfig {
             encoder: format!("{model_dir_str}/encoder.int8.onnx"),
@@ -260,6 +458,57 @@ async fn transcribe_parakeet(
     Ok(transcript)
 }
 
+async fn transcribe_whisper_candle(
+    model_path: &str,
+    cache: &Arc<Mutex<Option<CandleWhisperModel>>>,
+    audio_path: &Path,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    if model_path.is_empty() {
+        return Err("No whisper-candle model path configured. Set one in Settings.".into());
+    }
+
+    tracing::info!("Whisper-candle transcription: model_path={model_path}, path={}", audio_path.display());
+
+    let model_path = model_path.to_string();
+    let cache = cache.clone();
+    let audio_path = audio_path.to_path_buf();
+
+    // Model loading and inference are CPU-bound; run in a blocking thread to
+    // avoid starving the tokio runtime.
+    let transcript = tokio::task::spawn_blocking(move || -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut guard = cache.lock().unwrap();
+        if guard.is_none() {
+            tracing::info!("Loading whisper-candle model from {model_path}");
+            *guard = Some(CandleWhisperModel::load(&model_path)?);
+        }
+        let model = guard.as_mut().unwrap();
+
+        let mut reader = hound::WavReader::open(&audio_path)?;
+        let spec = reader.spec();
+        tracing::info!(
+            "WAV: {}Hz, {} channels, {} bits, {:?}",
+            spec.sample_rate, spec.channels, spec.bits_per_sample, spec.sample_format
+        );
+        let samples: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Int => {
+                let max_val = (1 << (spec.bits_per_sample - 1)) as f32;
+                reader.samples::<i32>()
+                    .map(|s| s.map(|v| v as f32 / max_val))
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            hound::SampleFormat::Float => {
+                reader.samples::<f32>()
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+        };
+
+        model.transcribe(&samples)
+    }).await??;
+
+    tracing::info!("Whisper-candle transcription complete ({} chars)", transcript.len());
+    Ok(transcript)
+}
+
 #[cfg(test)]
 fn parse_mistral_response(
     json: &str,
@@ -271,7 +520,10 @@ fn parse_mistral_response(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use voxkey_ipc::{MistralConfig, MistralRealtimeConfig, ParakeetConfig, WhisperCppConfig};
+    use voxkey_ipc::{
+        MistralConfig, MistralRealtimeConfig, OpenAiCompatibleConfig, ParakeetConfig,
+        WhisperCandleConfig, WhisperCppConfig,
+    };
 
     #[test]
     fn from_config_creates_whisper_cpp_variant() {
@@ -284,6 +536,9 @@ mod tests {
             mistral: MistralConfig::default(),
             mistral_realtime: MistralRealtimeConfig::default(),
             parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         };
         let t = Transcriber::from_config(&config);
         match t {
@@ -307,6 +562,9 @@ mod tests {
             },
             mistral_realtime: MistralRealtimeConfig::default(),
             parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         };
         let t = Transcriber::from_config(&config);
         match t {
@@ -330,8 +588,16 @@ mod tests {
                 api_key: "sk-rt".to_string(),
                 model: "voxtral-mini-transcribe-realtime-2602".to_string(),
                 endpoint: String::new(),
+                stability: voxkey_ipc::StabilityLevel::default(),
+                max_reconnect_attempts: 5,
+                binary_audio: false,
+                tls_ca_path: None,
+                tls_insecure: false,
             },
             parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         };
         let t = Transcriber::from_config(&config);
         match t {
@@ -343,6 +609,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn from_provider_builds_a_non_primary_provider_for_fallback() {
+        let config = TranscriberConfig {
+            provider: TranscriberProvider::WhisperCpp,
+            whisper_cpp: WhisperCppConfig::default(),
+            mistral: MistralConfig {
+                api_key: "sk-fallback".to_string(),
+                model: "voxtral-mini-2507".to_string(),
+                endpoint: String::new(),
+            },
+            mistral_realtime: MistralRealtimeConfig::default(),
+            parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: vec![TranscriberProvider::Mistral],
+        };
+        let t = Transcriber::from_provider(TranscriberProvider::Mistral, &config);
+        match t {
+            Transcriber::Mistral { api_key, model, .. } => {
+                assert_eq!(api_key, "sk-fallback");
+                assert_eq!(model, "voxtral-mini-2507");
+            }
+            _ => panic!("Expected Mistral variant"),
+        }
+    }
+
     #[test]
     fn is_streaming_returns_true_for_mistral_realtime() {
         let t = Transcriber::MistralRealtime {
@@ -352,6 +644,15 @@ mod tests {
         assert!(t.is_streaming());
     }
 
+    #[test]
+    fn is_streaming_returns_true_for_parakeet_streaming() {
+        let t = Transcriber::ParakeetStreaming {
+            model_name: String::new(),
+            execution_provider: voxkey_ipc::ExecutionProviderChoice::Auto,
+        };
+        assert!(t.is_streaming());
+    }
+
     #[test]
     fn is_streaming_returns_false_for_batch_providers() {
         let whisper = Transcriber::WhisperCpp {
@@ -380,11 +681,89 @@ mod tests {
                 model: "parakeet-tdt-0.6b-v3".to_string(),
                 execution_provider: voxkey_ipc::ExecutionProviderChoice::Cpu,
             },
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         };
         let t = Transcriber::from_config(&config);
         assert!(!t.is_streaming());
     }
 
+    #[test]
+    fn from_config_creates_parakeet_streaming_variant() {
+        let config = TranscriberConfig {
+            provider: TranscriberProvider::ParakeetStreaming,
+            whisper_cpp: WhisperCppConfig::default(),
+            mistral: MistralConfig::default(),
+            mistral_realtime: MistralRealtimeConfig::default(),
+            parakeet: voxkey_ipc::ParakeetConfig {
+                model: "parakeet-tdt-0.6b-v3".to_string(),
+                execution_provider: voxkey_ipc::ExecutionProviderChoice::Cpu,
+            },
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
+        };
+        let t = Transcriber::from_config(&config);
+        assert!(t.is_streaming());
+    }
+
+    #[test]
+    fn from_config_creates_openai_compatible_variant() {
+        let config = TranscriberConfig {
+            provider: TranscriberProvider::OpenAiCompatible,
+            whisper_cpp: WhisperCppConfig::default(),
+            mistral: MistralConfig::default(),
+            mistral_realtime: MistralRealtimeConfig::default(),
+            parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig {
+                endpoint: "http://localhost:9000/v1/audio/transcriptions".to_string(),
+                api_key: String::new(),
+                model: "whisper-1".to_string(),
+            },
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
+        };
+        let t = Transcriber::from_config(&config);
+        match t {
+            Transcriber::OpenAiCompatible { endpoint, model, .. } => {
+                assert_eq!(endpoint, "http://localhost:9000/v1/audio/transcriptions");
+                assert_eq!(model, "whisper-1");
+            }
+            _ => panic!("Expected OpenAiCompatible variant"),
+        }
+    }
+
+    #[test]
+    fn from_config_creates_whisper_candle_variant() {
+        let config = TranscriberConfig {
+            provider: TranscriberProvider::WhisperCandle,
+            whisper_cpp: WhisperCppConfig::default(),
+            mistral: MistralConfig::default(),
+            mistral_realtime: MistralRealtimeConfig::default(),
+            parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig {
+                model_path: "/opt/whisper-models/base".to_string(),
+            },
+            fallback: Vec::new(),
+        };
+        let t = Transcriber::from_config(&config);
+        match t {
+            Transcriber::WhisperCandle { model_path, .. } => {
+                assert_eq!(model_path, "/opt/whisper-models/base");
+            }
+            _ => panic!("Expected WhisperCandle variant"),
+        }
+    }
+
+    #[test]
+    fn parse_openai_compatible_response_extracts_text() {
+        let json = r#"{"text": "hello from local whisper"}"#;
+        let parsed: OpenAiTranscriptionResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.text, "hello from local whisper");
+    }
+
     #[test]
     fn parse_mistral_response_extracts_text() {
         let json = r#"{"text": " Hello, world! "}"#;
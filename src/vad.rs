@@ -0,0 +1,371 @@
+// ABOUTME: FFT-based voice-activity detection used to auto-stop recording when the speaker pauses.
+// ABOUTME: Classifies short PCM frames as speech or silence via speech-band energy ratio, spectral flatness, and zero-crossing rate.
+
+use std::sync::Arc;
+
+use realfft::{num_complex::Complex32, RealFftPlanner, RealToComplex};
+
+use voxkey_ipc::VadConfig;
+
+/// Frame length for short-time analysis, in milliseconds.
+const FRAME_MS: u32 = 25;
+
+/// Frequency band containing most speech energy, in Hz. Used to compute the
+/// speech-band-to-total energy ratio that anchors the speech/silence call.
+const SPEECH_BAND_HZ: (f32, f32) = (300.0, 3400.0);
+
+/// Number of leading frames (~250ms at the default frame length) used to
+/// seed the initial noise-floor estimate, assumed to be silence.
+const NOISE_FLOOR_FRAMES: usize = 10;
+
+/// Detects end-of-speech from a stream of PCM samples using short-time
+/// spectral analysis. Callers feed arbitrarily sized chunks (e.g. raw cpal
+/// callback buffers); frames are accumulated and classified internally.
+pub struct VoiceActivityDetector {
+    frame_len: usize,
+    sample_rate: u32,
+    sensitivity: f32,
+    silence_hangover_frames: u32,
+
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+
+    buffer: Vec<i16>,
+    noise_floor: f32,
+    frames_seen: usize,
+    consecutive_silence: u32,
+    speech_started: bool,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(sample_rate: u32, config: &VadConfig) -> Self {
+        let frame_len = ((sample_rate * FRAME_MS) / 1000).max(2) as usize;
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(frame_len);
+        let silence_hangover_frames = (config.silence_timeout_ms / FRAME_MS).max(1);
+
+        Self {
+            frame_len,
+            sample_rate,
+            sensitivity: config.sensitivity.clamp(0.0, 1.0),
+            silence_hangover_frames,
+            fft,
+            window: hann_window(frame_len),
+            buffer: Vec::with_capacity(frame_len * 2),
+            noise_floor: 0.0,
+            frames_seen: 0,
+            consecutive_silence: 0,
+            speech_started: false,
+        }
+    }
+
+    /// Feed newly captured samples. Returns true the moment end-of-speech is
+    /// declared: speech was seen, then `silence_timeout_ms` passed with no
+    /// frame classified as speech. Not latched: further silent frames keep
+    /// returning true, so callers should stop pushing samples once it does.
+    pub fn push_samples(&mut self, samples: &[i16]) -> bool {
+        self.buffer.extend_from_slice(samples);
+
+        let mut endpointed = false;
+        while self.buffer.len() >= self.frame_len {
+            let frame: Vec<i16> = self.buffer.drain(..self.frame_len).collect();
+            if self.classify_frame(&frame) {
+                endpointed = true;
+            }
+        }
+        endpointed
+    }
+
+    fn classify_frame(&mut self, frame: &[i16]) -> bool {
+        let energy = frame_energy(frame);
+
+        self.frames_seen += 1;
+        if self.frames_seen <= NOISE_FLOOR_FRAMES {
+            self.noise_floor += (energy - self.noise_floor) / self.frames_seen as f32;
+            return false;
+        }
+
+        let spectrum = self.magnitude_spectrum(frame);
+        let flatness = spectral_flatness(&spectrum);
+        let zcr = zero_crossing_rate(frame);
+        let band_ratio = speech_band_ratio(&spectrum, self.frame_len, self.sample_rate);
+
+        // Higher sensitivity lowers the bar above the noise floor needed to count as speech.
+        let threshold_db = 6.0 + (1.0 - self.sensitivity) * 12.0;
+        let above_floor = energy > self.noise_floor * db_to_linear(threshold_db);
+        let is_speech = above_floor && flatness < 0.5 && zcr < 0.35 && band_ratio > 0.5;
+
+        if is_speech {
+            self.speech_started = true;
+            self.consecutive_silence = 0;
+        } else {
+            // Slowly track ambient noise so the floor adapts to a changing room.
+            self.noise_floor = self.noise_floor * 0.98 + energy * 0.02;
+            if self.speech_started {
+                self.consecutive_silence += 1;
+            }
+        }
+
+        self.speech_started && self.consecutive_silence >= self.silence_hangover_frames
+    }
+
+    fn magnitude_spectrum(&self, frame: &[i16]) -> Vec<f32> {
+        let mut input: Vec<f32> = frame
+            .iter()
+            .zip(&self.window)
+            .map(|(&sample, &w)| sample as f32 * w)
+            .collect();
+        let mut output = self.fft.make_output_vec();
+        let _ = self.fft.process(&mut input, &mut output);
+        output.iter().map(Complex32::norm).collect()
+    }
+}
+
+/// Trim leading/trailing silence from a full recording, returning the sample
+/// range `[start, end)` that should be kept. Falls back to the whole clip if
+/// no speech is detected.
+pub fn trim_silence(samples: &[i16], sample_rate: u32, config: &VadConfig) -> (usize, usize) {
+    let frame_len = ((sample_rate * FRAME_MS) / 1000).max(2) as usize;
+    if samples.is_empty() || frame_len == 0 {
+        return (0, samples.len());
+    }
+
+    let mut detector = VoiceActivityDetector::new(sample_rate, config);
+    let mut first_speech_frame = None;
+    let mut last_speech_frame = None;
+
+    for (frame_index, frame) in samples.chunks(frame_len).enumerate() {
+        let was_silent = !detector.speech_started;
+        detector.push_samples(frame);
+        let became_speech = detector.speech_started && (was_silent || detector.consecutive_silence == 0);
+        if became_speech {
+            first_speech_frame.get_or_insert(frame_index);
+            last_speech_frame = Some(frame_index);
+        }
+    }
+
+    match (first_speech_frame, last_speech_frame) {
+        (Some(first), Some(last)) => {
+            let start = first * frame_len;
+            let end = ((last + 1) * frame_len).min(samples.len());
+            (start, end)
+        }
+        _ => (0, samples.len()),
+    }
+}
+
+fn frame_energy(frame: &[i16]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f64 = frame.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    ((sum_sq / frame.len() as f64).sqrt()) as f32
+}
+
+fn zero_crossing_rate(frame: &[i16]) -> f32 {
+    if frame.len() < 2 {
+        return 0.0;
+    }
+    let crossings = frame
+        .windows(2)
+        .filter(|pair| (pair[0] >= 0) != (pair[1] >= 0))
+        .count();
+    crossings as f32 / (frame.len() - 1) as f32
+}
+
+/// Spectral flatness: geometric mean over arithmetic mean of the magnitude
+/// spectrum. Near 1.0 for noise-like signals, lower for tonal speech.
+fn spectral_flatness(spectrum: &[f32]) -> f32 {
+    let bins: Vec<f32> = spectrum.iter().copied().filter(|&m| m > 1e-6).collect();
+    if bins.is_empty() {
+        return 1.0;
+    }
+    let log_sum: f32 = bins.iter().map(|m| m.ln()).sum();
+    let geometric_mean = (log_sum / bins.len() as f32).exp();
+    let arithmetic_mean = bins.iter().sum::<f32>() / bins.len() as f32;
+    if arithmetic_mean <= 0.0 {
+        1.0
+    } else {
+        geometric_mean / arithmetic_mean
+    }
+}
+
+/// Fraction of total magnitude-spectrum energy that falls within the speech
+/// band (`SPEECH_BAND_HZ`). Broadband noise spreads energy evenly across the
+/// spectrum and scores low here, while voiced speech concentrates it.
+fn speech_band_ratio(spectrum: &[f32], frame_len: usize, sample_rate: u32) -> f32 {
+    let total: f32 = spectrum.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    let hz_per_bin = sample_rate as f32 / frame_len as f32;
+    let (low_hz, high_hz) = SPEECH_BAND_HZ;
+    let band_energy: f32 = spectrum
+        .iter()
+        .enumerate()
+        .filter(|(bin, _)| {
+            let hz = *bin as f32 * hz_per_bin;
+            hz >= low_hz && hz <= high_hz
+        })
+        .map(|(_, &m)| m)
+        .sum();
+    band_energy / total
+}
+
+fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+fn hann_window(len: usize) -> Vec<f32> {
+    if len <= 1 {
+        return vec![1.0; len];
+    }
+    (0..len)
+        .map(|n| {
+            0.5 * (1.0 - (2.0 * std::f32::consts::PI * n as f32 / (len - 1) as f32).cos())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn silence(len: usize) -> Vec<i16> {
+        vec![0; len]
+    }
+
+    fn tone(len: usize, sample_rate: u32, freq: f32, amplitude: i16) -> Vec<i16> {
+        (0..len)
+            .map(|n| {
+                let t = n as f32 / sample_rate as f32;
+                (amplitude as f32 * (2.0 * std::f32::consts::PI * freq * t).sin()) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn frame_energy_is_zero_for_silence() {
+        assert_eq!(frame_energy(&silence(400)), 0.0);
+    }
+
+    #[test]
+    fn frame_energy_increases_with_amplitude() {
+        let quiet = tone(400, 16000, 200.0, 1000);
+        let loud = tone(400, 16000, 200.0, 20000);
+        assert!(frame_energy(&loud) > frame_energy(&quiet));
+    }
+
+    #[test]
+    fn zero_crossing_rate_is_zero_for_constant_signal() {
+        let frame = vec![100i16; 64];
+        assert_eq!(zero_crossing_rate(&frame), 0.0);
+    }
+
+    #[test]
+    fn zero_crossing_rate_is_high_for_alternating_signal() {
+        let frame: Vec<i16> = (0..64).map(|n| if n % 2 == 0 { 100 } else { -100 }).collect();
+        assert!(zero_crossing_rate(&frame) > 0.9);
+    }
+
+    #[test]
+    fn speech_band_ratio_is_high_for_tone_inside_band() {
+        let sample_rate = 16000;
+        let frame_len = ((sample_rate * FRAME_MS) / 1000) as usize;
+        let frame = tone(frame_len, sample_rate, 1000.0, 20000);
+        let spectrum: Vec<f32> = {
+            let fft = RealFftPlanner::<f32>::new().plan_fft_forward(frame_len);
+            let mut input: Vec<f32> = frame.iter().map(|&s| s as f32).collect();
+            let mut output = fft.make_output_vec();
+            let _ = fft.process(&mut input, &mut output);
+            output.iter().map(Complex32::norm).collect()
+        };
+        assert!(speech_band_ratio(&spectrum, frame_len, sample_rate) > 0.9);
+    }
+
+    #[test]
+    fn speech_band_ratio_is_low_for_tone_outside_band() {
+        let sample_rate = 16000;
+        let frame_len = ((sample_rate * FRAME_MS) / 1000) as usize;
+        let frame = tone(frame_len, sample_rate, 80.0, 20000);
+        let spectrum: Vec<f32> = {
+            let fft = RealFftPlanner::<f32>::new().plan_fft_forward(frame_len);
+            let mut input: Vec<f32> = frame.iter().map(|&s| s as f32).collect();
+            let mut output = fft.make_output_vec();
+            let _ = fft.process(&mut input, &mut output);
+            output.iter().map(Complex32::norm).collect()
+        };
+        assert!(speech_band_ratio(&spectrum, frame_len, sample_rate) < 0.1);
+    }
+
+    #[test]
+    fn hann_window_tapers_to_zero_at_edges() {
+        let window = hann_window(512);
+        assert!(window[0] < 0.01);
+        assert!(window[window.len() - 1] < 0.01);
+        assert!(window[window.len() / 2] > 0.9);
+    }
+
+    #[test]
+    fn db_to_linear_is_identity_at_zero_db() {
+        assert!((db_to_linear(0.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn detector_declares_endpoint_after_speech_then_silence() {
+        let sample_rate = 16000;
+        let config = VadConfig {
+            enabled: true,
+            silence_timeout_ms: 100,
+            sensitivity: 0.5,
+        };
+        let mut detector = VoiceActivityDetector::new(sample_rate, &config);
+
+        // Prime the noise floor with leading silence.
+        let frame_len = detector.frame_len;
+        assert!(!detector.push_samples(&silence(frame_len * (NOISE_FLOOR_FRAMES + 2))));
+
+        // Loud tonal speech should be classified as speech, not yet endpointed.
+        assert!(!detector.push_samples(&tone(frame_len * 4, sample_rate, 1000.0, 20000)));
+
+        // Enough trailing silence should declare end-of-speech.
+        let endpointed = detector.push_samples(&silence(frame_len * 10));
+        assert!(endpointed);
+    }
+
+    #[test]
+    fn detector_does_not_endpoint_without_prior_speech() {
+        let config = VadConfig {
+            enabled: true,
+            silence_timeout_ms: 100,
+            sensitivity: 0.5,
+        };
+        let mut detector = VoiceActivityDetector::new(16000, &config);
+        assert!(!detector.push_samples(&silence(16000 * 2)));
+    }
+
+    #[test]
+    fn trim_silence_falls_back_to_whole_clip_when_silent() {
+        let samples = silence(16000);
+        let config = VadConfig::default();
+        assert_eq!(trim_silence(&samples, 16000, &config), (0, samples.len()));
+    }
+
+    #[test]
+    fn trim_silence_trims_leading_and_trailing_silence() {
+        let sample_rate = 16000;
+        let frame_len = ((sample_rate * FRAME_MS) / 1000) as usize;
+        let mut samples = silence(frame_len * (NOISE_FLOOR_FRAMES + 4));
+        samples.extend(tone(frame_len * 6, sample_rate, 1000.0, 20000));
+        samples.extend(silence(frame_len * 6));
+
+        let config = VadConfig {
+            enabled: true,
+            silence_timeout_ms: 200,
+            sensitivity: 0.5,
+        };
+        let (start, end) = trim_silence(&samples, sample_rate, &config);
+        assert!(start > 0);
+        assert!(end < samples.len());
+        assert!(start < end);
+    }
+}
@@ -0,0 +1,110 @@
+// ABOUTME: Loads and runs Whisper speech-to-text models in-process via Candle, avoiding whisper-cpp's subprocess overhead.
+// ABOUTME: Keeps the decoded model resident in memory across calls, unlike Parakeet which rebuilds its recognizer every time.
+
+use candle_core::{Device, IndexOp, Tensor};
+use candle_nn::ops::softmax;
+use candle_transformers::models::whisper::{self as m, audio, Config};
+use tokenizers::Tokenizer;
+
+/// A loaded Whisper model, kept resident so repeated calls skip the weight-load cost.
+pub struct CandleWhisperModel {
+    model: m::model::Whisper,
+    tokenizer: Tokenizer,
+    config: Config,
+    device: Device,
+}
+
+impl CandleWhisperModel {
+    /// Load model weights, config, and tokenizer from `model_path`, in the
+    /// layout produced by Hugging Face's `openai/whisper-*` repos.
+    pub fn load(model_path: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let device = Device::Cpu;
+        let config: Config = serde_json::from_str(&std::fs::read_to_string(format!(
+            "{model_path}/config.json"
+        ))?)?;
+        let tokenizer = Tokenizer::from_file(format!("{model_path}/tokenizer.json"))
+            .map_err(|e| format!("failed to load tokenizer: {e}"))?;
+        let vb = unsafe {
+            candle_nn::VarBuilder::from_mmaped_safetensors(
+                &[format!("{model_path}/model.safetensors")],
+                m::DTYPE,
+                &device,
+            )?
+        };
+        let model = m::model::Whisper::load(&vb, config.clone())?;
+
+        Ok(Self {
+            model,
+            tokenizer,
+            config,
+            device,
+        })
+    }
+
+    /// Transcribe a single utterance of 16kHz mono f32 samples.
+    ///
+    /// Resets the model's internal KV cache before each call so the compute
+    /// cache is scoped to one utterance rather than growing unbounded across
+    /// the lifetime of the cached model.
+    pub fn transcribe(
+        &mut self,
+        samples: &[f32],
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.model.reset_kv_cache();
+
+        let mel_bytes = audio::pcm_to_mel(&self.config, samples, &m::audio::Mel::builtin());
+        let mel_len = mel_bytes.len();
+        let mel = Tensor::from_vec(
+            mel_bytes,
+            (1, self.config.num_mel_bins, mel_len / self.config.num_mel_bins),
+            &self.device,
+        )?;
+
+        let audio_features = self.model.encoder.forward(&mel, true)?;
+        let tokens = self.decode(&audio_features)?;
+        let text = self
+            .tokenizer
+            .decode(&tokens, true)
+            .map_err(|e| format!("failed to decode tokens: {e}"))?;
+
+        Ok(text.trim().to_string())
+    }
+
+    /// Greedy-decode text tokens from encoded audio features.
+    fn decode(&mut self, audio_features: &Tensor) -> Result<Vec<u32>, Box<dyn std::error::Error + Send + Sync>> {
+        let sot_token = token_id(&self.tokenizer, m::SOT_TOKEN)?;
+        let eot_token = token_id(&self.tokenizer, m::EOT_TOKEN)?;
+        let no_timestamps_token = token_id(&self.tokenizer, m::NO_TIMESTAMPS_TOKEN)?;
+
+        let mut tokens = vec![sot_token, no_timestamps_token];
+        for _ in 0..self.config.max_target_positions {
+            let tokens_tensor = Tensor::new(tokens.as_slice(), &self.device)?.unsqueeze(0)?;
+            let logits = self
+                .model
+                .decoder
+                .forward(&tokens_tensor, audio_features, tokens.len() == 2)?;
+            let logits = logits.i((0, logits.dim(1)? - 1))?;
+            let probs = softmax(&logits, candle_core::D::Minus1)?;
+            let next_token = probs
+                .to_vec1::<f32>()?
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(idx, _)| idx as u32)
+                .unwrap_or(eot_token);
+
+            if next_token == eot_token {
+                break;
+            }
+            tokens.push(next_token);
+        }
+
+        Ok(tokens)
+    }
+}
+
+fn token_id(tokenizer: &Tokenizer, token: &str) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
+    tokenizer
+        .token_to_id(token)
+        .ok_or_else(|| format!("tokenizer is missing expected token {token}").into())
+}
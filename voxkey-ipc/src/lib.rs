@@ -52,7 +52,7 @@ impl std::str::FromStr for DaemonState {
 }
 
 /// Which transcription backend to use.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum TranscriberProvider {
     #[default]
@@ -60,6 +60,9 @@ pub enum TranscriberProvider {
     Mistral,
     MistralRealtime,
     Parakeet,
+    ParakeetStreaming,
+    OpenAiCompatible,
+    WhisperCandle,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -68,6 +71,25 @@ pub struct WhisperCppConfig {
     pub args: Vec<String>,
 }
 
+/// Configuration for the in-process Whisper backend run via Candle, as an
+/// alternative to shelling out to `whisper-cpp` per utterance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WhisperCandleConfig {
+    /// Directory containing the model weights (`model.safetensors`),
+    /// `config.json`, and `tokenizer.json`, in the layout produced by
+    /// Hugging Face's `openai/whisper-*` repos.
+    #[serde(default)]
+    pub model_path: String,
+}
+
+impl Default for WhisperCandleConfig {
+    fn default() -> Self {
+        Self {
+            model_path: String::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MistralConfig {
     pub api_key: String,
@@ -82,6 +104,40 @@ pub struct MistralRealtimeConfig {
     pub model: String,
     #[serde(default)]
     pub endpoint: String,
+    #[serde(default)]
+    pub stability: StabilityLevel,
+    /// Cap on automatic WebSocket reconnect attempts after a dropped connection
+    /// before the streaming session gives up and returns an error.
+    #[serde(default = "default_max_reconnect_attempts")]
+    pub max_reconnect_attempts: u32,
+    /// Send PCM audio as raw binary WebSocket frames instead of base64-encoded
+    /// JSON, for endpoints that support it. Falls back to the JSON path when
+    /// unset, since not every compatible endpoint understands binary frames.
+    #[serde(default)]
+    pub binary_audio: bool,
+    /// Path to a PEM-encoded custom root CA to trust, for self-hosted
+    /// endpoints with a private or self-signed certificate.
+    #[serde(default)]
+    pub tls_ca_path: Option<String>,
+    /// Skip TLS certificate verification entirely. Dangerous — only meant as
+    /// an escape hatch for LAN testing against a self-hosted endpoint.
+    #[serde(default)]
+    pub tls_insecure: bool,
+}
+
+fn default_max_reconnect_attempts() -> u32 {
+    5
+}
+
+/// How aggressively the realtime provider waits for words to stabilize before
+/// injecting them. Higher stability trades latency for fewer re-typed corrections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum StabilityLevel {
+    Low,
+    #[default]
+    Medium,
+    High,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -91,6 +147,50 @@ pub enum ExecutionProviderChoice {
     Auto,
     Cpu,
     Cuda,
+    TensorRt,
+    CoreMl,
+    DirectMl,
+    Rocm,
+}
+
+impl ExecutionProviderChoice {
+    /// All concrete (non-`Auto`) execution providers, in the order shown in the combo.
+    pub const ALL_CONCRETE: &[ExecutionProviderChoice] = &[
+        ExecutionProviderChoice::Cpu,
+        ExecutionProviderChoice::Cuda,
+        ExecutionProviderChoice::TensorRt,
+        ExecutionProviderChoice::CoreMl,
+        ExecutionProviderChoice::DirectMl,
+        ExecutionProviderChoice::Rocm,
+    ];
+
+    /// The serialized (kebab-case) name used on the wire, e.g. in
+    /// `available_execution_providers`'s D-Bus response.
+    pub fn config_name(self) -> &'static str {
+        match self {
+            ExecutionProviderChoice::Auto => "auto",
+            ExecutionProviderChoice::Cpu => "cpu",
+            ExecutionProviderChoice::Cuda => "cuda",
+            ExecutionProviderChoice::TensorRt => "tensor-rt",
+            ExecutionProviderChoice::CoreMl => "core-ml",
+            ExecutionProviderChoice::DirectMl => "direct-ml",
+            ExecutionProviderChoice::Rocm => "rocm",
+        }
+    }
+
+    /// The ONNX Runtime execution provider name sherpa-rs expects, or `None`
+    /// for `Auto` (let sherpa-rs pick its own default).
+    pub fn onnx_provider_name(self) -> Option<&'static str> {
+        match self {
+            ExecutionProviderChoice::Auto => None,
+            ExecutionProviderChoice::Cpu => Some("cpu"),
+            ExecutionProviderChoice::Cuda => Some("cuda"),
+            ExecutionProviderChoice::TensorRt => Some("tensorrt"),
+            ExecutionProviderChoice::CoreMl => Some("coreml"),
+            ExecutionProviderChoice::DirectMl => Some("directml"),
+            ExecutionProviderChoice::Rocm => Some("rocm"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -112,11 +212,47 @@ impl Default for ParakeetConfig {
 impl MistralConfig {
     pub const DEFAULT_MODEL: &str = "voxtral-mini-2602";
     pub const DEFAULT_ENDPOINT: &str = "https://api.mistral.ai/v1/audio/transcriptions";
+    /// Stable secret-store account label for this provider's API key.
+    pub const SECRET_ACCOUNT: &str = "mistral-api-key";
+}
+
+/// Configuration for a generic OpenAI-compatible HTTP transcription endpoint
+/// (e.g. a local whisper.cpp server, vLLM, or LocalAI).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiCompatibleConfig {
+    #[serde(default = "default_openai_compatible_endpoint")]
+    pub endpoint: String,
+    #[serde(default)]
+    pub api_key: String,
+    #[serde(default)]
+    pub model: String,
+}
+
+fn default_openai_compatible_endpoint() -> String {
+    OpenAiCompatibleConfig::DEFAULT_ENDPOINT.to_string()
+}
+
+impl OpenAiCompatibleConfig {
+    pub const DEFAULT_ENDPOINT: &str = "http://localhost:8080/v1/audio/transcriptions";
+    /// Stable secret-store account label for this provider's API key.
+    pub const SECRET_ACCOUNT: &str = "openai-compatible-api-key";
+}
+
+impl Default for OpenAiCompatibleConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: default_openai_compatible_endpoint(),
+            api_key: String::new(),
+            model: String::new(),
+        }
+    }
 }
 
 impl MistralRealtimeConfig {
     pub const DEFAULT_MODEL: &str = "voxtral-mini-transcribe-realtime-2602";
     pub const DEFAULT_ENDPOINT: &str = "wss://api.mistral.ai/v1/audio/transcriptions/realtime";
+    /// Stable secret-store account label for this provider's API key.
+    pub const SECRET_ACCOUNT: &str = "mistral-realtime-api-key";
 }
 
 /// Provider-based transcription configuration.
@@ -133,6 +269,16 @@ pub struct TranscriberConfig {
     pub mistral_realtime: MistralRealtimeConfig,
     #[serde(default)]
     pub parakeet: ParakeetConfig,
+    #[serde(default)]
+    pub openai_compatible: OpenAiCompatibleConfig,
+    #[serde(default)]
+    pub whisper_candle: WhisperCandleConfig,
+    /// Providers to try, in order, if `provider` returns an error. No time
+    /// limit is applied, so a provider that hangs is not failed over. The
+    /// daemon reports back which provider ultimately served the request so
+    /// the settings GUI can surface the degradation as a toast.
+    #[serde(default)]
+    pub fallback: Vec<TranscriberProvider>,
 }
 
 impl Default for WhisperCppConfig {
@@ -160,10 +306,30 @@ impl Default for MistralRealtimeConfig {
             api_key: String::new(),
             model: Self::DEFAULT_MODEL.to_string(),
             endpoint: String::new(),
+            stability: StabilityLevel::default(),
+            max_reconnect_attempts: default_max_reconnect_attempts(),
+            binary_audio: false,
+            tls_ca_path: None,
+            tls_insecure: false,
         }
     }
 }
 
+impl TranscriberConfig {
+    /// `(secret-store account, api_key field)` pairs for every provider whose
+    /// API key may live in the OS secret store instead of this config, keyed
+    /// by a stable account label. Used by the daemon to refill keys left
+    /// blank by the GUI and to redact keys before the config is persisted or
+    /// exposed over D-Bus.
+    pub fn secret_fields_mut(&mut self) -> [(&'static str, &mut String); 3] {
+        [
+            (MistralConfig::SECRET_ACCOUNT, &mut self.mistral.api_key),
+            (MistralRealtimeConfig::SECRET_ACCOUNT, &mut self.mistral_realtime.api_key),
+            (OpenAiCompatibleConfig::SECRET_ACCOUNT, &mut self.openai_compatible.api_key),
+        ]
+    }
+}
+
 impl Default for TranscriberConfig {
     fn default() -> Self {
         Self {
@@ -172,15 +338,54 @@ impl Default for TranscriberConfig {
             mistral: MistralConfig::default(),
             mistral_realtime: MistralRealtimeConfig::default(),
             parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         }
     }
 }
 
+/// How transcribed text reaches the focused application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputMode {
+    /// Synthesize keystrokes one at a time (the default — works with apps
+    /// that don't watch the clipboard, and with incremental streaming output).
+    #[default]
+    Keystrokes,
+    /// Set the clipboard and simulate Ctrl+V, for apps that drop fast
+    /// synthetic keystrokes.
+    ClipboardPaste,
+    /// Try keystroke synthesis first, falling back to clipboard-paste if it
+    /// fails (e.g. the portal session drops mid-session).
+    Both,
+}
+
+/// Which clipboard tool backs `OutputMode::ClipboardPaste`/`Both`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClipboardProviderChoice {
+    /// Probe `$WAYLAND_DISPLAY` and pick the first available tool
+    /// (the default — works unmodified on Wayland, X11, and XWayland).
+    #[default]
+    Auto,
+    /// `wl-copy`/`wl-paste`, for Wayland sessions.
+    WlClipboard,
+    /// `xclip`, for X11 and XWayland sessions.
+    Xclip,
+    /// `xsel`, for X11 and XWayland sessions.
+    Xsel,
+}
+
 /// Configuration for text injection behavior.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InjectionConfig {
     #[serde(default = "default_typing_delay_ms")]
     pub typing_delay_ms: u32,
+    #[serde(default)]
+    pub mode: OutputMode,
+    #[serde(default)]
+    pub clipboard_provider: ClipboardProviderChoice,
 }
 
 fn default_typing_delay_ms() -> u32 {
@@ -191,10 +396,122 @@ impl Default for InjectionConfig {
     fn default() -> Self {
         Self {
             typing_delay_ms: default_typing_delay_ms(),
+            mode: OutputMode::default(),
+            clipboard_provider: ClipboardProviderChoice::default(),
         }
     }
 }
 
+/// Configuration for voice-activity-detection based auto-stop.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VadConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_silence_timeout_ms")]
+    pub silence_timeout_ms: u32,
+    #[serde(default = "default_vad_sensitivity")]
+    pub sensitivity: f32,
+}
+
+fn default_silence_timeout_ms() -> u32 {
+    800
+}
+
+fn default_vad_sensitivity() -> f32 {
+    0.5
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            silence_timeout_ms: default_silence_timeout_ms(),
+            sensitivity: default_vad_sensitivity(),
+        }
+    }
+}
+
+/// Configuration for optionally recording the exact PCM stream sent during a
+/// realtime streaming session to a `.wav` file, for diagnosing misrecognitions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamingCaptureConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Destination directory for captured WAV files. Empty means fall back to
+    /// the default XDG data directory.
+    #[serde(default)]
+    pub directory: String,
+}
+
+impl Default for StreamingCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: String::new(),
+        }
+    }
+}
+
+/// Configuration for the optional MQTT bridge that mirrors daemon state and
+/// transcription events for headless/home-automation integration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MqttConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_mqtt_url")]
+    pub url: String,
+    #[serde(default = "default_mqtt_topic_prefix")]
+    pub topic_prefix: String,
+}
+
+fn default_mqtt_url() -> String {
+    "mqtt://localhost:1883".to_string()
+}
+
+fn default_mqtt_topic_prefix() -> String {
+    "voxkey".to_string()
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: default_mqtt_url(),
+            topic_prefix: default_mqtt_topic_prefix(),
+        }
+    }
+}
+
+/// State of a queued or in-flight model download job, as tracked by the
+/// daemon's download manager and persisted across restarts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadJobState {
+    Pending,
+    InProgress,
+    Verifying,
+    Complete,
+    ChecksumFailed,
+    Failed,
+    Cancelled,
+}
+
+/// Settings-GUI-facing snapshot of a download job, stripped of the URL and
+/// checksum overrides the daemon needs to actually run it. Serialized to
+/// JSON for the `download_queue` D-Bus property.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DownloadJobStatus {
+    pub job_id: u64,
+    pub model_name: String,
+    pub state: DownloadJobState,
+    pub percent: u8,
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+    pub bytes_per_sec: f64,
+    /// Failure detail for `Failed`/`ChecksumFailed`, empty otherwise.
+    pub error: String,
+}
+
 /// D-Bus proxy for the GUI to communicate with the daemon.
 ///
 /// The daemon implements the server side of this interface using
@@ -221,6 +538,14 @@ pub trait Daemon {
     #[zbus(property)]
     fn injection_config(&self) -> zbus::Result<String>;
 
+    /// Voice-activity-detection configuration as serialized JSON.
+    #[zbus(property)]
+    fn vad_config(&self) -> zbus::Result<String>;
+
+    /// MQTT bridge configuration as serialized JSON.
+    #[zbus(property)]
+    fn mqtt_config(&self) -> zbus::Result<String>;
+
     /// Audio sample rate in Hz.
     #[zbus(property)]
     fn sample_rate(&self) -> zbus::Result<u32>;
@@ -229,6 +554,15 @@ pub trait Daemon {
     #[zbus(property)]
     fn channels(&self) -> zbus::Result<u16>;
 
+    /// Names of the currently available audio input devices, refreshed as
+    /// hardware is plugged or unplugged.
+    #[zbus(property)]
+    fn input_devices(&self) -> zbus::Result<Vec<String>>;
+
+    /// Name of the selected input device, empty when using the host default.
+    #[zbus(property)]
+    fn input_device(&self) -> zbus::Result<String>;
+
     /// Whether portal sessions are connected.
     #[zbus(property)]
     fn portal_connected(&self) -> zbus::Result<bool>;
@@ -250,9 +584,20 @@ pub trait Daemon {
     /// Update the injection configuration from JSON.
     fn set_injection_config(&self, config_json: &str) -> zbus::Result<()>;
 
+    /// Update the VAD configuration from JSON.
+    fn set_vad_config(&self, config_json: &str) -> zbus::Result<()>;
+
+    /// Update the MQTT bridge configuration from JSON.
+    fn set_mqtt_config(&self, config_json: &str) -> zbus::Result<()>;
+
     /// Update audio settings. Takes effect on next recording.
     fn set_audio(&self, sample_rate: u32, channels: u16) -> zbus::Result<()>;
 
+    /// Select the input device to record from, by name as reported by the
+    /// `input_devices` property. Empty string selects the host default.
+    /// Takes effect on next session restart.
+    fn set_input_device(&self, device: &str) -> zbus::Result<()>;
+
     /// Re-read config.toml from disk.
     fn reload_config(&self) -> zbus::Result<()>;
 
@@ -262,27 +607,93 @@ pub trait Daemon {
     /// Shut down the daemon process.
     fn quit(&self) -> zbus::Result<()>;
 
-    /// Start downloading a Parakeet model by name.
-    fn download_model(&self, model_name: &str) -> zbus::Result<()>;
+    /// Restart the capture session, e.g. to reconnect a lost portal session.
+    fn restart_session(&self) -> zbus::Result<()>;
+
+    /// Start downloading a Parakeet model by name. `url` and `sha256` come
+    /// from the settings GUI's provider/model catalog; empty strings fall
+    /// back to the daemon's built-in URL for well-known model names.
+    fn download_model(&self, model_name: &str, url: &str, sha256: &str) -> zbus::Result<()>;
 
     /// Delete a downloaded Parakeet model.
     fn delete_model(&self, model_name: &str) -> zbus::Result<()>;
 
+    /// Cancel a queued or in-progress download by job id, as assigned by the
+    /// `download_queue` property.
+    fn cancel_download(&self, job_id: u64) -> zbus::Result<()>;
+
     /// Check if a Parakeet model is available locally.
-    /// Returns "available", "downloading", or "not_downloaded".
+    /// Returns "available", "downloading", "verifying", "checksum_failed", or "not_downloaded".
     fn model_status(&self, model_name: &str) -> zbus::Result<String>;
 
+    /// SHA-256 of a fully-downloaded model's files, or empty if not fully
+    /// downloaded. Used by the settings GUI to detect a published update.
+    fn installed_model_sha256(&self, model_name: &str) -> zbus::Result<String>;
+
+    /// Execution providers usable on this machine, as kebab-case config
+    /// names (see `ExecutionProviderChoice::config_name`), for graying out
+    /// unsupported entries in the execution provider combo.
+    fn available_execution_providers(&self) -> zbus::Result<Vec<String>>;
+
+    /// Store a secret (e.g. a provider API key) in the OS secret service
+    /// under a stable account label such as `MistralConfig::SECRET_ACCOUNT`,
+    /// so it never has to live in the on-disk transcriber config.
+    fn store_secret(&self, key: &str, value: &str) -> zbus::Result<()>;
+
+    /// Remove a previously stored secret, if any.
+    fn clear_secret(&self, key: &str) -> zbus::Result<()>;
+
+    /// Load a previously stored secret, or an empty string if none is stored
+    /// or no secret service is available.
+    fn load_secret(&self, key: &str) -> zbus::Result<String>;
+
+    /// Execution provider the active Parakeet transcriber resolved `Auto`
+    /// to, or empty if Parakeet isn't the active provider.
+    #[zbus(property)]
+    fn resolved_execution_provider(&self) -> zbus::Result<String>;
+
+    /// The download manager's queue as serialized JSON (`Vec<DownloadJobStatus>`),
+    /// covering every job from pending through its terminal state.
+    #[zbus(property)]
+    fn download_queue(&self) -> zbus::Result<String>;
+
+    /// Lifetime count of non-empty transcripts produced (empty ones are not counted).
+    #[zbus(property)]
+    fn transcriptions_total(&self) -> zbus::Result<u64>;
+
+    /// Lifetime seconds of audio captured across all recordings.
+    #[zbus(property)]
+    fn audio_seconds_total(&self) -> zbus::Result<f64>;
+
+    /// Lifetime words successfully injected.
+    #[zbus(property)]
+    fn words_injected_total(&self) -> zbus::Result<u64>;
+
+    /// Lifetime characters successfully injected.
+    #[zbus(property)]
+    fn characters_injected_total(&self) -> zbus::Result<u64>;
+
+    /// Lifetime outright transcription failures.
+    #[zbus(property)]
+    fn transcription_failures_total(&self) -> zbus::Result<u64>;
+
+    /// Per-engine invocation counts as serialized JSON (`HashMap<String, u64>`),
+    /// keyed by `Transcriber::engine_label()`.
+    #[zbus(property)]
+    fn model_invocations(&self) -> zbus::Result<String>;
+
     /// Emitted when a transcription completes.
     #[zbus(signal)]
     fn transcription_complete(text: &str) -> zbus::Result<()>;
 
-    /// Emitted on recoverable errors.
+    /// Emitted during realtime streaming with the not-yet-stable tail of the
+    /// in-progress transcript, for use as a live preview.
     #[zbus(signal)]
-    fn error_occurred(message: &str) -> zbus::Result<()>;
+    fn transcription_partial(text: &str) -> zbus::Result<()>;
 
-    /// Emitted during model download with progress percentage.
+    /// Emitted on recoverable errors.
     #[zbus(signal)]
-    fn download_progress(model_name: &str, percent: u8) -> zbus::Result<()>;
+    fn error_occurred(message: &str) -> zbus::Result<()>;
 }
 
 #[cfg(test)]
@@ -311,6 +722,9 @@ mod tests {
             },
             mistral_realtime: MistralRealtimeConfig::default(),
             parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         };
         let json = serde_json::to_string(&config).unwrap();
         let parsed: TranscriberConfig = serde_json::from_str(&json).unwrap();
@@ -328,6 +742,9 @@ mod tests {
             mistral: MistralConfig::default(),
             mistral_realtime: MistralRealtimeConfig::default(),
             parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         };
         let toml_str = toml::to_string(&config).unwrap();
         let parsed: TranscriberConfig = toml::from_str(&toml_str).unwrap();
@@ -361,8 +778,16 @@ mod tests {
                 api_key: "sk-rt-test".to_string(),
                 model: "voxtral-mini-transcribe-realtime-2602".to_string(),
                 endpoint: String::new(),
+                stability: StabilityLevel::default(),
+                max_reconnect_attempts: 5,
+                binary_audio: false,
+                tls_ca_path: None,
+                tls_insecure: false,
             },
             parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         };
         let json = serde_json::to_string(&config).unwrap();
         let parsed: TranscriberConfig = serde_json::from_str(&json).unwrap();
@@ -379,8 +804,16 @@ mod tests {
                 api_key: "sk-rt-test".to_string(),
                 model: "voxtral-mini-transcribe-realtime-2602".to_string(),
                 endpoint: String::new(),
+                stability: StabilityLevel::default(),
+                max_reconnect_attempts: 5,
+                binary_audio: false,
+                tls_ca_path: None,
+                tls_insecure: false,
             },
             parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         };
         let toml_str = toml::to_string(&config).unwrap();
         let parsed: TranscriberConfig = toml::from_str(&toml_str).unwrap();
@@ -415,11 +848,21 @@ mod tests {
         assert_eq!(json, "\"parakeet\"");
     }
 
+    #[test]
+    fn provider_serializes_parakeet_streaming_as_kebab_case() {
+        let json = serde_json::to_string(&TranscriberProvider::ParakeetStreaming).unwrap();
+        assert_eq!(json, "\"parakeet-streaming\"");
+    }
+
     #[test]
     fn execution_provider_choice_serializes_as_kebab_case() {
         assert_eq!(serde_json::to_string(&ExecutionProviderChoice::Auto).unwrap(), "\"auto\"");
         assert_eq!(serde_json::to_string(&ExecutionProviderChoice::Cpu).unwrap(), "\"cpu\"");
         assert_eq!(serde_json::to_string(&ExecutionProviderChoice::Cuda).unwrap(), "\"cuda\"");
+        assert_eq!(serde_json::to_string(&ExecutionProviderChoice::TensorRt).unwrap(), "\"tensor-rt\"");
+        assert_eq!(serde_json::to_string(&ExecutionProviderChoice::CoreMl).unwrap(), "\"core-ml\"");
+        assert_eq!(serde_json::to_string(&ExecutionProviderChoice::DirectMl).unwrap(), "\"direct-ml\"");
+        assert_eq!(serde_json::to_string(&ExecutionProviderChoice::Rocm).unwrap(), "\"rocm\"");
     }
 
     #[test]
@@ -433,6 +876,9 @@ mod tests {
                 model: "parakeet-tdt-0.6b-v2".to_string(),
                 execution_provider: ExecutionProviderChoice::Cuda,
             },
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         };
         let json = serde_json::to_string(&config).unwrap();
         let parsed: TranscriberConfig = serde_json::from_str(&json).unwrap();
@@ -447,6 +893,9 @@ mod tests {
             mistral: MistralConfig::default(),
             mistral_realtime: MistralRealtimeConfig::default(),
             parakeet: ParakeetConfig::default(),
+            openai_compatible: OpenAiCompatibleConfig::default(),
+            whisper_candle: WhisperCandleConfig::default(),
+            fallback: Vec::new(),
         };
         let toml_str = toml::to_string(&config).unwrap();
         let parsed: TranscriberConfig = toml::from_str(&toml_str).unwrap();
@@ -0,0 +1,144 @@
+// ABOUTME: Loads transcription provider/model descriptors from TOML files in a catalog directory.
+// ABOUTME: Lets new engines or Parakeet model variants be added without recompiling the settings GUI.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// One selectable entry in the Provider combo row: either a transcription
+/// provider (whisper.cpp, Mistral, ...) or a specific downloadable Parakeet
+/// model variant, which is why `provider` and `id` are tracked separately —
+/// several entries can share the same `provider` (e.g. all Parakeet models).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ProviderDescriptor {
+    pub id: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+    pub provider: String,
+    #[serde(default)]
+    pub download_url: String,
+    #[serde(default)]
+    pub sha256: String,
+    #[serde(default)]
+    pub update_interval_secs: Option<u64>,
+}
+
+/// Catalog directory: XDG_CONFIG_HOME/voxkey/catalog/providers (mirrors
+/// `gui_settings::path()`'s XDG_CONFIG_HOME resolution).
+fn catalog_dir() -> PathBuf {
+    let config_dir = std::env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            let home = std::env::var("HOME").expect("HOME not set");
+            PathBuf::from(home).join(".config")
+        });
+    config_dir.join("voxkey").join("catalog").join("providers")
+}
+
+/// Load the provider/model catalog shown in the Provider combo row. Scans
+/// `catalog_dir()` for `*.toml` descriptor files, sorted by filename for
+/// deterministic ordering; a malformed file is logged and skipped rather than
+/// failing the whole GUI. Falls back to the built-in defaults when the
+/// directory is absent or empty, so a fresh install behaves as before.
+pub fn load_catalog() -> Vec<ProviderDescriptor> {
+    let loaded = load_descriptors(&catalog_dir());
+    if loaded.is_empty() {
+        default_catalog()
+    } else {
+        loaded
+    }
+}
+
+fn load_descriptors(dir: &Path) -> Vec<ProviderDescriptor> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let contents = match std::fs::read_to_string(&path) {
+                Ok(c) => c,
+                Err(e) => {
+                    tracing::warn!("Failed to read catalog descriptor {}: {e}", path.display());
+                    return None;
+                }
+            };
+            match toml::from_str::<ProviderDescriptor>(&contents) {
+                Ok(descriptor) => Some(descriptor),
+                Err(e) => {
+                    tracing::warn!("Skipping malformed catalog descriptor {}: {e}", path.display());
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// The catalog shipped today, used when no descriptor files are installed.
+fn default_catalog() -> Vec<ProviderDescriptor> {
+    vec![
+        ProviderDescriptor {
+            id: "whisper-cpp".to_string(),
+            display_name: "whisper.cpp".to_string(),
+            description: "Local transcription via a whisper.cpp binary".to_string(),
+            provider: "whisper-cpp".to_string(),
+            download_url: String::new(),
+            sha256: String::new(),
+            update_interval_secs: None,
+        },
+        ProviderDescriptor {
+            id: "mistral".to_string(),
+            display_name: "Mistral".to_string(),
+            description: "Batch transcription via the Mistral API".to_string(),
+            provider: "mistral".to_string(),
+            download_url: String::new(),
+            sha256: String::new(),
+            update_interval_secs: None,
+        },
+        ProviderDescriptor {
+            id: "mistral-realtime".to_string(),
+            display_name: "Mistral Realtime".to_string(),
+            description: "Streaming transcription via the Mistral realtime API".to_string(),
+            provider: "mistral-realtime".to_string(),
+            download_url: String::new(),
+            sha256: String::new(),
+            update_interval_secs: None,
+        },
+        ProviderDescriptor {
+            id: "parakeet-tdt-0.6b-v2".to_string(),
+            display_name: "Parakeet v2".to_string(),
+            description: "Local transcription via sherpa-rs (TDT v2)".to_string(),
+            provider: "parakeet".to_string(),
+            download_url: "https://huggingface.co/csukuangfj/sherpa-onnx-nemo-parakeet-tdt-0.6b-v2-int8/resolve/main".to_string(),
+            sha256: String::new(),
+            update_interval_secs: None,
+        },
+        ProviderDescriptor {
+            id: "parakeet-tdt-0.6b-v3".to_string(),
+            display_name: "Parakeet v3".to_string(),
+            description: "Local transcription via sherpa-rs (TDT v3)".to_string(),
+            provider: "parakeet".to_string(),
+            download_url: "https://huggingface.co/csukuangfj/sherpa-onnx-nemo-parakeet-tdt-0.6b-v3-int8/resolve/main".to_string(),
+            sha256: String::new(),
+            update_interval_secs: None,
+        },
+        ProviderDescriptor {
+            id: "openai-compatible".to_string(),
+            display_name: "OpenAI-compatible".to_string(),
+            description: "Batch transcription via an OpenAI-compatible API".to_string(),
+            provider: "openai-compatible".to_string(),
+            download_url: String::new(),
+            sha256: String::new(),
+            update_interval_secs: None,
+        },
+    ]
+}
@@ -7,6 +7,8 @@ use std::sync::Arc;
 use futures_util::StreamExt;
 use voxkey_ipc::DaemonProxy;
 
+use crate::transport::DaemonTransport;
+
 /// Messages sent from the D-Bus background thread to the GTK main loop.
 #[derive(Debug)]
 pub enum DaemonUpdate {
@@ -25,14 +27,62 @@ pub enum DaemonUpdate {
         name: String,
         value: String,
     },
-    DownloadProgress {
-        model_name: String,
-        percent: u8,
+    ErrorOccurred {
+        message: String,
+        action: Option<ToastAction>,
     },
+    InputDevices {
+        devices: Vec<String>,
+        selected: String,
+    },
+    /// The download manager's queue changed: a job was added, progressed, or
+    /// reached a terminal state.
+    DownloadQueue(Vec<voxkey_ipc::DownloadJobStatus>),
     ModelStatusResult {
         model_name: String,
         status: String,
     },
+    UpdateAvailable {
+        model_name: String,
+        display_name: String,
+    },
+    ExecutionProvidersResult(Vec<String>),
+    SecretLoaded {
+        key: String,
+        value: String,
+    },
+    /// The models directory changed on disk outside this process (e.g. the
+    /// user dropped files in via "Open Models Dir"). Carries the directory
+    /// names currently present so callers don't need to re-list it themselves.
+    ModelsChanged(Vec<String>),
+    /// The GUI settings file (`hide_on_close`) changed on disk outside this
+    /// process.
+    GuiSettingsChanged,
+}
+
+/// A suggested recovery action attached to an error update, surfaced as a
+/// button on the resulting toast.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToastAction {
+    /// Retry downloading the named model.
+    RetryDownload(String),
+    /// Reconnect the capture session (e.g. after a lost portal session).
+    ReconnectPortal,
+}
+
+/// Infer a suggested recovery action from a `last_error` message, if any.
+pub fn infer_toast_action(message: &str) -> Option<ToastAction> {
+    if let Some(rest) = message
+        .strip_prefix("Download failed for ")
+        .or_else(|| message.strip_prefix("Checksum failed for "))
+    {
+        let model_name = rest.split(':').next().unwrap_or(rest).trim();
+        return Some(ToastAction::RetryDownload(model_name.to_string()));
+    }
+    if message.starts_with("Session recovery failed") {
+        return Some(ToastAction::ReconnectPortal);
+    }
+    None
 }
 
 /// Handle for sending commands to the daemon from the GTK thread.
@@ -46,12 +96,28 @@ pub enum DaemonCommand {
     SetShortcut(String),
     SetTranscriberConfig(String),
     SetInjectionConfig(String),
-    DownloadModel(String),
+    SetInputDevice(String),
+    DownloadModel {
+        model_name: String,
+        url: String,
+        sha256: String,
+    },
     DeleteModel(String),
+    CancelDownload(u64),
     ModelStatus(String),
+    CheckModelUpdate {
+        model_name: String,
+        display_name: String,
+        expected_sha256: String,
+    },
+    QueryExecutionProviders,
+    StoreSecret { key: String, value: String },
+    ClearSecret(String),
+    LoadSecret(String),
     OpenModelsDir,
     ReloadConfig,
     ClearRestoreToken,
+    RestartSession,
     QuitDaemon { ack: mpsc::Sender<()> },
 }
 
@@ -61,12 +127,24 @@ impl std::fmt::Debug for DaemonCommand {
             Self::SetShortcut(s) => f.debug_tuple("SetShortcut").field(s).finish(),
             Self::SetTranscriberConfig(s) => f.debug_tuple("SetTranscriberConfig").field(s).finish(),
             Self::SetInjectionConfig(s) => f.debug_tuple("SetInjectionConfig").field(s).finish(),
-            Self::DownloadModel(s) => f.debug_tuple("DownloadModel").field(s).finish(),
+            Self::SetInputDevice(s) => f.debug_tuple("SetInputDevice").field(s).finish(),
+            Self::DownloadModel { model_name, .. } => {
+                f.debug_tuple("DownloadModel").field(model_name).finish()
+            }
             Self::DeleteModel(s) => f.debug_tuple("DeleteModel").field(s).finish(),
+            Self::CancelDownload(job_id) => f.debug_tuple("CancelDownload").field(job_id).finish(),
             Self::ModelStatus(s) => f.debug_tuple("ModelStatus").field(s).finish(),
+            Self::CheckModelUpdate { model_name, .. } => {
+                f.debug_tuple("CheckModelUpdate").field(model_name).finish()
+            }
+            Self::QueryExecutionProviders => write!(f, "QueryExecutionProviders"),
+            Self::StoreSecret { key, .. } => f.debug_struct("StoreSecret").field("key", key).finish(),
+            Self::ClearSecret(key) => f.debug_tuple("ClearSecret").field(key).finish(),
+            Self::LoadSecret(key) => f.debug_tuple("LoadSecret").field(key).finish(),
             Self::OpenModelsDir => write!(f, "OpenModelsDir"),
             Self::ReloadConfig => write!(f, "ReloadConfig"),
             Self::ClearRestoreToken => write!(f, "ClearRestoreToken"),
+            Self::RestartSession => write!(f, "RestartSession"),
             Self::QuitDaemon { .. } => write!(f, "QuitDaemon"),
         }
     }
@@ -85,9 +163,11 @@ impl DaemonHandle {
     }
 }
 
-/// Spawn a background tokio runtime that connects to the daemon D-Bus interface.
+/// Spawn a background tokio runtime that connects to the daemon D-Bus interface
+/// over `transport` (the local session bus by default, or a remote daemon
+/// reached over TCP/SSH per the "Remote Daemon" GUI field).
 /// Returns an mpsc Receiver for updates and a DaemonHandle for sending commands.
-pub fn connect() -> (mpsc::Receiver<DaemonUpdate>, DaemonHandle) {
+pub fn connect(transport: DaemonTransport) -> (mpsc::Receiver<DaemonUpdate>, DaemonHandle) {
     let (update_tx, update_rx) = mpsc::channel();
     let (cmd_tx, cmd_rx) = mpsc::channel::<DaemonCommand>();
 
@@ -95,19 +175,22 @@ pub fn connect() -> (mpsc::Receiver<DaemonUpdate>, DaemonHandle) {
         cmd_tx: Arc::new(std::sync::Mutex::new(cmd_tx)),
     };
 
+    crate::fs_watcher::spawn(update_tx.clone());
+
     std::thread::spawn(move || {
         let rt = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
             .expect("Failed to create tokio runtime");
 
-        rt.block_on(run_client(update_tx, cmd_rx));
+        rt.block_on(run_client(transport, update_tx, cmd_rx));
     });
 
     (update_rx, handle)
 }
 
 async fn run_client(
+    transport: DaemonTransport,
     update_tx: mpsc::Sender<DaemonUpdate>,
     cmd_rx: mpsc::Receiver<DaemonCommand>,
 ) {
@@ -115,7 +198,7 @@ async fn run_client(
     let cmd_rx = Arc::new(std::sync::Mutex::new(cmd_rx));
 
     loop {
-        match try_connect(&update_tx, &cmd_rx).await {
+        match try_connect(&transport, &update_tx, &cmd_rx).await {
             Ok(()) => return,
             Err(e) => {
                 tracing::warn!("Daemon connection failed: {e}");
@@ -127,10 +210,11 @@ async fn run_client(
 }
 
 async fn try_connect(
+    transport: &DaemonTransport,
     update_tx: &mpsc::Sender<DaemonUpdate>,
     cmd_rx: &Arc<std::sync::Mutex<mpsc::Receiver<DaemonCommand>>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let connection = zbus::Connection::session().await?;
+    let connection = transport.connect().await?;
     let proxy = DaemonProxy::new(&connection).await?;
 
     // Read initial state
@@ -152,15 +236,31 @@ async fn try_connect(
         last_error,
     })?;
 
+    let input_devices = proxy.input_devices().await?;
+    let input_device = proxy.input_device().await?;
+    update_tx.send(DaemonUpdate::InputDevices {
+        devices: input_devices,
+        selected: input_device,
+    })?;
+
+    let resolved_execution_provider = proxy.resolved_execution_provider().await?;
+    update_tx.send(DaemonUpdate::PropertyChanged {
+        name: "resolved_execution_provider".to_string(),
+        value: resolved_execution_provider,
+    })?;
+
     // Subscribe to property change streams
     let mut state_stream = proxy.receive_state_changed().await;
+    let mut resolved_execution_provider_stream = proxy.receive_resolved_execution_provider_changed().await;
     let mut transcript_stream = proxy.receive_last_transcript_changed().await;
     let mut portal_stream = proxy.receive_portal_connected_changed().await;
     let mut shortcut_stream = proxy.receive_shortcut_trigger_changed().await;
     let mut transcriber_stream = proxy.receive_transcriber_config_changed().await;
     let mut error_stream = proxy.receive_last_error_changed().await;
     let mut injection_stream = proxy.receive_injection_config_changed().await;
-    let mut download_stream = proxy.receive_download_progress().await?;
+    let mut input_devices_stream = proxy.receive_input_devices_changed().await;
+    let mut input_device_stream = proxy.receive_input_device_changed().await;
+    let mut download_queue_stream = proxy.receive_download_queue_changed().await;
 
     // Poll for commands periodically
     let mut cmd_interval = tokio::time::interval(std::time::Duration::from_millis(50));
@@ -172,6 +272,14 @@ async fn try_connect(
                     let _ = update_tx.send(DaemonUpdate::StateChanged(val));
                 }
             }
+            Some(change) = resolved_execution_provider_stream.next() => {
+                if let Ok(val) = change.get().await {
+                    let _ = update_tx.send(DaemonUpdate::PropertyChanged {
+                        name: "resolved_execution_provider".to_string(),
+                        value: val,
+                    });
+                }
+            }
             Some(change) = transcript_stream.next() => {
                 if let Ok(val) = change.get().await {
                     let _ = update_tx.send(DaemonUpdate::PropertyChanged {
@@ -206,9 +314,10 @@ async fn try_connect(
             }
             Some(change) = error_stream.next() => {
                 if let Ok(val) = change.get().await {
-                    let _ = update_tx.send(DaemonUpdate::PropertyChanged {
-                        name: "last_error".to_string(),
-                        value: val,
+                    let action = infer_toast_action(&val);
+                    let _ = update_tx.send(DaemonUpdate::ErrorOccurred {
+                        message: val,
+                        action,
                     });
                 }
             }
@@ -220,12 +329,25 @@ async fn try_connect(
                     });
                 }
             }
-            Some(signal) = download_stream.next() => {
-                if let Ok(args) = signal.args() {
-                    let _ = update_tx.send(DaemonUpdate::DownloadProgress {
-                        model_name: args.model_name.to_string(),
-                        percent: args.percent,
-                    });
+            Some(change) = input_devices_stream.next() => {
+                if let Ok(devices) = change.get().await {
+                    if let Ok(selected) = proxy.input_device().await {
+                        let _ = update_tx.send(DaemonUpdate::InputDevices { devices, selected });
+                    }
+                }
+            }
+            Some(change) = input_device_stream.next() => {
+                if let Ok(selected) = change.get().await {
+                    if let Ok(devices) = proxy.input_devices().await {
+                        let _ = update_tx.send(DaemonUpdate::InputDevices { devices, selected });
+                    }
+                }
+            }
+            Some(change) = download_queue_stream.next() => {
+                if let Ok(json) = change.get().await {
+                    if let Ok(queue) = serde_json::from_str(&json) {
+                        let _ = update_tx.send(DaemonUpdate::DownloadQueue(queue));
+                    }
                 }
             }
             _ = cmd_interval.tick() => {
@@ -256,12 +378,18 @@ async fn handle_command(
         DaemonCommand::SetInjectionConfig(config_json) => {
             proxy.set_injection_config(&config_json).await?;
         }
-        DaemonCommand::DownloadModel(name) => {
-            proxy.download_model(&name).await?;
+        DaemonCommand::SetInputDevice(device) => {
+            proxy.set_input_device(&device).await?;
+        }
+        DaemonCommand::DownloadModel { model_name, url, sha256 } => {
+            proxy.download_model(&model_name, &url, &sha256).await?;
         }
         DaemonCommand::DeleteModel(name) => {
             proxy.delete_model(&name).await?;
         }
+        DaemonCommand::CancelDownload(job_id) => {
+            proxy.cancel_download(job_id).await?;
+        }
         DaemonCommand::ModelStatus(name) => {
             let status = proxy.model_status(&name).await?;
             let _ = update_tx.send(DaemonUpdate::ModelStatusResult {
@@ -269,6 +397,29 @@ async fn handle_command(
                 status,
             });
         }
+        DaemonCommand::CheckModelUpdate { model_name, display_name, expected_sha256 } => {
+            let installed = proxy.installed_model_sha256(&model_name).await?;
+            if !installed.is_empty()
+                && !expected_sha256.is_empty()
+                && !installed.eq_ignore_ascii_case(&expected_sha256)
+            {
+                let _ = update_tx.send(DaemonUpdate::UpdateAvailable { model_name, display_name });
+            }
+        }
+        DaemonCommand::QueryExecutionProviders => {
+            let providers = proxy.available_execution_providers().await?;
+            let _ = update_tx.send(DaemonUpdate::ExecutionProvidersResult(providers));
+        }
+        DaemonCommand::StoreSecret { key, value } => {
+            proxy.store_secret(&key, &value).await?;
+        }
+        DaemonCommand::ClearSecret(key) => {
+            proxy.clear_secret(&key).await?;
+        }
+        DaemonCommand::LoadSecret(key) => {
+            let value = proxy.load_secret(&key).await?;
+            let _ = update_tx.send(DaemonUpdate::SecretLoaded { key, value });
+        }
         DaemonCommand::OpenModelsDir => {
             let data_dir = std::env::var("XDG_DATA_HOME")
                 .unwrap_or_else(|_| {
@@ -287,6 +438,9 @@ async fn handle_command(
         DaemonCommand::ClearRestoreToken => {
             proxy.clear_restore_token().await?;
         }
+        DaemonCommand::RestartSession => {
+            proxy.restart_session().await?;
+        }
         DaemonCommand::QuitDaemon { ack } => {
             if let Err(e) = proxy.quit().await {
                 tracing::warn!("Failed to send quit to daemon: {e}");
@@ -0,0 +1,109 @@
+// ABOUTME: Watches the models directory and GUI settings file for out-of-band changes.
+// ABOUTME: Debounces filesystem events and forwards them to the GTK thread via DaemonUpdate.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+
+use crate::daemon_client::DaemonUpdate;
+use crate::gui_settings;
+
+/// Quiet period after the last filesystem event before a debounced update is
+/// sent, so a burst of events from e.g. a multi-file copy collapses into one.
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+fn models_dir() -> PathBuf {
+    let data_dir = std::env::var("XDG_DATA_HOME").unwrap_or_else(|_| {
+        let home = std::env::var("HOME").unwrap_or_else(|_| "~".to_string());
+        format!("{home}/.local/share")
+    });
+    PathBuf::from(data_dir).join("voxkey").join("models")
+}
+
+fn list_model_dirs(dir: &std::path::Path) -> std::io::Result<Vec<String>> {
+    let mut names = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            if let Some(name) = entry.file_name().to_str() {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Spawn a background thread that watches `models_dir()` and the GUI settings
+/// path for changes made outside this process, debounces them, and forwards
+/// `ModelsChanged`/`GuiSettingsChanged` updates through `update_tx` — the same
+/// channel the D-Bus client thread uses — so the GTK thread picks them up in
+/// its existing poll loop without a restart.
+pub fn spawn(update_tx: mpsc::Sender<DaemonUpdate>) {
+    std::thread::spawn(move || {
+        let models_dir = models_dir();
+        let gui_settings_path = gui_settings::path();
+        let _ = std::fs::create_dir_all(&models_dir);
+        if let Some(parent) = gui_settings_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        let (event_tx, event_rx) = mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = event_tx.send(event);
+            }
+        }) {
+            Ok(w) => w,
+            Err(e) => {
+                tracing::warn!("Failed to create filesystem watcher: {e}");
+                return;
+            }
+        };
+
+        if let Err(e) = watcher.watch(&models_dir, RecursiveMode::NonRecursive) {
+            tracing::warn!("Failed to watch models dir {}: {e}", models_dir.display());
+        }
+        if let Some(parent) = gui_settings_path.parent() {
+            if let Err(e) = watcher.watch(parent, RecursiveMode::NonRecursive) {
+                tracing::warn!("Failed to watch GUI settings dir {}: {e}", parent.display());
+            }
+        }
+
+        let mut models_dirty = false;
+        let mut gui_settings_dirty = false;
+
+        loop {
+            match event_rx.recv_timeout(DEBOUNCE) {
+                Ok(event) => {
+                    for path in &event.paths {
+                        if path.starts_with(&models_dir) {
+                            models_dirty = true;
+                        } else if *path == gui_settings_path {
+                            gui_settings_dirty = true;
+                        }
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if models_dirty {
+                        models_dirty = false;
+                        if let Ok(names) = list_model_dirs(&models_dir) {
+                            if update_tx.send(DaemonUpdate::ModelsChanged(names)).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    if gui_settings_dirty {
+                        gui_settings_dirty = false;
+                        if update_tx.send(DaemonUpdate::GuiSettingsChanged).is_err() {
+                            return;
+                        }
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    });
+}
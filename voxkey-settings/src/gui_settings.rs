@@ -3,14 +3,18 @@
 
 use std::path::PathBuf;
 
-fn path() -> PathBuf {
+fn config_dir() -> PathBuf {
     let config_dir = std::env::var("XDG_CONFIG_HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|_| {
             let home = std::env::var("HOME").expect("HOME not set");
             PathBuf::from(home).join(".config")
         });
-    config_dir.join("voxkey").join("hide_on_close")
+    config_dir.join("voxkey")
+}
+
+pub(crate) fn path() -> PathBuf {
+    config_dir().join("hide_on_close")
 }
 
 pub fn load_hide_on_close() -> bool {
@@ -26,3 +30,23 @@ pub fn save_hide_on_close(value: bool) {
     }
     let _ = std::fs::write(p, if value { "true" } else { "false" });
 }
+
+fn daemon_transport_path() -> PathBuf {
+    config_dir().join("daemon_transport")
+}
+
+/// The persisted "Remote Daemon" connection string, or empty for the local
+/// session bus. Feed this to `transport::DaemonTransport::parse`.
+pub fn load_daemon_transport() -> String {
+    std::fs::read_to_string(daemon_transport_path())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
+pub fn save_daemon_transport(value: &str) {
+    let p = daemon_transport_path();
+    if let Some(parent) = p.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(p, value.trim());
+}
@@ -1,8 +1,11 @@
 // ABOUTME: Entry point for the voxkey settings GUI.
 // ABOUTME: GTK4+libadwaita application for configuring and monitoring the voxkey daemon.
 
+mod catalog;
 mod daemon_client;
+mod fs_watcher;
 mod gui_settings;
+mod transport;
 mod window;
 
 use libadwaita as adw;
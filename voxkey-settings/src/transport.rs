@@ -0,0 +1,125 @@
+// ABOUTME: Parses a daemon connection string and opens the corresponding zbus Connection.
+// ABOUTME: Supports the local session bus, D-Bus-over-TCP, and an SSH-forwarded remote session bus.
+
+use std::time::Duration;
+
+/// How to reach the voxkey daemon's D-Bus interface, selected via the
+/// "Remote Daemon" field in its own settings group and persisted by
+/// `gui_settings`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaemonTransport {
+    /// The local session bus (the default, used when the field is blank).
+    SessionBus,
+    /// A D-Bus server reachable directly over TCP, given as a D-Bus address
+    /// string, e.g. `tcp:host=10.0.0.5,port=12345`.
+    Tcp(String),
+    /// A remote session bus reached by forwarding its socket over SSH, given
+    /// as an `ssh://user@host` target.
+    Ssh(String),
+}
+
+impl DaemonTransport {
+    /// Parse a connection string as persisted by the GUI field. An empty
+    /// string means the local session bus; `ssh://user@host` selects the SSH
+    /// transport; anything else is passed through as a raw D-Bus TCP address.
+    pub fn parse(s: &str) -> Self {
+        let s = s.trim();
+        if s.is_empty() {
+            DaemonTransport::SessionBus
+        } else if let Some(target) = s.strip_prefix("ssh://") {
+            DaemonTransport::Ssh(target.to_string())
+        } else {
+            DaemonTransport::Tcp(s.to_string())
+        }
+    }
+
+    /// Open a zbus connection for this transport.
+    pub async fn connect(&self) -> zbus::Result<zbus::Connection> {
+        match self {
+            DaemonTransport::SessionBus => zbus::Connection::session().await,
+            DaemonTransport::Tcp(address) => zbus::Connection::builder(address.as_str())?.build().await,
+            DaemonTransport::Ssh(target) => connect_via_ssh(target).await,
+        }
+    }
+}
+
+/// Forward the remote session bus's Unix socket to a local temp socket over
+/// SSH, then connect to the local end of the tunnel — the same trick as
+/// `ssh -L`, but for a Unix socket instead of a TCP port.
+async fn connect_via_ssh(target: &str) -> zbus::Result<zbus::Connection> {
+    let remote_socket = remote_session_bus_socket(target).await?;
+    let local_socket = std::env::temp_dir().join(format!("voxkey-ssh-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&local_socket);
+
+    let _child = tokio::process::Command::new("ssh")
+        .arg("-N")
+        .arg("-L")
+        .arg(format!("{}:{remote_socket}", local_socket.display()))
+        .arg(target)
+        .spawn()
+        .map_err(zbus::Error::InputOutput)?;
+
+    // Give the tunnel a moment to establish before the first connect attempt.
+    for _ in 0..20 {
+        if local_socket.exists() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let stream = tokio::net::UnixStream::connect(&local_socket)
+        .await
+        .map_err(zbus::Error::InputOutput)?;
+    zbus::Connection::unix_stream(stream).await
+}
+
+/// Ask the remote host for its session bus address via `printenv` over SSH,
+/// and extract the `unix:path=...` socket path from it.
+async fn remote_session_bus_socket(target: &str) -> zbus::Result<String> {
+    let output = tokio::process::Command::new("ssh")
+        .arg(target)
+        .arg("printenv")
+        .arg("DBUS_SESSION_BUS_ADDRESS")
+        .output()
+        .await
+        .map_err(zbus::Error::InputOutput)?;
+
+    let address = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    address
+        .split(',')
+        .find_map(|part| part.strip_prefix("unix:path="))
+        .map(|path| path.to_string())
+        .ok_or_else(|| {
+            zbus::Error::InputOutput(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("could not determine remote session bus socket from {target} (got {address:?})"),
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_blank_is_session_bus() {
+        assert_eq!(DaemonTransport::parse(""), DaemonTransport::SessionBus);
+        assert_eq!(DaemonTransport::parse("   "), DaemonTransport::SessionBus);
+    }
+
+    #[test]
+    fn parse_ssh_prefix_strips_scheme() {
+        assert_eq!(
+            DaemonTransport::parse("ssh://alice@example.com"),
+            DaemonTransport::Ssh("alice@example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_other_strings_are_tcp_addresses() {
+        assert_eq!(
+            DaemonTransport::parse("tcp:host=10.0.0.5,port=12345"),
+            DaemonTransport::Tcp("tcp:host=10.0.0.5,port=12345".to_string())
+        );
+    }
+}
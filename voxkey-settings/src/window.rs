@@ -2,6 +2,7 @@
 // ABOUTME: Wires D-Bus property changes to widget updates and user actions to D-Bus method calls.
 
 use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::time::Duration;
 
@@ -11,11 +12,14 @@ use gtk4::prelude::*;
 use libadwaita as adw;
 use adw::prelude::*;
 
+use crate::catalog::{self, ProviderDescriptor};
 use crate::daemon_client::{self, DaemonCommand, DaemonHandle, DaemonUpdate};
 use crate::gui_settings;
+use crate::transport::DaemonTransport;
 
 pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
-    let (update_rx, handle) = daemon_client::connect();
+    let transport = DaemonTransport::parse(&gui_settings::load_daemon_transport());
+    let (update_rx, handle) = daemon_client::connect(transport);
 
     let toast_overlay = adw::ToastOverlay::new();
 
@@ -25,6 +29,14 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
     banner.set_revealed(true);
     content.append(&banner);
 
+    // Revealed when a background catalog check finds a newer model revision
+    // than what's installed; dismissed once the refreshed artifact reports
+    // "available" via ModelStatusResult.
+    let update_banner = adw::Banner::new("");
+    update_banner.set_revealed(false);
+    content.append(&update_banner);
+    let pending_update_model = Rc::new(RefCell::new(Option::<String>::None));
+
     let scrolled = gtk4::ScrolledWindow::builder()
         .vexpand(true)
         .build();
@@ -79,6 +91,23 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
     dictation_group.add(&shortcut_row);
     groups_box.append(&dictation_group);
 
+    // -- Audio Input group --
+    let audio_input_group = adw::PreferencesGroup::builder()
+        .title("Audio Input")
+        .build();
+
+    // Populated live from DaemonUpdate::InputDevices; kept alongside the
+    // StringList so selection changes can be mapped back to a device name.
+    let input_devices = Rc::new(RefCell::new(Vec::<String>::new()));
+    let input_device_model = gtk4::StringList::new(&[]);
+    let input_device_row = adw::ComboRow::builder()
+        .title("Microphone")
+        .model(&input_device_model)
+        .build();
+
+    audio_input_group.add(&input_device_row);
+    groups_box.append(&audio_input_group);
+
     // -- Transcript group --
     let transcript_group = adw::PreferencesGroup::builder()
         .title("Last Transcript")
@@ -110,9 +139,48 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
         .title("Transcription Engine")
         .build();
 
-    let provider_model = gtk4::StringList::new(&[
-        "whisper.cpp", "Mistral", "Mistral Realtime", "Parakeet v2", "Parakeet v3",
-    ]);
+    let catalog = Rc::new(catalog::load_catalog());
+
+    update_banner.set_button_label(Some("Update"));
+    {
+        let handle = handle.clone();
+        let catalog = catalog.clone();
+        let pending_update_model = pending_update_model.clone();
+        update_banner.connect_button_clicked(move |_| {
+            let Some(model_name) = pending_update_model.borrow().clone() else {
+                return;
+            };
+            let entry = catalog.iter().find(|d| d.id == model_name);
+            let url = entry.map(|d| d.download_url.clone()).unwrap_or_default();
+            let sha256 = entry.map(|d| d.sha256.clone()).unwrap_or_default();
+            handle.send(DaemonCommand::DownloadModel { model_name, url, sha256 });
+        });
+    }
+
+    // Periodically compare each catalog entry's checksum against what's
+    // installed, at the entry's own `update_interval_secs`, and surface
+    // `update_banner` when they diverge (a newer revision was published).
+    for entry in catalog.iter() {
+        let (Some(interval_secs), false) = (entry.update_interval_secs, entry.sha256.is_empty()) else {
+            continue;
+        };
+        let handle = handle.clone();
+        let model_name = entry.id.clone();
+        let display_name = entry.display_name.clone();
+        let expected_sha256 = entry.sha256.clone();
+        glib::timeout_add_seconds_local(interval_secs as u32, move || {
+            handle.send(DaemonCommand::CheckModelUpdate {
+                model_name: model_name.clone(),
+                display_name: display_name.clone(),
+                expected_sha256: expected_sha256.clone(),
+            });
+            glib::ControlFlow::Continue
+        });
+    }
+
+    let provider_model = gtk4::StringList::new(
+        &catalog.iter().map(|d| d.display_name.as_str()).collect::<Vec<_>>(),
+    );
     let provider_row = adw::ComboRow::builder()
         .title("Provider")
         .model(&provider_model)
@@ -151,7 +219,9 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
     transcription_group.add(&endpoint_row);
 
     // Parakeet sub-rows
-    let execution_provider_model = gtk4::StringList::new(&["Auto", "CPU", "CUDA"]);
+    let execution_provider_labels: Vec<&str> =
+        EXECUTION_PROVIDER_COMBO.iter().map(|(_, label)| *label).collect();
+    let execution_provider_model = gtk4::StringList::new(&execution_provider_labels);
     let execution_provider_row = adw::ComboRow::builder()
         .title("Execution Provider")
         .model(&execution_provider_model)
@@ -162,6 +232,19 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
         .subtitle("Unknown")
         .build();
 
+    let download_progress_bar = gtk4::ProgressBar::new();
+    download_progress_bar.set_valign(gtk4::Align::Center);
+    download_progress_bar.set_size_request(120, -1);
+    download_progress_bar.set_show_text(true);
+    download_progress_bar.set_visible(false);
+    model_status_row.add_suffix(&download_progress_bar);
+
+    let cancel_download_button = gtk4::Button::from_icon_name("process-stop-symbolic");
+    cancel_download_button.set_valign(gtk4::Align::Center);
+    cancel_download_button.add_css_class("flat");
+    cancel_download_button.set_visible(false);
+    model_status_row.add_suffix(&cancel_download_button);
+
     let download_button = gtk4::Button::with_label("Download");
     download_button.set_valign(gtk4::Align::Center);
     model_status_row.add_suffix(&download_button);
@@ -180,6 +263,22 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
     transcription_group.add(&execution_provider_row);
     transcription_group.add(&model_status_row);
 
+    // Fallback chain: one switch per eligible provider, tried in
+    // FALLBACK_PROVIDER_COMBO order if the primary provider errors.
+    let fallback_row = adw::ExpanderRow::builder()
+        .title("Fallback Providers")
+        .subtitle("Tried in order if the primary provider fails")
+        .build();
+    let fallback_switches: Vec<adw::SwitchRow> = FALLBACK_PROVIDER_COMBO
+        .iter()
+        .map(|(_, label)| {
+            let switch_row = adw::SwitchRow::builder().title(*label).build();
+            fallback_row.add_row(&switch_row);
+            switch_row
+        })
+        .collect();
+    transcription_group.add(&fallback_row);
+
     // Initially hide non-whisper.cpp rows (default provider)
     api_key_row.set_visible(false);
     model_row.set_visible(false);
@@ -193,6 +292,35 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
     let transcriber_state = Rc::new(RefCell::new(voxkey_ipc::TranscriberConfig::default()));
     // Guard to suppress send_transcriber_config during programmatic widget updates
     let updating_widgets = Rc::new(Cell::new(false));
+    // Execution providers usable on this machine, as kebab-case config names
+    let available_execution_providers: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+    // Most recent download job id per model name, from DaemonUpdate::DownloadQueue,
+    // so the single cancel-download button can resolve the job to cancel.
+    let download_job_ids: Rc<RefCell<HashMap<String, u64>>> = Rc::new(RefCell::new(HashMap::new()));
+
+    // -- Output group --
+    let output_group = adw::PreferencesGroup::builder()
+        .title("Output")
+        .description("How transcribed text reaches the focused application")
+        .build();
+
+    let output_mode_model = gtk4::StringList::new(&["Keystrokes", "Clipboard Paste", "Both"]);
+    let output_mode_row = adw::ComboRow::builder()
+        .title("Mode")
+        .model(&output_mode_model)
+        .build();
+
+    let typing_delay_adjustment = gtk4::Adjustment::new(5.0, 0.0, 500.0, 1.0, 10.0, 0.0);
+    let typing_delay_row = adw::SpinRow::new(Some(&typing_delay_adjustment), 1.0, 0);
+    typing_delay_row.set_title("Inter-Keystroke Delay (ms)");
+    typing_delay_row.set_subtitle("Pace typed keystrokes for apps that drop fast input");
+
+    output_group.add(&output_mode_row);
+    output_group.add(&typing_delay_row);
+    groups_box.append(&output_group);
+
+    // Shared injection config state for building JSON from widgets
+    let injection_state = Rc::new(RefCell::new(voxkey_ipc::InjectionConfig::default()));
 
     // -- Advanced group --
     let advanced_group = adw::PreferencesGroup::builder()
@@ -224,7 +352,11 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
         .build();
 
     let hide_on_close_for_toggle = hide_on_close.clone();
+    let updating_widgets_for_hide_on_close = updating_widgets.clone();
     hide_on_close_row.connect_active_notify(move |row| {
+        if updating_widgets_for_hide_on_close.get() {
+            return;
+        }
         let value = row.is_active();
         hide_on_close_for_toggle.set(value);
         gui_settings::save_hide_on_close(value);
@@ -238,11 +370,34 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
     let quit_icon = gtk4::Image::from_icon_name("application-exit-symbolic");
     quit_row.add_suffix(&quit_icon);
 
+    let remote_daemon_row = adw::EntryRow::builder()
+        .title("Remote Daemon")
+        .text(&gui_settings::load_daemon_transport())
+        .show_apply_button(true)
+        .build();
+    let remote_daemon_group = adw::PreferencesGroup::builder()
+        .title("Remote Daemon")
+        .description(
+            "tcp:host=...,port=... or ssh://user@host to control a daemon on another machine. \
+             Blank connects to the local session bus. Takes effect after restarting the app.",
+        )
+        .build();
+    remote_daemon_group.add(&remote_daemon_row);
+
+    {
+        let toast_overlay = toast_overlay.clone();
+        remote_daemon_row.connect_apply(move |row| {
+            gui_settings::save_daemon_transport(&row.text());
+            toast_overlay.add_toast(adw::Toast::new("Restart the app to connect to the new daemon"));
+        });
+    }
+
     advanced_group.add(&hide_on_close_row);
     advanced_group.add(&reload_row);
     advanced_group.add(&clear_token_row);
     advanced_group.add(&quit_row);
     groups_box.append(&advanced_group);
+    groups_box.append(&remote_daemon_group);
 
     clamp.set_child(Some(&groups_box));
     scrolled.set_child(Some(&clamp));
@@ -284,9 +439,16 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
         &api_key_row, &model_row, &endpoint_row,
         &execution_provider_row, &model_status_row,
         &download_button, &delete_model_button, &open_folder_button,
-        &transcriber_state, &updating_widgets, &handle,
+        &download_progress_bar, &cancel_download_button,
+        &fallback_switches,
+        &transcriber_state, &updating_widgets, &handle, &catalog, &download_job_ids,
+        &available_execution_providers,
     );
     wire_advanced_actions(&reload_row, &clear_token_row, &handle, &toast_overlay);
+    wire_input_device_actions(&input_device_row, &input_devices, &updating_widgets, &handle);
+    wire_injection_actions(
+        &output_mode_row, &typing_delay_row, &injection_state, &updating_widgets, &handle,
+    );
 
     // -- Wire quit button --
     let handle_for_quit = handle.clone();
@@ -315,18 +477,35 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
     let current_trigger_update = current_trigger.clone();
     let transcript_buffer = transcript_buffer.clone();
     let provider_row_update = provider_row.clone();
+    let fallback_switches_update = fallback_switches.clone();
     let command_row_update = command_row.clone();
     let args_row_update = args_row.clone();
     let api_key_row_update = api_key_row.clone();
     let model_row_update = model_row.clone();
     let endpoint_row_update = endpoint_row.clone();
     let execution_provider_row_update = execution_provider_row.clone();
+    let available_execution_providers_poll = available_execution_providers.clone();
     let model_status_row_update = model_status_row.clone();
     let transcriber_state_update = transcriber_state.clone();
     let updating_widgets_poll = updating_widgets.clone();
     let banner = banner.clone();
     let toast_overlay_poll = toast_overlay.clone();
     let handle_poll = handle.clone();
+    let catalog_poll = catalog.clone();
+    let input_device_row_update = input_device_row.clone();
+    let input_devices_update = input_devices.clone();
+    let update_banner_poll = update_banner.clone();
+    let pending_update_model_poll = pending_update_model.clone();
+    let output_mode_row_update = output_mode_row.clone();
+    let typing_delay_row_update = typing_delay_row.clone();
+    let injection_state_update = injection_state.clone();
+    let download_button_update = download_button.clone();
+    let download_progress_bar_update = download_progress_bar.clone();
+    let cancel_download_button_update = cancel_download_button.clone();
+    let download_job_ids_update = download_job_ids.clone();
+    let transcriber_state_for_downloads = transcriber_state.clone();
+    let hide_on_close_update = hide_on_close.clone();
+    let hide_on_close_row_update = hide_on_close_row.clone();
 
     glib::timeout_add_local(Duration::from_millis(50), move || {
         while let Ok(update) = update_rx.try_recv() {
@@ -335,6 +514,7 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
                     state,
                     shortcut_trigger,
                     transcriber_config,
+                    injection_config,
                     portal_connected,
                     last_transcript,
                     last_error,
@@ -359,11 +539,27 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
                         &endpoint_row_update,
                         &execution_provider_row_update,
                         &model_status_row_update,
+                        &fallback_switches_update,
                         &transcriber_state_update,
                         &updating_widgets_poll,
+                        &catalog_poll,
+                        &handle_poll,
+                    );
+                    apply_injection_config_to_widgets(
+                        &injection_config,
+                        &output_mode_row_update,
+                        &typing_delay_row_update,
+                        &injection_state_update,
+                        &updating_widgets_poll,
                     );
                     if !last_error.is_empty() {
-                        toast_overlay_poll.add_toast(adw::Toast::new(&last_error));
+                        let action = daemon_client::infer_toast_action(&last_error);
+                        toast_overlay_poll.add_toast(build_error_toast(
+                            &last_error,
+                            action.as_ref(),
+                            &handle_poll,
+                            &catalog_poll,
+                        ));
                     }
                     // Query model status if Parakeet is active
                     if let Ok(tc) = serde_json::from_str::<voxkey_ipc::TranscriberConfig>(&transcriber_config) {
@@ -371,6 +567,7 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
                             handle_poll.send(DaemonCommand::ModelStatus(tc.parakeet.model.clone()));
                         }
                     }
+                    handle_poll.send(DaemonCommand::QueryExecutionProviders);
                 }
                 DaemonUpdate::Disconnected => {
                     banner.set_revealed(true);
@@ -380,15 +577,20 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
                 DaemonUpdate::StateChanged(state) => {
                     update_state_row(&state_row, &state_icon, &state);
                 }
+                DaemonUpdate::ErrorOccurred { message, action } => {
+                    if !message.is_empty() {
+                        toast_overlay_poll.add_toast(build_error_toast(
+                            &message,
+                            action.as_ref(),
+                            &handle_poll,
+                            &catalog_poll,
+                        ));
+                    }
+                }
                 DaemonUpdate::PropertyChanged { name, value } => match name.as_str() {
                     "last_transcript" => {
                         transcript_buffer.set_text(&value);
                     }
-                    "last_error" => {
-                        if !value.is_empty() {
-                            toast_overlay_poll.add_toast(adw::Toast::new(&value));
-                        }
-                    }
                     "portal_connected" => {
                         portal_row.set_subtitle(
                             if value == "true" { "Connected" } else { "Disconnected" },
@@ -411,25 +613,137 @@ pub fn build_window(app: &adw::Application) -> adw::ApplicationWindow {
                             &model_status_row_update,
                             &transcriber_state_update,
                             &updating_widgets_poll,
+                            &catalog_poll,
+                            &handle_poll,
                         );
                     }
+                    "injection_config" => {
+                        apply_injection_config_to_widgets(
+                            &value,
+                            &output_mode_row_update,
+                            &typing_delay_row_update,
+                            &injection_state_update,
+                            &updating_widgets_poll,
+                        );
+                    }
+                    "resolved_execution_provider" => {
+                        if value.is_empty() {
+                            execution_provider_row_update.set_subtitle("");
+                        } else {
+                            execution_provider_row_update
+                                .set_subtitle(&format!("Resolved to {value}"));
+                        }
+                    }
                     _ => {}
                 },
-                DaemonUpdate::DownloadProgress { model_name, percent } => {
-                    model_status_row_update.set_subtitle(
-                        &format!("Downloading {model_name}... {percent}%"),
+                DaemonUpdate::ExecutionProvidersResult(providers) => {
+                    *available_execution_providers_poll.borrow_mut() = providers;
+                }
+                DaemonUpdate::ModelsChanged(_) => {
+                    let model_name = transcriber_state_update.borrow().parakeet.model.clone();
+                    if !model_name.is_empty() {
+                        handle_poll.send(DaemonCommand::ModelStatus(model_name));
+                    }
+                }
+                DaemonUpdate::GuiSettingsChanged => {
+                    let value = gui_settings::load_hide_on_close();
+                    hide_on_close_update.set(value);
+                    updating_widgets_poll.set(true);
+                    hide_on_close_row_update.set_active(value);
+                    updating_widgets_poll.set(false);
+                }
+                DaemonUpdate::SecretLoaded { key, value } => {
+                    if !value.is_empty()
+                        && key == secret_account(transcriber_state_update.borrow().provider)
+                    {
+                        updating_widgets_poll.set(true);
+                        set_password_entry_text_without_apply(&api_key_row_update, &value);
+                        updating_widgets_poll.set(false);
+                    }
+                }
+                DaemonUpdate::InputDevices { devices, selected } => {
+                    updating_widgets_poll.set(true);
+                    let model = gtk4::StringList::new(
+                        &devices.iter().map(String::as_str).collect::<Vec<_>>(),
                     );
-                    if percent >= 100 {
-                        model_status_row_update.set_subtitle("Available");
+                    input_device_row_update.set_model(Some(&model));
+                    let selected_idx = devices.iter().position(|d| *d == selected).unwrap_or(0) as u32;
+                    input_device_row_update.set_selected(selected_idx);
+                    *input_devices_update.borrow_mut() = devices;
+                    updating_widgets_poll.set(false);
+                }
+                DaemonUpdate::DownloadQueue(jobs) => {
+                    {
+                        let mut job_ids = download_job_ids_update.borrow_mut();
+                        for job in &jobs {
+                            job_ids.insert(job.model_name.clone(), job.job_id);
+                        }
+                    }
+
+                    let current_model = transcriber_state_for_downloads.borrow().parakeet.model.clone();
+                    let Some(job) = jobs.iter().rev().find(|j| j.model_name == current_model) else {
+                        continue;
+                    };
+
+                    match job.state {
+                        voxkey_ipc::DownloadJobState::Pending | voxkey_ipc::DownloadJobState::InProgress => {
+                            download_button_update.set_sensitive(false);
+                            execution_provider_row_update.set_sensitive(false);
+                            download_progress_bar_update.set_visible(true);
+                            cancel_download_button_update.set_visible(true);
+                            download_progress_bar_update.set_fraction(job.percent as f64 / 100.0);
+                            let downloaded_mb = job.downloaded_bytes as f64 / 1_000_000.0;
+                            let total_mb = job.total_bytes as f64 / 1_000_000.0;
+                            let throughput_mb = job.bytes_per_sec / 1_000_000.0;
+                            model_status_row_update.set_subtitle(&format!(
+                                "Downloading {current_model}\u{2026} {}% ({downloaded_mb:.1}/{total_mb:.1} MB, {throughput_mb:.1} MB/s)",
+                                job.percent,
+                            ));
+                        }
+                        voxkey_ipc::DownloadJobState::Verifying => {
+                            download_progress_bar_update.set_fraction(1.0);
+                            model_status_row_update.set_subtitle("Verifying\u{2026}");
+                            handle_poll.send(DaemonCommand::ModelStatus(current_model));
+                        }
+                        voxkey_ipc::DownloadJobState::Complete
+                        | voxkey_ipc::DownloadJobState::Failed
+                        | voxkey_ipc::DownloadJobState::ChecksumFailed
+                        | voxkey_ipc::DownloadJobState::Cancelled => {
+                            download_button_update.set_sensitive(true);
+                            execution_provider_row_update.set_sensitive(true);
+                            download_progress_bar_update.set_visible(false);
+                            cancel_download_button_update.set_visible(false);
+                            handle_poll.send(DaemonCommand::ModelStatus(current_model));
+                        }
                     }
                 }
-                DaemonUpdate::ModelStatusResult { status, .. } => {
+                DaemonUpdate::ModelStatusResult { model_name, status } => {
                     let label = match status.as_str() {
                         "available" => "Available",
+                        "verifying" => "Verifying...",
+                        "checksum_failed" => "Checksum failed",
                         "downloading" => "Downloading...",
                         _ => "Not downloaded",
                     };
                     model_status_row_update.set_subtitle(label);
+
+                    let still_downloading = status == "downloading";
+                    download_button_update.set_sensitive(!still_downloading);
+                    execution_provider_row_update.set_sensitive(!still_downloading);
+                    download_progress_bar_update.set_visible(still_downloading);
+                    cancel_download_button_update.set_visible(still_downloading);
+
+                    if status == "available"
+                        && pending_update_model_poll.borrow().as_deref() == Some(model_name.as_str())
+                    {
+                        *pending_update_model_poll.borrow_mut() = None;
+                        update_banner_poll.set_revealed(false);
+                    }
+                }
+                DaemonUpdate::UpdateAvailable { model_name, display_name } => {
+                    *pending_update_model_poll.borrow_mut() = Some(model_name);
+                    update_banner_poll.set_title(&format!("Update available for {display_name}"));
+                    update_banner_poll.set_revealed(true);
                 }
             }
         }
@@ -455,6 +769,44 @@ fn update_state_row(row: &adw::ActionRow, icon: &gtk4::Image, state: &str) {
     }
 }
 
+/// Build a toast for an error message, wiring up a recovery button when the
+/// daemon suggested one.
+fn build_error_toast(
+    message: &str,
+    action: Option<&daemon_client::ToastAction>,
+    handle: &DaemonHandle,
+    catalog: &Rc<Vec<ProviderDescriptor>>,
+) -> adw::Toast {
+    let toast = adw::Toast::new(message);
+    match action {
+        Some(daemon_client::ToastAction::RetryDownload(model_name)) => {
+            toast.set_button_label(Some("Retry"));
+            let handle = handle.clone();
+            let catalog = catalog.clone();
+            let model_name = model_name.clone();
+            toast.connect_button_clicked(move |_| {
+                let entry = catalog.iter().find(|d| d.id == model_name);
+                let url = entry.map(|d| d.download_url.clone()).unwrap_or_default();
+                let sha256 = entry.map(|d| d.sha256.clone()).unwrap_or_default();
+                handle.send(DaemonCommand::DownloadModel {
+                    model_name: model_name.clone(),
+                    url,
+                    sha256,
+                });
+            });
+        }
+        Some(daemon_client::ToastAction::ReconnectPortal) => {
+            toast.set_button_label(Some("Reconnect"));
+            let handle = handle.clone();
+            toast.connect_button_clicked(move |_| {
+                handle.send(DaemonCommand::RestartSession);
+            });
+        }
+        None => {}
+    }
+    toast
+}
+
 /// Convert a GDK key + modifiers into the portal trigger format: "<Control><Alt>d"
 fn key_to_trigger(key: gdk::Key, modifiers: gdk::ModifierType) -> Option<String> {
     // Ignore lone modifier presses
@@ -612,6 +964,71 @@ fn set_password_entry_text_without_apply(row: &adw::PasswordEntryRow, text: &str
     row.set_show_apply_button(true);
 }
 
+/// The catalog entry id that corresponds to the active transcriber config,
+/// matching the ids `catalog::default_catalog` assigns to each provider (and,
+/// for Parakeet, to each model variant).
+fn active_catalog_id(tc: &voxkey_ipc::TranscriberConfig) -> String {
+    match tc.provider {
+        voxkey_ipc::TranscriberProvider::WhisperCpp => "whisper-cpp".to_string(),
+        voxkey_ipc::TranscriberProvider::Mistral => "mistral".to_string(),
+        voxkey_ipc::TranscriberProvider::MistralRealtime => "mistral-realtime".to_string(),
+        voxkey_ipc::TranscriberProvider::Parakeet => tc.parakeet.model.clone(),
+        voxkey_ipc::TranscriberProvider::OpenAiCompatible => "openai-compatible".to_string(),
+        voxkey_ipc::TranscriberProvider::ParakeetStreaming => "parakeet-streaming".to_string(),
+        voxkey_ipc::TranscriberProvider::WhisperCandle => "whisper-candle".to_string(),
+    }
+}
+
+/// Execution provider combo entries, in display order, paired with the
+/// variant they represent and the label shown in the combo.
+const EXECUTION_PROVIDER_COMBO: &[(voxkey_ipc::ExecutionProviderChoice, &str)] = &[
+    (voxkey_ipc::ExecutionProviderChoice::Auto, "Auto"),
+    (voxkey_ipc::ExecutionProviderChoice::Cpu, "CPU"),
+    (voxkey_ipc::ExecutionProviderChoice::Cuda, "CUDA"),
+    (voxkey_ipc::ExecutionProviderChoice::TensorRt, "TensorRT"),
+    (voxkey_ipc::ExecutionProviderChoice::CoreMl, "CoreML"),
+    (voxkey_ipc::ExecutionProviderChoice::DirectMl, "DirectML"),
+    (voxkey_ipc::ExecutionProviderChoice::Rocm, "ROCm"),
+];
+
+fn execution_provider_combo_index(choice: voxkey_ipc::ExecutionProviderChoice) -> u32 {
+    EXECUTION_PROVIDER_COMBO
+        .iter()
+        .position(|(c, _)| *c == choice)
+        .unwrap_or(0) as u32
+}
+
+fn execution_provider_from_combo_index(idx: u32) -> voxkey_ipc::ExecutionProviderChoice {
+    EXECUTION_PROVIDER_COMBO
+        .get(idx as usize)
+        .map(|(c, _)| *c)
+        .unwrap_or(voxkey_ipc::ExecutionProviderChoice::Auto)
+}
+
+/// Providers eligible for the fallback chain, in priority order. Streaming
+/// providers (`MistralRealtime`, `ParakeetStreaming`) are omitted: the batch
+/// `stop_recording` flow that drives fallback can't host a streaming session.
+const FALLBACK_PROVIDER_COMBO: &[(voxkey_ipc::TranscriberProvider, &str)] = &[
+    (voxkey_ipc::TranscriberProvider::WhisperCpp, "whisper.cpp"),
+    (voxkey_ipc::TranscriberProvider::Mistral, "Mistral"),
+    (voxkey_ipc::TranscriberProvider::Parakeet, "Parakeet"),
+    (voxkey_ipc::TranscriberProvider::OpenAiCompatible, "OpenAI-compatible"),
+    (voxkey_ipc::TranscriberProvider::WhisperCandle, "Whisper (candle)"),
+];
+
+/// The secret-store account label for the active provider's API key.
+fn secret_account(provider: voxkey_ipc::TranscriberProvider) -> &'static str {
+    match provider {
+        voxkey_ipc::TranscriberProvider::MistralRealtime => {
+            voxkey_ipc::MistralRealtimeConfig::SECRET_ACCOUNT
+        }
+        voxkey_ipc::TranscriberProvider::OpenAiCompatible => {
+            voxkey_ipc::OpenAiCompatibleConfig::SECRET_ACCOUNT
+        }
+        _ => voxkey_ipc::MistralConfig::SECRET_ACCOUNT,
+    }
+}
+
 /// Parse transcriber config JSON and update all transcriber widgets.
 fn apply_transcriber_config_to_widgets(
     config_json: &str,
@@ -623,8 +1040,11 @@ fn apply_transcriber_config_to_widgets(
     endpoint_row: &adw::EntryRow,
     execution_provider_row: &adw::ComboRow,
     model_status_row: &adw::ActionRow,
+    fallback_switches: &[adw::SwitchRow],
     state: &Rc<RefCell<voxkey_ipc::TranscriberConfig>>,
     updating_widgets: &Rc<Cell<bool>>,
+    catalog: &[ProviderDescriptor],
+    handle: &DaemonHandle,
 ) {
     let Ok(tc) = serde_json::from_str::<voxkey_ipc::TranscriberConfig>(config_json) else {
         return;
@@ -637,14 +1057,10 @@ fn apply_transcriber_config_to_widgets(
     // connect_selected_notify which reads from state — it must see current values.
     *state.borrow_mut() = tc.clone();
 
-    let provider_idx = match tc.provider {
-        voxkey_ipc::TranscriberProvider::WhisperCpp => 0u32,
-        voxkey_ipc::TranscriberProvider::Mistral => 1,
-        voxkey_ipc::TranscriberProvider::MistralRealtime => 2,
-        voxkey_ipc::TranscriberProvider::Parakeet => {
-            if tc.parakeet.model == "parakeet-tdt-0.6b-v2" { 3 } else { 4 }
-        }
-    };
+    let provider_idx = catalog
+        .iter()
+        .position(|d| d.id == active_catalog_id(&tc))
+        .unwrap_or(0) as u32;
     provider_row.set_selected(provider_idx);
 
     // Set entry text and reset the "applied text" baseline so the apply button
@@ -664,6 +1080,10 @@ fn apply_transcriber_config_to_widgets(
                 (&tc.mistral_realtime.api_key, &tc.mistral_realtime.model, &tc.mistral_realtime.endpoint,
                  voxkey_ipc::MistralRealtimeConfig::DEFAULT_MODEL, voxkey_ipc::MistralRealtimeConfig::DEFAULT_ENDPOINT)
             }
+            voxkey_ipc::TranscriberProvider::OpenAiCompatible => {
+                (&tc.openai_compatible.api_key, &tc.openai_compatible.model, &tc.openai_compatible.endpoint,
+                 "", voxkey_ipc::OpenAiCompatibleConfig::DEFAULT_ENDPOINT)
+            }
             _ => {
                 (&tc.mistral.api_key, &tc.mistral.model, &tc.mistral.endpoint,
                  voxkey_ipc::MistralConfig::DEFAULT_MODEL, voxkey_ipc::MistralConfig::DEFAULT_ENDPOINT)
@@ -672,15 +1092,18 @@ fn apply_transcriber_config_to_widgets(
         set_password_entry_text_without_apply(api_key_row, active_api_key);
         set_entry_with_default(model_row, active_model, default_model);
         set_entry_with_default(endpoint_row, active_endpoint, default_endpoint);
+
+        // The config carries a blank api_key once the daemon has a matching
+        // secret-store entry (see `secret_fields_mut`); fetch the real value
+        // to repopulate the password field.
+        if active_api_key.is_empty() {
+            handle.send(DaemonCommand::LoadSecret(secret_account(tc.provider).to_string()));
+        }
     }
 
     if is_parakeet {
-        let ep_idx = match tc.parakeet.execution_provider {
-            voxkey_ipc::ExecutionProviderChoice::Auto => 0u32,
-            voxkey_ipc::ExecutionProviderChoice::Cpu => 1,
-            voxkey_ipc::ExecutionProviderChoice::Cuda => 2,
-        };
-        execution_provider_row.set_selected(ep_idx);
+        execution_provider_row
+            .set_selected(execution_provider_combo_index(tc.parakeet.execution_provider));
     }
 
     // Toggle visibility
@@ -692,6 +1115,10 @@ fn apply_transcriber_config_to_widgets(
     execution_provider_row.set_visible(is_parakeet);
     model_status_row.set_visible(is_parakeet);
 
+    for (switch_row, (provider, _)) in fallback_switches.iter().zip(FALLBACK_PROVIDER_COMBO) {
+        switch_row.set_active(tc.fallback.contains(provider));
+    }
+
     updating_widgets.set(false);
 }
 
@@ -703,6 +1130,81 @@ fn send_transcriber_config(state: &Rc<RefCell<voxkey_ipc::TranscriberConfig>>, h
     }
 }
 
+/// Build the current InjectionConfig from shared state and send it to the daemon.
+fn send_injection_config(state: &Rc<RefCell<voxkey_ipc::InjectionConfig>>, handle: &DaemonHandle) {
+    let config = state.borrow().clone();
+    if let Ok(json) = serde_json::to_string(&config) {
+        handle.send(DaemonCommand::SetInjectionConfig(json));
+    }
+}
+
+/// Parse injection config JSON and update the Output group widgets.
+fn apply_injection_config_to_widgets(
+    config_json: &str,
+    output_mode_row: &adw::ComboRow,
+    typing_delay_row: &adw::SpinRow,
+    state: &Rc<RefCell<voxkey_ipc::InjectionConfig>>,
+    updating_widgets: &Rc<Cell<bool>>,
+) {
+    let Ok(ic) = serde_json::from_str::<voxkey_ipc::InjectionConfig>(config_json) else {
+        return;
+    };
+
+    updating_widgets.set(true);
+    *state.borrow_mut() = ic.clone();
+
+    let mode_idx = match ic.mode {
+        voxkey_ipc::OutputMode::Keystrokes => 0u32,
+        voxkey_ipc::OutputMode::ClipboardPaste => 1,
+        voxkey_ipc::OutputMode::Both => 2,
+    };
+    output_mode_row.set_selected(mode_idx);
+    typing_delay_row.set_value(ic.typing_delay_ms as f64);
+
+    updating_widgets.set(false);
+}
+
+/// Wire the Output group's mode combo and typing-delay spin row to send a
+/// `SetInjectionConfig` update on change, guarded by `updating_widgets` so
+/// programmatic updates from the daemon don't echo back as a command.
+fn wire_injection_actions(
+    output_mode_row: &adw::ComboRow,
+    typing_delay_row: &adw::SpinRow,
+    state: &Rc<RefCell<voxkey_ipc::InjectionConfig>>,
+    updating_widgets: &Rc<Cell<bool>>,
+    handle: &DaemonHandle,
+) {
+    {
+        let state = state.clone();
+        let handle = handle.clone();
+        let updating_widgets = updating_widgets.clone();
+        output_mode_row.connect_selected_notify(move |row| {
+            if updating_widgets.get() {
+                return;
+            }
+            state.borrow_mut().mode = match row.selected() {
+                1 => voxkey_ipc::OutputMode::ClipboardPaste,
+                2 => voxkey_ipc::OutputMode::Both,
+                _ => voxkey_ipc::OutputMode::Keystrokes,
+            };
+            send_injection_config(&state, &handle);
+        });
+    }
+
+    {
+        let state = state.clone();
+        let handle = handle.clone();
+        let updating_widgets = updating_widgets.clone();
+        typing_delay_row.adjustment().connect_value_changed(move |adj| {
+            if updating_widgets.get() {
+                return;
+            }
+            state.borrow_mut().typing_delay_ms = adj.value() as u32;
+            send_injection_config(&state, &handle);
+        });
+    }
+}
+
 fn wire_transcriber_actions(
     provider_row: &adw::ComboRow,
     command_row: &adw::EntryRow,
@@ -715,9 +1217,15 @@ fn wire_transcriber_actions(
     download_button: &gtk4::Button,
     delete_model_button: &gtk4::Button,
     open_folder_button: &gtk4::Button,
+    download_progress_bar: &gtk4::ProgressBar,
+    cancel_download_button: &gtk4::Button,
+    fallback_switches: &[adw::SwitchRow],
     state: &Rc<RefCell<voxkey_ipc::TranscriberConfig>>,
     updating_widgets: &Rc<Cell<bool>>,
     handle: &DaemonHandle,
+    catalog: &Rc<Vec<ProviderDescriptor>>,
+    download_job_ids: &Rc<RefCell<HashMap<String, u64>>>,
+    available_execution_providers: &Rc<RefCell<Vec<String>>>,
 ) {
     // Provider combo: toggle visibility, update fields, and send config
     {
@@ -731,25 +1239,23 @@ fn wire_transcriber_actions(
         let state = state.clone();
         let updating_widgets = updating_widgets.clone();
         let handle = handle.clone();
+        let catalog = catalog.clone();
         provider_row.connect_selected_notify(move |row| {
             if updating_widgets.get() {
                 return;
             }
-            let selected = row.selected();
-            let is_parakeet = selected == 3 || selected == 4;
+            let selected = row.selected() as usize;
+            let Some(entry) = catalog.get(selected) else { return };
+            let is_parakeet = entry.provider == "parakeet";
 
             if is_parakeet {
-                let model_name = if selected == 3 {
-                    "parakeet-tdt-0.6b-v2"
-                } else {
-                    "parakeet-tdt-0.6b-v3"
-                };
                 state.borrow_mut().provider = voxkey_ipc::TranscriberProvider::Parakeet;
-                state.borrow_mut().parakeet.model = model_name.to_string();
+                state.borrow_mut().parakeet.model = entry.id.clone();
             } else {
-                let provider = match selected {
-                    0 => voxkey_ipc::TranscriberProvider::WhisperCpp,
-                    2 => voxkey_ipc::TranscriberProvider::MistralRealtime,
+                let provider = match entry.provider.as_str() {
+                    "whisper-cpp" => voxkey_ipc::TranscriberProvider::WhisperCpp,
+                    "mistral-realtime" => voxkey_ipc::TranscriberProvider::MistralRealtime,
+                    "openai-compatible" => voxkey_ipc::TranscriberProvider::OpenAiCompatible,
                     _ => voxkey_ipc::TranscriberProvider::Mistral,
                 };
                 state.borrow_mut().provider = provider;
@@ -768,14 +1274,20 @@ fn wire_transcriber_actions(
             model_status_row.set_visible(is_parakeet);
 
             if is_mistral_api {
-                let is_realtime = provider == voxkey_ipc::TranscriberProvider::MistralRealtime;
                 let st = state.borrow();
-                let (key, model, endpoint, default_model, default_endpoint) = if is_realtime {
-                    (&st.mistral_realtime.api_key, &st.mistral_realtime.model, &st.mistral_realtime.endpoint,
-                     voxkey_ipc::MistralRealtimeConfig::DEFAULT_MODEL, voxkey_ipc::MistralRealtimeConfig::DEFAULT_ENDPOINT)
-                } else {
-                    (&st.mistral.api_key, &st.mistral.model, &st.mistral.endpoint,
-                     voxkey_ipc::MistralConfig::DEFAULT_MODEL, voxkey_ipc::MistralConfig::DEFAULT_ENDPOINT)
+                let (key, model, endpoint, default_model, default_endpoint) = match provider {
+                    voxkey_ipc::TranscriberProvider::MistralRealtime => {
+                        (&st.mistral_realtime.api_key, &st.mistral_realtime.model, &st.mistral_realtime.endpoint,
+                         voxkey_ipc::MistralRealtimeConfig::DEFAULT_MODEL, voxkey_ipc::MistralRealtimeConfig::DEFAULT_ENDPOINT)
+                    }
+                    voxkey_ipc::TranscriberProvider::OpenAiCompatible => {
+                        (&st.openai_compatible.api_key, &st.openai_compatible.model, &st.openai_compatible.endpoint,
+                         "", voxkey_ipc::OpenAiCompatibleConfig::DEFAULT_ENDPOINT)
+                    }
+                    _ => {
+                        (&st.mistral.api_key, &st.mistral.model, &st.mistral.endpoint,
+                         voxkey_ipc::MistralConfig::DEFAULT_MODEL, voxkey_ipc::MistralConfig::DEFAULT_ENDPOINT)
+                    }
                 };
                 set_password_entry_text_without_apply(&api_key_row, key);
                 set_entry_with_default(&model_row, model, default_model);
@@ -821,13 +1333,24 @@ fn wire_transcriber_actions(
         let handle = handle.clone();
         api_key_row.connect_apply(move |row| {
             let key = row.text().to_string();
+            let account = secret_account(state.borrow().provider);
+            if key.is_empty() {
+                handle.send(DaemonCommand::ClearSecret(account.to_string()));
+            } else {
+                handle.send(DaemonCommand::StoreSecret { key: account.to_string(), value: key });
+            }
+            // The key travels to the daemon out-of-band via the secret-store
+            // commands above; keep it out of the config payload itself.
             let mut st = state.borrow_mut();
             match st.provider {
                 voxkey_ipc::TranscriberProvider::MistralRealtime => {
-                    st.mistral_realtime.api_key = key;
+                    st.mistral_realtime.api_key = String::new();
+                }
+                voxkey_ipc::TranscriberProvider::OpenAiCompatible => {
+                    st.openai_compatible.api_key = String::new();
                 }
                 _ => {
-                    st.mistral.api_key = key;
+                    st.mistral.api_key = String::new();
                 }
             }
             drop(st);
@@ -846,6 +1369,9 @@ fn wire_transcriber_actions(
                 voxkey_ipc::TranscriberProvider::MistralRealtime => {
                     st.mistral_realtime.model = model;
                 }
+                voxkey_ipc::TranscriberProvider::OpenAiCompatible => {
+                    st.openai_compatible.model = model;
+                }
                 _ => {
                     st.mistral.model = model;
                 }
@@ -867,6 +1393,10 @@ fn wire_transcriber_actions(
                     let default = voxkey_ipc::MistralRealtimeConfig::DEFAULT_ENDPOINT;
                     st.mistral_realtime.endpoint = if raw == default { String::new() } else { raw };
                 }
+                voxkey_ipc::TranscriberProvider::OpenAiCompatible => {
+                    let default = voxkey_ipc::OpenAiCompatibleConfig::DEFAULT_ENDPOINT;
+                    st.openai_compatible.endpoint = if raw == default { String::new() } else { raw };
+                }
                 _ => {
                     let default = voxkey_ipc::MistralConfig::DEFAULT_ENDPOINT;
                     st.mistral.endpoint = if raw == default { String::new() } else { raw };
@@ -882,27 +1412,94 @@ fn wire_transcriber_actions(
         let state = state.clone();
         let handle = handle.clone();
         let updating_widgets = updating_widgets.clone();
+        let available_execution_providers = available_execution_providers.clone();
         execution_provider_row.connect_selected_notify(move |row| {
             if updating_widgets.get() {
                 return;
             }
-            let ep = match row.selected() {
-                1 => voxkey_ipc::ExecutionProviderChoice::Cpu,
-                2 => voxkey_ipc::ExecutionProviderChoice::Cuda,
-                _ => voxkey_ipc::ExecutionProviderChoice::Auto,
-            };
+            let ep = execution_provider_from_combo_index(row.selected());
+            let available = available_execution_providers.borrow();
+            if ep != voxkey_ipc::ExecutionProviderChoice::Auto
+                && !available.is_empty()
+                && !available.iter().any(|p| p == ep.config_name())
+            {
+                row.set_subtitle(&format!("{} is not available on this machine", ep.config_name()));
+                updating_widgets.set(true);
+                row.set_selected(execution_provider_combo_index(state.borrow().parakeet.execution_provider));
+                updating_widgets.set(false);
+                return;
+            }
+            drop(available);
+            row.set_subtitle("");
             state.borrow_mut().parakeet.execution_provider = ep;
             send_transcriber_config(&state, &handle);
         });
     }
 
+    // Fallback provider switches: rebuild the fallback Vec from whichever
+    // switches are active, preserving FALLBACK_PROVIDER_COMBO's priority order.
+    for switch_row in fallback_switches {
+        let state = state.clone();
+        let updating_widgets = updating_widgets.clone();
+        let handle = handle.clone();
+        let fallback_switches = fallback_switches.to_vec();
+        switch_row.connect_active_notify(move |_| {
+            if updating_widgets.get() {
+                return;
+            }
+            state.borrow_mut().fallback = FALLBACK_PROVIDER_COMBO
+                .iter()
+                .zip(&fallback_switches)
+                .filter(|(_, row)| row.is_active())
+                .map(|((provider, _), _)| *provider)
+                .collect();
+            send_transcriber_config(&state, &handle);
+        });
+    }
+
     // Download button
     {
         let state = state.clone();
         let handle = handle.clone();
-        download_button.connect_clicked(move |_| {
+        let catalog = catalog.clone();
+        let execution_provider_row = execution_provider_row.clone();
+        let download_progress_bar = download_progress_bar.clone();
+        let cancel_download_button = cancel_download_button.clone();
+        download_button.connect_clicked(move |button| {
             let model_name = state.borrow().parakeet.model.clone();
-            handle.send(DaemonCommand::DownloadModel(model_name));
+            let entry = catalog.iter().find(|d| d.id == model_name);
+            let url = entry.map(|d| d.download_url.clone()).unwrap_or_default();
+            let sha256 = entry.map(|d| d.sha256.clone()).unwrap_or_default();
+            handle.send(DaemonCommand::DownloadModel { model_name, url, sha256 });
+
+            button.set_sensitive(false);
+            execution_provider_row.set_sensitive(false);
+            download_progress_bar.set_fraction(0.0);
+            download_progress_bar.set_visible(true);
+            cancel_download_button.set_visible(true);
+        });
+    }
+
+    // Cancel download button
+    {
+        let state = state.clone();
+        let handle = handle.clone();
+        let model_status_row = model_status_row.clone();
+        let download_button = download_button.clone();
+        let execution_provider_row = execution_provider_row.clone();
+        let download_progress_bar = download_progress_bar.clone();
+        let download_job_ids = download_job_ids.clone();
+        cancel_download_button.connect_clicked(move |button| {
+            let model_name = state.borrow().parakeet.model.clone();
+            if let Some(job_id) = download_job_ids.borrow().get(&model_name) {
+                handle.send(DaemonCommand::CancelDownload(*job_id));
+            }
+
+            model_status_row.set_subtitle("Not downloaded");
+            download_progress_bar.set_visible(false);
+            button.set_visible(false);
+            download_button.set_sensitive(true);
+            execution_provider_row.set_sensitive(true);
         });
     }
 
@@ -947,3 +1544,26 @@ fn wire_advanced_actions(
         toast_clone.add_toast(adw::Toast::new("Portal token cleared"));
     });
 }
+
+/// Wire the microphone combo row to send `SetInputDevice` on selection,
+/// guarded by `updating_widgets` so rebuilding the list on a hotplug update
+/// doesn't echo back as a command.
+fn wire_input_device_actions(
+    input_device_row: &adw::ComboRow,
+    input_devices: &Rc<RefCell<Vec<String>>>,
+    updating_widgets: &Rc<Cell<bool>>,
+    handle: &DaemonHandle,
+) {
+    let input_devices = input_devices.clone();
+    let updating_widgets = updating_widgets.clone();
+    let handle = handle.clone();
+    input_device_row.connect_selected_notify(move |row| {
+        if updating_widgets.get() {
+            return;
+        }
+        let Some(device) = input_devices.borrow().get(row.selected() as usize).cloned() else {
+            return;
+        };
+        handle.send(DaemonCommand::SetInputDevice(device));
+    });
+}